@@ -9,9 +9,16 @@ use bevy::{
     ui::ui_layout_system,
 };
 use bevy_editor_camera::{EditorCamera2d, EditorCamera2dPlugin};
-use bevy_editor_styles::Theme;
+use bevy_editor_core::{actions, EditorKeybindings, EditorRenderLayers};
+use bevy_editor_styles::{icons, StylesPlugin, Theme};
 use bevy_infinite_grid::{InfiniteGrid, InfiniteGridPlugin, InfiniteGridSettings};
 use bevy_pane_layout::prelude::*;
+use pointer::update_viewport_pointer;
+
+pub use bevy_infinite_grid::GridStyle;
+pub use pointer::{Viewport2dPointer, Viewport2dPointerPosition};
+
+mod pointer;
 
 /// The identifier for the 2D Viewport.
 /// This is present on any pane that is a 2D Viewport.
@@ -36,30 +43,64 @@ impl Plugin for Viewport2dPanePlugin {
         if !app.is_plugin_added::<InfiniteGridPlugin>() {
             app.add_plugins(InfiniteGridPlugin);
         }
+        if !app.is_plugin_added::<StylesPlugin>() {
+            app.add_plugins(StylesPlugin);
+        }
         app.add_plugins(EditorCamera2dPlugin)
+            .init_resource::<EditorRenderLayers>()
+            .init_resource::<EditorKeybindings>()
+            .init_resource::<Viewport2dGridStyle>()
+            .init_resource::<Viewport2dPointer>()
             .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    (update_theme, update_clear_color).run_if(resource_changed::<Theme>),
+                    update_render_layers.run_if(resource_changed::<EditorRenderLayers>),
+                    update_grid_style.run_if(resource_changed::<Viewport2dGridStyle>),
+                    apply_viewport_background,
+                    reset_view_shortcut,
+                ),
+            )
             .add_systems(
                 PostUpdate,
-                update_render_target_size.after(ui_layout_system),
+                (
+                    update_render_target_size.after(ui_layout_system),
+                    update_viewport_pointer,
+                )
+                    .chain(),
             )
-            .add_observer(
-                |trigger: On<Remove, Bevy2dViewport>,
-                 mut commands: Commands,
-                 query: Query<&Bevy2dViewport>| {
-                    // Despawn the viewport camera
-                    commands
-                        .entity(query.get(trigger.target()).unwrap().camera_id)
-                        .despawn();
-                },
-            );
+            .add_observer(despawn_viewport_camera)
+            .add_observer(restore_theme_background);
 
-        app.register_pane("Viewport 2D", on_pane_creation);
+        app.register_pane_with_tab("Viewport 2D", PaneTab::new(icons::FRAME), on_pane_creation);
     }
 }
 
-fn setup(mut commands: Commands, theme: Res<Theme>) {
+/// How the grid behind the 2D viewport is drawn.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport2dGridStyle(pub GridStyle);
+
+/// Overrides the background color of the 2D viewport pane it is on, instead of the viewport
+/// background color of the [`Theme`].
+///
+/// Insert it on the pane root, next to the [`Bevy2dViewport`]. Removing it goes back to the theme.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Viewport2dBackground(pub Color);
+
+/// Marker for the grid spawned by the 2D viewport, to tell it apart from grids in user content.
+#[derive(Component)]
+struct ViewportGrid;
+
+fn setup(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    render_layers: Res<EditorRenderLayers>,
+    grid_style: Res<Viewport2dGridStyle>,
+) {
     commands.spawn((
         InfiniteGrid,
+        ViewportGrid,
         InfiniteGridSettings {
             scale: 100.,
             dot_fadeout_strength: 0.,
@@ -67,18 +108,159 @@ fn setup(mut commands: Commands, theme: Res<Theme>) {
             z_axis_color: theme.viewport.y_axis_color,
             major_line_color: theme.viewport.grid_major_line_color,
             minor_line_color: theme.viewport.grid_minor_line_color,
+            style: grid_style.0,
             ..default()
         },
         Transform::from_rotation(Quat::from_rotation_arc(Vec3::Y, Vec3::Z)),
-        RenderLayers::layer(2),
+        RenderLayers::layer(render_layers.grid_2d),
     ));
 }
 
+/// Applies the current [`Theme`] to the viewport grid, so theme changes take effect without
+/// reopening the viewports.
+fn update_theme(
+    theme: Res<Theme>,
+    mut grids: Query<&mut InfiniteGridSettings, With<ViewportGrid>>,
+) {
+    for mut settings in &mut grids {
+        settings.x_axis_color = theme.viewport.x_axis_color;
+        settings.z_axis_color = theme.viewport.y_axis_color;
+        settings.major_line_color = theme.viewport.grid_major_line_color;
+        settings.minor_line_color = theme.viewport.grid_minor_line_color;
+    }
+}
+
+/// Applies the viewport background color of the [`Theme`] to the viewport cameras.
+///
+/// Only changes to that color are applied, so a camera given another clear color keeps it when
+/// other parts of the theme change.
+fn update_clear_color(
+    theme: Res<Theme>,
+    mut background_color: Local<Option<Color>>,
+    viewports: Query<&Bevy2dViewport, Without<Viewport2dBackground>>,
+    mut cameras: Query<&mut Camera>,
+) {
+    if *background_color == Some(theme.viewport.background_color) {
+        return;
+    }
+    *background_color = Some(theme.viewport.background_color);
+
+    for viewport in &viewports {
+        if let Ok(mut camera) = cameras.get_mut(viewport.camera_id) {
+            camera.clear_color = ClearColorConfig::Custom(theme.viewport.background_color);
+        }
+    }
+}
+
+/// Applies the [`Viewport2dBackground`] of viewports to their camera, when it is added or changed,
+/// or when the viewport camera is spawned.
+fn apply_viewport_background(
+    viewports: Query<
+        (&Bevy2dViewport, &Viewport2dBackground),
+        Or<(Changed<Viewport2dBackground>, Changed<Bevy2dViewport>)>,
+    >,
+    mut cameras: Query<&mut Camera>,
+) {
+    for (viewport, background) in &viewports {
+        if let Ok(mut camera) = cameras.get_mut(viewport.camera_id) {
+            camera.clear_color = ClearColorConfig::Custom(background.0);
+        }
+    }
+}
+
+/// Gives the camera of a viewport the background color of the [`Theme`] back when its
+/// [`Viewport2dBackground`] is removed.
+fn restore_theme_background(
+    trigger: On<Remove, Viewport2dBackground>,
+    theme: Res<Theme>,
+    viewports: Query<&Bevy2dViewport>,
+    mut cameras: Query<&mut Camera>,
+) {
+    let Ok(viewport) = viewports.get(trigger.target()) else {
+        return;
+    };
+    if let Ok(mut camera) = cameras.get_mut(viewport.camera_id) {
+        camera.clear_color = ClearColorConfig::Custom(theme.viewport.background_color);
+    }
+}
+
+fn update_grid_style(
+    grid_style: Res<Viewport2dGridStyle>,
+    mut grids: Query<&mut InfiniteGridSettings, With<ViewportGrid>>,
+) {
+    for mut settings in &mut grids {
+        settings.style = grid_style.0;
+    }
+}
+
+fn update_render_layers(
+    render_layers: Res<EditorRenderLayers>,
+    mut grids: Query<&mut RenderLayers, With<ViewportGrid>>,
+    viewports: Query<&Bevy2dViewport>,
+    mut cameras: Query<&mut RenderLayers, Without<ViewportGrid>>,
+) {
+    for mut layers in &mut grids {
+        *layers = RenderLayers::layer(render_layers.grid_2d);
+    }
+
+    for viewport in &viewports {
+        if let Ok(mut layers) = cameras.get_mut(viewport.camera_id) {
+            *layers = render_layers.viewport_2d_camera();
+        }
+    }
+}
+
+/// The reset view action, `Home` by default, moves the camera of the hovered 2D viewport back to
+/// the origin at a scale of 1, however far it was panned and zoomed.
+fn reset_view_shortcut(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    viewports: Query<&Bevy2dViewport>,
+    mut cameras: Query<(&EditorCamera2d, &mut Transform, &mut Projection)>,
+) {
+    if !keybindings.is_action_pressed(actions::RESET_VIEW, &keyboard) {
+        return;
+    }
+
+    for viewport in &viewports {
+        let Ok((editor_camera, mut transform, mut projection)) =
+            cameras.get_mut(viewport.camera_id)
+        else {
+            continue;
+        };
+        // Viewport cameras are only enabled while the viewport is hovered
+        if !editor_camera.enabled {
+            continue;
+        }
+
+        transform.translation = Vec3::new(0., 0., transform.translation.z);
+        if let Projection::Orthographic(projection) = projection.as_mut() {
+            projection.scale = 1.;
+        }
+    }
+}
+
+/// Despawns the camera of a removed viewport.
+fn despawn_viewport_camera(
+    trigger: On<Remove, Bevy2dViewport>,
+    mut commands: Commands,
+    query: Query<&Bevy2dViewport>,
+) {
+    let Ok(viewport) = query.get(trigger.target()) else {
+        return;
+    };
+    // The pane can be removed before its camera is spawned, or along with it
+    if viewport.camera_id != Entity::PLACEHOLDER {
+        commands.entity(viewport.camera_id).try_despawn();
+    }
+}
+
 fn on_pane_creation(
     structure: In<PaneStructure>,
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
     theme: Res<Theme>,
+    render_layers: Res<EditorRenderLayers>,
 ) {
     let mut image = Image::default();
 
@@ -114,7 +296,7 @@ fn on_pane_creation(
                 clear_color: ClearColorConfig::Custom(theme.viewport.background_color),
                 ..default()
             },
-            RenderLayers::from_layers(&[0, 2]),
+            render_layers.viewport_2d_camera(),
         ))
         .id();
 
@@ -157,14 +339,19 @@ fn update_render_target_size(
         let Ok((computed_node, global_transform)) = pos_query.get(content_node_id) else {
             continue;
         };
-        // TODO Convert to physical pixels
+        // The UI is laid out in physical pixels, which the render target matches, while the
+        // pointer and cursor positions the viewport override is compared to are logical
         let content_node_size = computed_node.size();
 
-        let node_position = global_transform.translation;
-        let rect = Rect::from_center_size(node_position, computed_node.size());
+        let node_position = global_transform.translation * computed_node.inverse_scale_factor();
+        let rect = Rect::from_center_size(
+            node_position,
+            computed_node.size() * computed_node.inverse_scale_factor(),
+        );
 
         let (camera, mut editor_camera) = camera_query.get_mut(viewport.camera_id).unwrap();
 
+        // The only place the override is set, so it always follows the content node
         editor_camera.viewport_override = Some(rect);
 
         let image_handle = camera.target.as_image().unwrap();
@@ -176,3 +363,256 @@ fn update_render_target_size(
         images.get_mut(image_handle).unwrap().resize(size);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Affine2;
+
+    use super::*;
+
+    #[test]
+    fn theme_changes_update_viewport_grid_and_camera() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .add_systems(
+                Update,
+                (update_theme, update_clear_color).run_if(resource_changed::<Theme>),
+            );
+
+        let grid = app
+            .world_mut()
+            .spawn((ViewportGrid, InfiniteGridSettings::default()))
+            .id();
+        let user_grid = app.world_mut().spawn(InfiniteGridSettings::default()).id();
+        let camera_id = app.world_mut().spawn(Camera::default()).id();
+        app.world_mut().spawn(Bevy2dViewport { camera_id });
+        app.update();
+
+        let new_color = Color::srgb(1., 0., 1.);
+        {
+            let mut theme = app.world_mut().resource_mut::<Theme>();
+            theme.viewport.grid_major_line_color = new_color;
+            theme.viewport.background_color = new_color;
+        }
+        app.update();
+
+        let world = app.world();
+        let settings = world.get::<InfiniteGridSettings>(grid).unwrap();
+        assert_eq!(settings.major_line_color, new_color);
+
+        let user_settings = world.get::<InfiniteGridSettings>(user_grid).unwrap();
+        assert_eq!(
+            user_settings.major_line_color,
+            InfiniteGridSettings::default().major_line_color
+        );
+
+        let camera = world.get::<Camera>(camera_id).unwrap();
+        assert!(matches!(
+            camera.clear_color,
+            ClearColorConfig::Custom(color) if color == new_color
+        ));
+
+        // Changing another color keeps the clear color the camera was given since
+        let camera_color = Color::srgb(0., 1., 0.);
+        app.world_mut()
+            .get_mut::<Camera>(camera_id)
+            .unwrap()
+            .clear_color = ClearColorConfig::Custom(camera_color);
+        app.world_mut()
+            .resource_mut::<Theme>()
+            .viewport
+            .grid_minor_line_color = new_color;
+        app.update();
+        let camera = app.world().get::<Camera>(camera_id).unwrap();
+        assert!(matches!(
+            camera.clear_color,
+            ClearColorConfig::Custom(color) if color == camera_color
+        ));
+    }
+
+    #[test]
+    fn grid_style_applies_to_viewport_grid() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .init_resource::<EditorRenderLayers>()
+            .insert_resource(Viewport2dGridStyle(GridStyle::Dots))
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                update_grid_style.run_if(resource_changed::<Viewport2dGridStyle>),
+            );
+        app.update();
+
+        let mut grids = app
+            .world_mut()
+            .query_filtered::<&InfiniteGridSettings, With<ViewportGrid>>();
+        let settings = *grids.single(app.world()).unwrap();
+        assert_eq!(settings.style, GridStyle::Dots);
+        // The themed colors are kept in both styles
+        let theme = app.world().resource::<Theme>();
+        assert_eq!(
+            settings.major_line_color,
+            theme.viewport.grid_major_line_color
+        );
+
+        app.insert_resource(Viewport2dGridStyle(GridStyle::Lines));
+        app.update();
+        let settings = grids.single(app.world()).unwrap();
+        assert_eq!(settings.style, GridStyle::Lines);
+    }
+
+    #[test]
+    fn background_override_replaces_theme_color() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .add_observer(restore_theme_background)
+            .add_systems(
+                Update,
+                (
+                    update_clear_color.run_if(resource_changed::<Theme>),
+                    apply_viewport_background,
+                ),
+            );
+
+        let danger = Color::srgb(0.6, 0.1, 0.1);
+        let camera_id = app.world_mut().spawn(Camera::default()).id();
+        let pane = app
+            .world_mut()
+            .spawn((Bevy2dViewport { camera_id }, Viewport2dBackground(danger)))
+            .id();
+        app.update();
+        let clear_color = |app: &App| app.world().get::<Camera>(camera_id).unwrap().clear_color;
+        assert!(matches!(clear_color(&app), ClearColorConfig::Custom(color) if color == danger));
+
+        // The override wins over theme changes
+        let themed = Color::srgb(0.2, 0.2, 0.3);
+        app.world_mut()
+            .resource_mut::<Theme>()
+            .viewport
+            .background_color = themed;
+        app.update();
+        assert!(matches!(clear_color(&app), ClearColorConfig::Custom(color) if color == danger));
+
+        app.world_mut()
+            .entity_mut(pane)
+            .remove::<Viewport2dBackground>();
+        assert!(matches!(clear_color(&app), ClearColorConfig::Custom(color) if color == themed));
+    }
+
+    #[test]
+    fn reset_view_only_resets_hovered_viewport() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EditorKeybindings>()
+            .add_systems(Update, reset_view_shortcut);
+
+        let spawn_viewport = |world: &mut World, enabled: bool| {
+            let camera_id = world
+                .spawn((
+                    EditorCamera2d {
+                        enabled,
+                        ..default()
+                    },
+                    Transform::from_xyz(120., -40., 5.),
+                    Projection::Orthographic(OrthographicProjection {
+                        scale: 3.5,
+                        ..OrthographicProjection::default_2d()
+                    }),
+                ))
+                .id();
+            world.spawn(Bevy2dViewport { camera_id });
+            camera_id
+        };
+        let hovered = spawn_viewport(app.world_mut(), true);
+        let other = spawn_viewport(app.world_mut(), false);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Home);
+        app.update();
+
+        let world = app.world();
+        let scale = |camera| match world.get::<Projection>(camera) {
+            Some(Projection::Orthographic(projection)) => projection.scale,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            world.get::<Transform>(hovered).unwrap().translation,
+            Vec3::new(0., 0., 5.)
+        );
+        assert_eq!(scale(hovered), 1.);
+        assert_eq!(
+            world.get::<Transform>(other).unwrap().translation,
+            Vec3::new(120., -40., 5.)
+        );
+        assert_eq!(scale(other), 3.5);
+    }
+
+    #[test]
+    fn viewport_override_follows_content_node() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .add_systems(Update, update_render_target_size);
+
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::default());
+        let camera_id = app
+            .world_mut()
+            .spawn((
+                Camera {
+                    target: RenderTarget::Image(image.into()),
+                    ..default()
+                },
+                EditorCamera2d::default(),
+            ))
+            .id();
+        let pane = app.world_mut().spawn(Bevy2dViewport { camera_id }).id();
+        // Laid out at a scale factor of 2
+        app.world_mut().spawn((
+            PaneContentNode,
+            ComputedNode {
+                size: Vec2::new(400., 300.),
+                inverse_scale_factor: 0.5,
+                ..default()
+            },
+            UiGlobalTransform::from(Affine2::from_translation(Vec2::new(300., 250.))),
+            ChildOf(pane),
+        ));
+        app.update();
+
+        let editor_camera = app.world().get::<EditorCamera2d>(camera_id).unwrap();
+        assert_eq!(
+            editor_camera.viewport_override(),
+            Some(Rect::from_corners(
+                Vec2::new(50., 50.),
+                Vec2::new(250., 200.)
+            ))
+        );
+    }
+
+    #[test]
+    fn removing_viewport_despawns_its_camera() {
+        let mut app = App::new();
+        app.add_observer(despawn_viewport_camera);
+
+        let world = app.world_mut();
+        let camera_id = world.spawn_empty().id();
+        let pane = world.spawn(Bevy2dViewport { camera_id }).id();
+        // Removed before the pane was created, the camera was never spawned
+        let new_pane = world.spawn(Bevy2dViewport::default()).id();
+        world.entity_mut(new_pane).remove::<Bevy2dViewport>();
+        world.despawn(pane);
+        app.update();
+
+        assert!(app.world().get_entity(camera_id).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! The position of the pointer in the world shown by the hovered 2D viewport.
+
+use bevy::{
+    picking::pointer::{PointerId, PointerLocation},
+    prelude::*,
+};
+use bevy_editor_camera::EditorCamera2d;
+
+use crate::Bevy2dViewport;
+
+/// The mouse pointer over a 2D viewport, in the world coordinates shown by its camera, so
+/// interactions with the content of the viewport don't have to convert pointer positions.
+///
+/// Updated every frame, `None` while the pointer isn't over an enabled 2D viewport.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq)]
+pub struct Viewport2dPointer(pub Option<Viewport2dPointerPosition>);
+
+/// The position of the pointer over a 2D viewport, see [`Viewport2dPointer`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport2dPointerPosition {
+    /// The camera of the viewport under the pointer.
+    pub camera: Entity,
+    /// The world position under the pointer.
+    pub world: Vec2,
+}
+
+pub(crate) fn update_viewport_pointer(
+    mut viewport_pointer: ResMut<Viewport2dPointer>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    viewports: Query<&Bevy2dViewport>,
+    cameras: Query<(&EditorCamera2d, &Transform, &Projection)>,
+) {
+    let position = pointers
+        .iter()
+        .find(|(id, _)| id.is_mouse())
+        .and_then(|(_, location)| location.location())
+        .map(|location| location.position);
+
+    let hovered = position.and_then(|position| {
+        viewports.iter().find_map(|viewport| {
+            let (editor_camera, transform, projection) = cameras.get(viewport.camera_id).ok()?;
+            let Projection::Orthographic(projection) = projection else {
+                return None;
+            };
+            if !editor_camera.enabled || !editor_camera.viewport_override()?.contains(position) {
+                return None;
+            }
+
+            Some(Viewport2dPointerPosition {
+                camera: viewport.camera_id,
+                world: editor_camera.viewport_to_world(transform, projection, position)?,
+            })
+        })
+    });
+    viewport_pointer.set_if_neq(Viewport2dPointer(hovered));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{picking::pointer::Location, render::camera::NormalizedRenderTarget};
+
+    use super::*;
+
+    #[test]
+    fn pointer_is_reported_in_world_coordinates() {
+        let mut app = App::new();
+        app.init_resource::<Viewport2dPointer>()
+            .add_systems(Update, update_viewport_pointer);
+
+        let viewport_rect = Rect::from_corners(Vec2::new(50., 20.), Vec2::new(250., 120.));
+        let mut projection = OrthographicProjection {
+            scale: 2.,
+            ..OrthographicProjection::default_2d()
+        };
+        projection.update(viewport_rect.width(), viewport_rect.height());
+        let camera_id = app
+            .world_mut()
+            .spawn((
+                EditorCamera2d {
+                    viewport_override: Some(viewport_rect),
+                    ..default()
+                },
+                Transform::from_xyz(10., 5., 0.),
+                Projection::Orthographic(projection),
+            ))
+            .id();
+        app.world_mut().spawn(Bevy2dViewport { camera_id });
+
+        let pointer = app.world_mut().spawn(PointerId::Mouse).id();
+        let mut move_pointer = |app: &mut App, position: Vec2| {
+            app.world_mut()
+                .entity_mut(pointer)
+                .insert(PointerLocation::new(Location {
+                    target: NormalizedRenderTarget::Image(Handle::default().into()),
+                    position,
+                }));
+            app.update();
+            app.world().resource::<Viewport2dPointer>().0
+        };
+
+        // The top left corner of the viewport, at twice the default zoom
+        assert_eq!(
+            move_pointer(&mut app, Vec2::new(50., 20.)),
+            Some(Viewport2dPointerPosition {
+                camera: camera_id,
+                world: Vec2::new(-190., 105.),
+            })
+        );
+        assert_eq!(
+            move_pointer(&mut app, Vec2::new(150., 70.)).map(|position| position.world),
+            Some(Vec2::new(10., 5.))
+        );
+        assert_eq!(move_pointer(&mut app, Vec2::new(10., 10.)), None);
+    }
+}
@@ -0,0 +1,168 @@
+//! Spawning assets dragged from the asset browser onto the 3D viewport.
+
+use bevy::{
+    asset::AssetPath,
+    picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings},
+    prelude::*,
+};
+use bevy_editor_core::DraggableAsset;
+use bevy_undo::{AddedEntity, NewChange};
+
+use crate::Bevy3dViewport;
+
+/// How far in front of the camera assets are dropped when there is no surface or ground under
+/// the pointer.
+const DROP_DISTANCE: f32 = 10.;
+
+/// Dropping a [`DraggableAsset`] on the viewport image spawns the asset under the pointer.
+pub(crate) fn drop_asset_on_viewport(
+    mut trigger: On<Pointer<DragDrop>>,
+    mut commands: Commands,
+    draggable_assets: Query<&DraggableAsset>,
+    parent_query: Query<&ChildOf>,
+    viewports: Query<&Bevy3dViewport>,
+    nodes: Query<(&ComputedNode, &UiGlobalTransform)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+) {
+    let dropped = trigger.event().dropped;
+    // The drag can start on the icon or the name of a file
+    let Some(asset) = std::iter::once(dropped)
+        .chain(parent_query.iter_ancestors(dropped))
+        .find_map(|entity| draggable_assets.get(entity).ok())
+    else {
+        return;
+    };
+    trigger.propagate(false);
+
+    let viewport_image = trigger.target();
+    let Some((camera, camera_transform)) = parent_query
+        .iter_ancestors(viewport_image)
+        .find_map(|entity| viewports.get(entity).ok())
+        .and_then(|viewport| cameras.get(viewport.camera_id).ok())
+    else {
+        return;
+    };
+    let Ok((computed_node, global_transform)) = nodes.get(viewport_image) else {
+        return;
+    };
+
+    // The pointer is over the image showing the render target, relocate it to the image
+    let node_rect = Rect::from_center_size(global_transform.translation, computed_node.size());
+    let position = trigger.event().pointer_location.position - node_rect.min;
+    let Ok(ray) = camera.viewport_to_world(camera_transform, position) else {
+        return;
+    };
+
+    commands.run_system_cached_with(spawn_dropped_asset, (asset.0.clone(), ray));
+}
+
+/// Spawns the asset at `path` where `ray` first hits a mesh, or the ground when it hits none, and
+/// records the spawn for undo.
+///
+/// glTF files spawn their first scene, or the mesh when the path is labeled with one, and scene
+/// files spawn the scene at the root of the hierarchy. Other assets can't be placed in the scene.
+pub(crate) fn spawn_dropped_asset(
+    In((path, ray)): In<(AssetPath<'static>, Ray3d)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut ray_cast: MeshRayCast,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let extension = path.get_full_extension().unwrap_or_default();
+    let is_mesh = path.label().is_some_and(|label| label.starts_with("Mesh"));
+    let is_gltf = matches!(extension.as_str(), "gltf" | "glb");
+    if !is_gltf && !matches!(extension.as_str(), "scn" | "scn.ron") {
+        warn!("Can't place {path} in the scene, only glTF and scene files can be dropped");
+        return;
+    }
+
+    let translation = ray_cast
+        .cast_ray(ray, &MeshRayCastSettings::default())
+        .first()
+        .map(|(_, hit)| hit.point)
+        .or_else(|| {
+            ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+                .map(|distance| ray.get_point(distance))
+        })
+        .unwrap_or_else(|| ray.get_point(DROP_DISTANCE));
+
+    let name = path
+        .path()
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    let mut entity = commands.spawn((Name::new(name), Transform::from_translation(translation)));
+    if is_gltf && is_mesh {
+        entity.insert((
+            Mesh3d(asset_server.load(path)),
+            MeshMaterial3d(materials.add(StandardMaterial::default())),
+        ));
+    } else if is_gltf {
+        entity.insert(SceneRoot(
+            asset_server.load(GltfAssetLabel::Scene(0).from_asset(path.without_label())),
+        ));
+    } else {
+        entity.insert(DynamicSceneRoot(asset_server.load(path)));
+    }
+
+    new_changes.write(NewChange::new(AddedEntity {
+        entity: entity.id(),
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::primitives::Aabb;
+
+    use super::*;
+
+    #[test]
+    fn dropped_mesh_spawns_at_hit_point() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>()
+            .add_event::<NewChange>();
+
+        let world = app.world_mut();
+        let box_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::new(2., 2., 2.));
+        // Ray casts only hit meshes that are visible in a view
+        let mut view_visibility = ViewVisibility::HIDDEN;
+        view_visibility.set();
+        world.spawn((
+            Mesh3d(box_mesh),
+            Transform::from_xyz(0., 1., 0.),
+            GlobalTransform::from_xyz(0., 1., 0.),
+            Aabb::from_min_max(Vec3::splat(-1.), Vec3::splat(1.)),
+            InheritedVisibility::VISIBLE,
+            view_visibility,
+        ));
+
+        let path = AssetPath::from("models/crate.glb#Mesh0/Primitive0");
+        let down = Ray3d::new(Vec3::new(0.5, 10., 0.5), Dir3::NEG_Y);
+        world
+            .run_system_cached_with(spawn_dropped_asset, (path.clone(), down))
+            .unwrap();
+        // Outside of the box, the mesh lands on the ground
+        let beside = Ray3d::new(Vec3::new(4., 10., 0.), Dir3::NEG_Y);
+        world
+            .run_system_cached_with(spawn_dropped_asset, (path.clone(), beside))
+            .unwrap();
+
+        let handle: Handle<Mesh> = world.resource::<AssetServer>().load(path);
+        let mut spawned: Vec<Vec3> = world
+            .query::<(&Mesh3d, &Transform)>()
+            .iter(world)
+            .filter(|(mesh, _)| mesh.0 == handle)
+            .map(|(_, transform)| transform.translation)
+            .collect();
+        spawned.sort_by(|a, b| a.x.total_cmp(&b.x));
+        assert_eq!(spawned.len(), 2);
+        assert!(spawned[0].abs_diff_eq(Vec3::new(0.5, 2., 0.5), 1e-4));
+        assert!(spawned[1].abs_diff_eq(Vec3::new(4., 0., 0.), 1e-4));
+        assert_eq!(world.resource::<Events<NewChange>>().len(), 2);
+    }
+}
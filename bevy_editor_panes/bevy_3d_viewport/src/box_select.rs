@@ -0,0 +1,275 @@
+//! Selecting the entities inside a rectangle dragged in the 3D viewport.
+
+use bevy::{
+    picking::{
+        mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings},
+        pointer::{PointerId, PointerLocation},
+    },
+    prelude::*,
+    render::camera::CameraProjection,
+};
+use bevy_editor_cam::prelude::{motion::CurrentMotion, EditorCam};
+use bevy_editor_core::{is_locked, Locked, SelectedEntities, SelectedEntity};
+use bevy_editor_styles::Theme;
+
+use crate::{active_viewport_camera, Active, Bevy3dViewport};
+
+/// The keys that turn a left drag into a box selection instead of a pan.
+const BOX_SELECT_MODIFIERS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+
+pub(crate) struct BoxSelectPlugin;
+
+impl Plugin for BoxSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoxSelection>()
+            .add_systems(Update, (box_select, update_box_select_rects).chain());
+    }
+}
+
+/// The box selection being dragged, if any.
+#[derive(Resource, Default)]
+struct BoxSelection(Option<BoxSelectionDrag>);
+
+struct BoxSelectionDrag {
+    camera: Entity,
+    start: Vec2,
+    end: Vec2,
+}
+
+impl BoxSelectionDrag {
+    fn rect(&self) -> Rect {
+        Rect::from_corners(self.start, self.end)
+    }
+}
+
+/// The rectangle drawn while box selecting in the viewport rendered by `camera`.
+#[derive(Component)]
+struct BoxSelectRect {
+    camera: Entity,
+}
+
+/// Spawns the box selection rectangle for the viewport rendered by `camera` in `viewport_image`.
+pub(crate) fn spawn_box_select_rect(
+    commands: &mut Commands,
+    theme: &Theme,
+    viewport_image: Entity,
+    camera: Entity,
+) {
+    commands.spawn((
+        BoxSelectRect { camera },
+        Node {
+            position_type: PositionType::Absolute,
+            display: Display::None,
+            border: UiRect::all(Val::Px(1.)),
+            ..default()
+        },
+        BorderColor::all(theme.text.high_priority),
+        BackgroundColor(theme.text.high_priority.with_alpha(0.1)),
+        Pickable::IGNORE,
+        ChildOf(viewport_image),
+    ));
+}
+
+/// Dragging with the left mouse button and `Shift` held, starting on empty space in a viewport,
+/// selects the entities whose origin ends up inside the dragged rectangle.
+fn box_select(
+    mut commands: Commands,
+    mut selection: ResMut<BoxSelection>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    mut cameras: Query<(&Camera, &GlobalTransform, Option<&mut EditorCam>)>,
+    mut ray_cast: MeshRayCast,
+) {
+    let Some(position) = pointers
+        .iter()
+        .find(|(id, _)| id.is_mouse())
+        .and_then(|(_, location)| location.location())
+        .map(|location| location.position)
+    else {
+        return;
+    };
+
+    if let Some(drag) = &mut selection.0 {
+        drag.end = position;
+        // The drag also started a pan, keep the camera in place
+        if let Ok((_, _, Some(mut editor_cam))) = cameras.get_mut(drag.camera) {
+            if editor_cam.current_motion.is_user_controlled() {
+                editor_cam.end_move();
+                editor_cam.current_motion = CurrentMotion::Stationary;
+            }
+        }
+
+        if !mouse_buttons.pressed(MouseButton::Left) {
+            let drag = selection.0.take().unwrap();
+            if let Some(viewport_size) = cameras
+                .get(drag.camera)
+                .ok()
+                .and_then(|(camera, ..)| camera.logical_viewport_size())
+            {
+                commands.run_system_cached_with(
+                    select_in_box,
+                    (drag.camera, drag.rect(), viewport_size),
+                );
+            }
+        }
+        return;
+    }
+
+    if !keyboard.any_pressed(BOX_SELECT_MODIFIERS) || !mouse_buttons.just_pressed(MouseButton::Left)
+    {
+        return;
+    }
+    let Some(camera) = active_viewport_camera(&viewports, &children_query, &active_query) else {
+        return;
+    };
+    let Ok((camera_component, camera_transform, _)) = cameras.get(camera) else {
+        return;
+    };
+
+    // Only start on empty space, so clicks on entities keep their own meaning
+    let on_entity = camera_component
+        .viewport_to_world(camera_transform, position)
+        .ok()
+        .is_some_and(|ray| {
+            !ray_cast
+                .cast_ray(ray, &MeshRayCastSettings::default())
+                .is_empty()
+        });
+    if on_entity {
+        return;
+    }
+
+    selection.0 = Some(BoxSelectionDrag {
+        camera,
+        start: position,
+        end: position,
+    });
+}
+
+fn update_box_select_rects(
+    selection: Res<BoxSelection>,
+    mut rects: Query<(&BoxSelectRect, &mut Node)>,
+) {
+    for (rect, mut node) in &mut rects {
+        match &selection.0 {
+            Some(drag) if drag.camera == rect.camera => {
+                let area = drag.rect();
+                node.display = Display::Flex;
+                node.left = Val::Px(area.min.x);
+                node.top = Val::Px(area.min.y);
+                node.width = Val::Px(area.width());
+                node.height = Val::Px(area.height());
+            }
+            _ if node.display != Display::None => node.display = Display::None,
+            _ => {}
+        }
+    }
+}
+
+/// Replaces the selection with the named entities whose origin is inside `rect`, as seen by
+/// `camera` in a viewport of `viewport_size`. Hidden and locked entities are skipped.
+pub(crate) fn select_in_box(
+    In((camera, rect, viewport_size)): In<(Entity, Rect, Vec2)>,
+    cameras: Query<(&Projection, &GlobalTransform)>,
+    candidates: Query<(Entity, &GlobalTransform, Option<&InheritedVisibility>), With<Name>>,
+    locked_query: Query<&Locked>,
+    parent_query: Query<&ChildOf>,
+    mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
+) {
+    let Ok((projection, camera_transform)) = cameras.get(camera) else {
+        return;
+    };
+    let clip_from_world =
+        projection.get_clip_from_view() * Mat4::from(camera_transform.affine().inverse());
+
+    let selected: Vec<Entity> = candidates
+        .iter()
+        .filter(|(entity, ..)| *entity != camera)
+        .filter(|(.., visibility)| visibility.is_none_or(|visibility| visibility.get()))
+        .filter(|(entity, ..)| !is_locked(*entity, &locked_query, &parent_query))
+        .filter(|(_, transform, _)| {
+            let clip = clip_from_world * transform.translation().extend(1.);
+            // Entities behind the camera aren't on screen
+            if clip.w <= 0. {
+                return false;
+            }
+            let ndc = clip.truncate() / clip.w;
+            let position = Vec2::new(ndc.x + 1., 1. - ndc.y) / 2. * viewport_size;
+            rect.contains(position)
+        })
+        .map(|(entity, ..)| entity)
+        .collect();
+
+    selected_entity.0 = selected.last().copied();
+    selected_entities.0 = selected;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_selects_entities_inside_rect() {
+        let mut app = App::new();
+        app.init_resource::<SelectedEntity>()
+            .init_resource::<SelectedEntities>();
+
+        let world = app.world_mut();
+        let viewport_size = Vec2::new(200., 100.);
+        let mut projection = Projection::Perspective(PerspectiveProjection::default());
+        if let Projection::Perspective(perspective) = &mut projection {
+            perspective.update(viewport_size.x, viewport_size.y);
+        }
+        let camera = world
+            .spawn((
+                projection,
+                GlobalTransform::from(
+                    Transform::from_xyz(0., 0., 10.).looking_at(Vec3::ZERO, Vec3::Y),
+                ),
+            ))
+            .id();
+
+        let mut spawn = |name: &str, x: f32| {
+            world
+                .spawn((
+                    Name::new(name.to_string()),
+                    GlobalTransform::from_xyz(x, 0., 0.),
+                ))
+                .id()
+        };
+        let left = spawn("Left", -3.);
+        let center = spawn("Center", 0.);
+        let right = spawn("Right", 3.);
+        let locked = world
+            .spawn((
+                Name::new("Locked"),
+                GlobalTransform::from_xyz(-1., 0., 0.),
+                Locked::default(),
+            ))
+            .id();
+        world.spawn((
+            Name::new("Hidden"),
+            GlobalTransform::from_xyz(0.5, 0., 0.),
+            InheritedVisibility::HIDDEN,
+        ));
+
+        // Covers the left half of the viewport and a bit past the center
+        let rect = Rect::new(0., 0., 110., 100.);
+        world
+            .run_system_cached_with(select_in_box, (camera, rect, viewport_size))
+            .unwrap();
+
+        let mut selected = world.resource::<SelectedEntities>().0.clone();
+        selected.sort();
+        let mut expected = vec![left, center];
+        expected.sort();
+        assert_eq!(selected, expected);
+        assert!(!selected.contains(&right));
+        assert!(!selected.contains(&locked));
+    }
+}
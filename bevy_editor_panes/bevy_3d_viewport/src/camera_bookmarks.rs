@@ -0,0 +1,287 @@
+//! Storing and recalling viewport camera poses with the number keys.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_editor_cam::prelude::{motion::CurrentMotion, EditorCam};
+use bevy_editor_settings::SettingsType;
+
+use crate::{active_viewport_camera, Active, Bevy3dViewport};
+
+/// How long the camera takes to move to a recalled bookmark or a focused selection.
+const BOOKMARK_TRANSITION: Duration = Duration::from_millis(300);
+
+/// The keys bookmarks are stored under, the first key being slot 1.
+const BOOKMARK_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+pub(crate) struct CameraBookmarksPlugin;
+
+impl Plugin for CameraBookmarksPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CameraBookmarks>()
+            .init_resource::<CameraBookmarks>()
+            .add_systems(
+                Update,
+                (camera_bookmark_shortcuts, animate_camera_transitions),
+            );
+    }
+}
+
+/// A stored viewport camera pose.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct CameraBookmark {
+    /// The number key the pose is stored under.
+    pub slot: u8,
+    /// The position of the camera.
+    pub translation: Vec3,
+    /// The orientation of the camera.
+    pub rotation: Quat,
+}
+
+/// Camera poses stored with `Ctrl` and a number key, and recalled by pressing the number key
+/// while hovering a 3D viewport.
+#[derive(Resource, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Resource, @SettingsType::Workspace)]
+pub struct CameraBookmarks {
+    /// The stored poses, at most one per slot.
+    pub bookmarks: Vec<CameraBookmark>,
+}
+
+impl CameraBookmarks {
+    /// The pose stored under `slot`, if any.
+    pub fn get(&self, slot: u8) -> Option<&CameraBookmark> {
+        self.bookmarks.iter().find(|bookmark| bookmark.slot == slot)
+    }
+
+    /// Stores `transform` under `slot`, replacing the pose stored there before.
+    pub fn store(&mut self, slot: u8, transform: &Transform) {
+        let bookmark = CameraBookmark {
+            slot,
+            translation: transform.translation,
+            rotation: transform.rotation,
+        };
+        match self
+            .bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.slot == slot)
+        {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+    }
+}
+
+/// A camera moving to a new pose.
+#[derive(Component)]
+pub(crate) struct CameraTransition {
+    from: Transform,
+    to: Transform,
+    elapsed: Duration,
+}
+
+fn camera_bookmark_shortcuts(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+) {
+    let Some(slot) = BOOKMARK_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+        .map(|index| index as u8 + 1)
+    else {
+        return;
+    };
+
+    let Some(camera) = active_viewport_camera(&viewports, &children_query, &active_query) else {
+        return;
+    };
+
+    if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        commands.run_system_cached_with(store_bookmark, (camera, slot));
+    } else {
+        commands.run_system_cached_with(recall_bookmark, (camera, slot));
+    }
+}
+
+/// Stores the pose of `camera` under `slot`.
+fn store_bookmark(
+    In((camera, slot)): In<(Entity, u8)>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    transforms: Query<&Transform>,
+) {
+    if let Ok(transform) = transforms.get(camera) {
+        bookmarks.store(slot, transform);
+    }
+}
+
+/// Starts moving `camera` to the pose stored under `slot`.
+fn recall_bookmark(
+    In((camera, slot)): In<(Entity, u8)>,
+    mut commands: Commands,
+    bookmarks: Res<CameraBookmarks>,
+    mut cameras: Query<(&Transform, Option<&mut EditorCam>)>,
+) {
+    let (Some(bookmark), Ok((transform, editor_cam))) =
+        (bookmarks.get(slot), cameras.get_mut(camera))
+    else {
+        return;
+    };
+
+    let target = transform
+        .with_translation(bookmark.translation)
+        .with_rotation(bookmark.rotation);
+    start_camera_transition(&mut commands, camera, *transform, target, editor_cam);
+}
+
+/// Smoothly moves `camera` from `from` to `to`.
+pub(crate) fn start_camera_transition(
+    commands: &mut Commands,
+    camera: Entity,
+    from: Transform,
+    to: Transform,
+    editor_cam: Option<Mut<EditorCam>>,
+) {
+    // Stop any ongoing camera motion so it doesn't fight the transition
+    if let Some(mut editor_cam) = editor_cam {
+        editor_cam.end_move();
+        editor_cam.current_motion = CurrentMotion::Stationary;
+    }
+
+    commands.entity(camera).insert(CameraTransition {
+        from,
+        to,
+        elapsed: Duration::ZERO,
+    });
+}
+
+pub(crate) fn animate_camera_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cameras: Query<(Entity, &mut Transform, &mut CameraTransition)>,
+) {
+    for (camera, mut transform, mut transition) in &mut cameras {
+        transition.elapsed += time.delta();
+        let progress =
+            (transition.elapsed.as_secs_f32() / BOOKMARK_TRANSITION.as_secs_f32()).clamp(0., 1.);
+        // Smoothstep, easing in and out of the transition
+        let t = progress * progress * (3. - 2. * progress);
+
+        transform.translation = transition
+            .from
+            .translation
+            .lerp(transition.to.translation, t);
+        transform.rotation = transition.from.rotation.slerp(transition.to.rotation, t);
+
+        if progress >= 1. {
+            commands.entity(camera).remove::<CameraTransition>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_editor_settings::{EditorSettingsPlugin, GlobalSettingsPath};
+
+    use super::*;
+
+    #[test]
+    fn recalled_bookmark_converges_to_stored_pose() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CameraBookmarks>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )))
+            .add_systems(Update, animate_camera_transitions);
+
+        let stored = Transform::from_xyz(5., 5., 5.).looking_at(Vec3::ZERO, Vec3::Y);
+        let world = app.world_mut();
+        let camera = world.spawn((stored, EditorCam::default())).id();
+        world
+            .run_system_cached_with(store_bookmark, (camera, 1))
+            .unwrap();
+
+        let moved = Transform::from_xyz(-3., 1., 8.).looking_at(Vec3::X, Vec3::Y);
+        *world.get_mut::<Transform>(camera).unwrap() = moved;
+        world
+            .run_system_cached_with(recall_bookmark, (camera, 1))
+            .unwrap();
+
+        // Partway through, the camera is between both poses
+        app.update();
+        app.update();
+        app.update();
+        let halfway = *app.world().get::<Transform>(camera).unwrap();
+        assert_ne!(halfway.translation, moved.translation);
+        assert_ne!(halfway.translation, stored.translation);
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let world = app.world();
+        let transform = world.get::<Transform>(camera).unwrap();
+        assert!(transform.translation.abs_diff_eq(stored.translation, 1e-4));
+        assert!(transform.rotation.angle_between(stored.rotation) < 1e-4);
+        assert!(!world.entity(camera).contains::<CameraTransition>());
+    }
+
+    #[test]
+    fn bookmarks_persist_in_workspace_settings() {
+        let path = std::env::temp_dir().join("bevy_3d_viewport_bookmarks_test");
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        let editor = |path: &std::path::Path| {
+            let mut app = App::new();
+            app.add_plugins(MinimalPlugins)
+                .insert_resource(GlobalSettingsPath(path.to_owned()))
+                .register_type::<CameraBookmarks>()
+                .init_resource::<CameraBookmarks>()
+                .add_plugins(EditorSettingsPlugin);
+            app.finish();
+            app.update();
+            app
+        };
+
+        let mut app = editor(&path);
+        let camera = app
+            .world_mut()
+            .spawn(Transform::from_xyz(1., 2., 3.).looking_at(Vec3::ZERO, Vec3::Y))
+            .id();
+        app.world_mut()
+            .run_system_cached_with(store_bookmark, (camera, 4))
+            .unwrap();
+        app.update();
+        let stored = app.world().resource::<CameraBookmarks>().clone();
+
+        // The next session starts with the bookmarks of the last one
+        let app = editor(&path);
+        assert_eq!(*app.world().resource::<CameraBookmarks>(), stored);
+        assert!(app.world().resource::<CameraBookmarks>().get(4).is_some());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn storing_a_slot_again_replaces_it() {
+        let mut bookmarks = CameraBookmarks::default();
+        bookmarks.store(2, &Transform::from_xyz(1., 0., 0.));
+        bookmarks.store(2, &Transform::from_xyz(2., 0., 0.));
+
+        assert_eq!(bookmarks.bookmarks.len(), 1);
+        assert_eq!(bookmarks.get(2).unwrap().translation, Vec3::X * 2.);
+        assert!(bookmarks.get(1).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! Showing the grid coordinates under the pointer in a corner of the viewport.
+
+use bevy::{
+    picking::pointer::{PointerId, PointerLocation},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_editor_styles::Theme;
+
+pub(crate) struct CursorCoordinatesPlugin;
+
+impl Plugin for CursorCoordinatesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_cursor_coordinates);
+    }
+}
+
+/// The text showing the grid coordinates under the pointer for the viewport camera.
+#[derive(Component)]
+pub(crate) struct CursorCoordinates {
+    camera: Entity,
+}
+
+/// Spawns the coordinates text for the viewport rendered by `camera`, in the bottom left corner
+/// of `viewport_image`.
+pub(crate) fn spawn_cursor_coordinates(
+    commands: &mut Commands,
+    theme: &Theme,
+    viewport_image: Entity,
+    camera: Entity,
+) {
+    commands.spawn((
+        CursorCoordinates { camera },
+        Text::default(),
+        TextFont {
+            font: theme.text.font.clone(),
+            font_size: 10.,
+            ..default()
+        },
+        TextColor(theme.text.low_priority),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(6.),
+            bottom: Val::Px(4.),
+            ..default()
+        },
+        Pickable::IGNORE,
+        ChildOf(viewport_image),
+    ));
+}
+
+fn update_cursor_coordinates(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut texts: Query<(&CursorCoordinates, &mut Text)>,
+) {
+    let Some(location) = pointers
+        .iter()
+        .find(|(id, _)| id.is_mouse())
+        .and_then(|(_, location)| location.location())
+    else {
+        return;
+    };
+
+    for (coordinates, mut text) in &mut texts {
+        let point = cameras
+            .get(coordinates.camera)
+            .ok()
+            .filter(|(camera, _)| location.is_in_viewport(camera, &primary_window))
+            .and_then(|(camera, global_transform)| {
+                camera
+                    .viewport_to_world(global_transform, location.position)
+                    .ok()
+            })
+            .and_then(grid_point);
+
+        let new_text = point.map_or_else(String::new, |point| {
+            format!("X {:.2}  Y {:.2}  Z {:.2}", point.x, point.y, point.z)
+        });
+        // Only touch the text when it changes, to avoid relayouts every frame
+        if text.0 != new_text {
+            text.0 = new_text;
+        }
+    }
+}
+
+/// The point where `ray` hits the grid plane, if it does.
+fn grid_point(ray: Ray3d) -> Option<Vec3> {
+    ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+        .map(|distance| ray.get_point(distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_hits_grid_plane() {
+        let ray = Ray3d::new(
+            Vec3::new(1., 4., 0.),
+            Dir3::new(Vec3::new(1., -2., 1.)).unwrap(),
+        );
+        let point = grid_point(ray).unwrap();
+        assert!(point.abs_diff_eq(Vec3::new(3., 0., 2.), 1e-5));
+
+        // Looking at the horizon or away from the grid
+        assert!(grid_point(Ray3d::new(Vec3::Y, Dir3::X)).is_none());
+        assert!(grid_point(Ray3d::new(Vec3::Y, Dir3::Y)).is_none());
+    }
+}
@@ -0,0 +1,218 @@
+//! Framing the selected entities with the viewport camera.
+
+use bevy::{
+    math::bounding::{Aabb3d, BoundingVolume},
+    prelude::*,
+    render::primitives::Aabb,
+};
+use bevy_command_palette::CommandAppExt;
+use bevy_editor_cam::prelude::EditorCam;
+use bevy_editor_core::{actions, EditorKeybindings, SelectedEntities};
+
+use crate::{
+    active_viewport_camera, camera_bookmarks::start_camera_transition,
+    selection_bounds::entity_bounds, Active, Bevy3dViewport,
+};
+
+/// The smallest radius framed, so point-like selections aren't zoomed into.
+const MIN_FOCUS_RADIUS: f32 = 1.;
+
+pub(crate) struct FocusSelectionPlugin;
+
+impl Plugin for FocusSelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_command("Focus Selection", focus_selection_command)
+            .add_systems(Update, focus_selection_shortcut);
+    }
+}
+
+/// The focus selection action, `F` by default, frames the selected entities in the hovered
+/// viewport.
+fn focus_selection_shortcut(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+) {
+    if !keybindings.is_action_pressed(actions::FOCUS_SELECTION, &keyboard) {
+        return;
+    }
+    if let Some(camera) = active_viewport_camera(&viewports, &children_query, &active_query) {
+        commands.run_system_cached_with(focus_selection, camera);
+    }
+}
+
+/// Frames the selected entities in the hovered viewport, or in the first viewport when none is
+/// hovered, like when the command is run from a menu.
+fn focus_selection_command(
+    mut commands: Commands,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+) {
+    let camera = active_viewport_camera(&viewports, &children_query, &active_query).or_else(|| {
+        viewports
+            .iter()
+            .next()
+            .map(|(_, viewport)| viewport.camera_id)
+    });
+    if let Some(camera) = camera {
+        commands.run_system_cached_with(focus_selection, camera);
+    }
+}
+
+/// Moves `camera` back along its view direction until the combined bounds of the selection fit
+/// in view, and makes the center of the bounds the point the camera orbits around.
+pub(crate) fn focus_selection(
+    In(camera): In<Entity>,
+    mut commands: Commands,
+    selected_entities: Res<SelectedEntities>,
+    bounds_query: Query<(&Aabb, &GlobalTransform)>,
+    global_transforms: Query<&GlobalTransform>,
+    children_query: Query<&Children>,
+    mut cameras: Query<(&Transform, Option<&Projection>, Option<&mut EditorCam>)>,
+) {
+    // Entities without meshes are framed by their position
+    let Some(bounds) = selected_entities
+        .0
+        .iter()
+        .filter_map(|&entity| {
+            entity_bounds(entity, &bounds_query, &children_query).or_else(|| {
+                let translation = global_transforms.get(entity).ok()?.translation();
+                Some(Aabb3d::new(translation, Vec3::ZERO))
+            })
+        })
+        .reduce(|a, b| a.merge(&b))
+    else {
+        return;
+    };
+    let Ok((transform, projection, mut editor_cam)) = cameras.get_mut(camera) else {
+        return;
+    };
+
+    let center = Vec3::from(bounds.center());
+    let radius = Vec3::from(bounds.half_size())
+        .length()
+        .max(MIN_FOCUS_RADIUS);
+    let distance = match projection {
+        Some(Projection::Perspective(perspective)) => {
+            radius
+                / (perspective
+                    .fov
+                    .min(perspective.fov * perspective.aspect_ratio)
+                    / 2.)
+                    .sin()
+        }
+        _ => radius * 2.,
+    };
+
+    let target = transform.with_translation(center - transform.forward() * distance);
+    if let Some(editor_cam) = &mut editor_cam {
+        editor_cam.last_anchor_depth = -f64::from(distance);
+    }
+    start_camera_transition(&mut commands, camera, *transform, target, editor_cam);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use bevy_editor_core::Keybinding;
+
+    use super::*;
+    use crate::camera_bookmarks::{animate_camera_transitions, CameraTransition};
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<SelectedEntities>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )))
+            .add_systems(Update, animate_camera_transitions);
+        app
+    }
+
+    #[test]
+    fn focus_centers_on_selection_bounds() {
+        let mut app = setup_app();
+        let world = app.world_mut();
+        let a = world.spawn(GlobalTransform::from_xyz(-4., 0., 0.)).id();
+        let b = world.spawn(GlobalTransform::from_xyz(6., 2., 0.)).id();
+        world.resource_mut::<SelectedEntities>().0 = vec![a, b];
+
+        let camera = world
+            .spawn((
+                Transform::from_xyz(0., 5., 10.).looking_at(Vec3::ZERO, Vec3::Y),
+                Projection::default(),
+                EditorCam::default(),
+            ))
+            .id();
+        world
+            .run_system_cached_with(focus_selection, camera)
+            .unwrap();
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let world = app.world();
+        let transform = world.get::<Transform>(camera).unwrap();
+        let editor_cam = world.get::<EditorCam>(camera).unwrap();
+        let focus =
+            transform.translation + transform.forward() * -editor_cam.last_anchor_depth() as f32;
+        assert!(focus.abs_diff_eq(Vec3::new(1., 1., 0.), 1e-4));
+    }
+
+    #[test]
+    fn focus_follows_remapped_keybinding() {
+        let mut app = setup_app();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EditorKeybindings>()
+            .add_systems(Update, focus_selection_shortcut);
+
+        let world = app.world_mut();
+        let selected = world.spawn(GlobalTransform::from_xyz(3., 0., 0.)).id();
+        world.resource_mut::<SelectedEntities>().0 = vec![selected];
+        let camera = world
+            .spawn((Transform::from_xyz(0., 0., 10.), EditorCam::default()))
+            .id();
+        let viewport = world.spawn(Bevy3dViewport { camera_id: camera }).id();
+        world.spawn((Active, ChildOf(viewport)));
+        world
+            .resource_mut::<EditorKeybindings>()
+            .set(actions::FOCUS_SELECTION, Keybinding::new(KeyCode::KeyG));
+
+        let press = |app: &mut App, key: KeyCode| {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.release_all();
+            keyboard.clear();
+            keyboard.press(key);
+            app.update();
+            app.world().entity(camera).contains::<CameraTransition>()
+        };
+
+        assert!(!press(&mut app, KeyCode::KeyF));
+        assert!(press(&mut app, KeyCode::KeyG));
+    }
+
+    #[test]
+    fn focus_without_selection_does_nothing() {
+        let mut app = setup_app();
+        let world = app.world_mut();
+        let start = Transform::from_xyz(0., 5., 10.);
+        let camera = world.spawn((start, EditorCam::default())).id();
+        world
+            .run_system_cached_with(focus_selection, camera)
+            .unwrap();
+
+        app.update();
+        let world = app.world();
+        assert!(!world.entity(camera).contains::<CameraTransition>());
+        assert_eq!(world.get::<Transform>(camera), Some(&start));
+    }
+}
@@ -1,5 +1,11 @@
 //! 3D Viewport for Bevy
+use std::time::Duration;
+
 use bevy::{
+    core_pipeline::{
+        bloom::Bloom,
+        tonemapping::{DebandDither, Tonemapping},
+    },
     picking::{
         pointer::{Location, PointerId, PointerInput, PointerLocation},
         PickingSystems,
@@ -13,14 +19,43 @@ use bevy::{
     ui::ui_layout_system,
 };
 use bevy_editor_cam::prelude::{DefaultEditorCamPlugins, EditorCam};
-use bevy_editor_styles::Theme;
+use bevy_editor_core::EditorRenderLayers;
+use bevy_editor_settings::SettingsType;
+use bevy_editor_styles::{icons, StylesPlugin, Theme};
 use bevy_infinite_grid::{InfiniteGrid, InfiniteGridPlugin, InfiniteGridSettings};
 use bevy_pane_layout::prelude::*;
+use bevy_undo::UndoPlugin;
 use view_gizmo::{spawn_view_gizmo_target_texture, ViewGizmoPlugin};
 
-use crate::outline_gizmo::OutlineGizmoPlugin;
+use crate::{
+    asset_drop::drop_asset_on_viewport,
+    box_select::{spawn_box_select_rect, BoxSelectPlugin},
+    camera_bookmarks::CameraBookmarksPlugin,
+    cursor_coordinates::{spawn_cursor_coordinates, CursorCoordinatesPlugin},
+    focus_selection::FocusSelectionPlugin,
+    look_through::LookThroughPlugin,
+    measure::{spawn_measurement_label, MeasurePlugin},
+    outline_gizmo::OutlineGizmoPlugin,
+    place_on_surface::PlaceOnSurfacePlugin,
+    selection_bounds::SelectionBoundsPlugin,
+    snap_to_ground::SnapToGroundPlugin,
+};
+
+pub use camera_bookmarks::{CameraBookmark, CameraBookmarks};
+pub use measure::Measurement;
+pub use selection_bounds::SelectionBoundsMode;
 
+mod asset_drop;
+mod box_select;
+mod camera_bookmarks;
+mod cursor_coordinates;
+mod focus_selection;
+mod look_through;
+mod measure;
 mod outline_gizmo;
+mod place_on_surface;
+mod selection_bounds;
+mod snap_to_ground;
 mod view_gizmo;
 
 /// The identifier for the 3D Viewport.
@@ -38,6 +73,75 @@ impl Default for Bevy3dViewport {
     }
 }
 
+/// Clipping planes used by the cameras of all 3D viewports.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ViewportClipPlanes {
+    /// The distance of the near clipping plane. The camera still moves it closer when zooming in
+    /// on something nearby, so this is the farthest it will be.
+    pub near: f32,
+    /// The distance of the far clipping plane.
+    pub far: f32,
+}
+
+impl Default for ViewportClipPlanes {
+    fn default() -> Self {
+        Self {
+            near: 0.1,
+            far: 1000.,
+        }
+    }
+}
+
+/// Rendering features of the cameras of all 3D viewports.
+///
+/// These only change the editor cameras, so they can be turned off for performance or clarity
+/// without touching the render settings of the cameras in the scene being edited. They are
+/// workspace settings, toggled from the settings pane.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource, @SettingsType::Workspace)]
+pub struct ViewportRenderFeatures {
+    /// Render bloom in the viewports.
+    pub bloom: bool,
+    /// Apply tonemapping and debanding to the viewports. When disabled, the viewports show the
+    /// raw rendered colors.
+    pub post_processing: bool,
+    /// Render shadows in the viewports. Bevy computes shadows per light rather than per camera,
+    /// so disabling them turns off the shadows of the lights only the viewports render. Lights
+    /// that also light a 3D camera of the scene keep their shadows.
+    pub shadows: bool,
+}
+
+impl Default for ViewportRenderFeatures {
+    fn default() -> Self {
+        Self {
+            bloom: false,
+            post_processing: true,
+            shadows: true,
+        }
+    }
+}
+
+/// Controls how the viewport under the pointer is activated for camera controls and picking.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ViewportActivationSettings {
+    /// How long a viewport stays active after the pointer leaves it for other UI, so momentary
+    /// pointer flicker at the pane edges doesn't toggle the activation. Entering another viewport
+    /// deactivates it right away.
+    pub deactivation_delay: Duration,
+    /// Keep the viewport active while a mouse button is held, so drags that leave the pane
+    /// keep going.
+    pub keep_active_while_dragging: bool,
+}
+
+impl Default for ViewportActivationSettings {
+    fn default() -> Self {
+        Self {
+            deactivation_delay: Duration::from_millis(150),
+            keep_active_while_dragging: true,
+        }
+    }
+}
+
 /// Plugin for the 3D Viewport pane.
 pub struct Viewport3dPanePlugin;
 
@@ -46,35 +150,126 @@ impl Plugin for Viewport3dPanePlugin {
         if !app.is_plugin_added::<InfiniteGridPlugin>() {
             app.add_plugins(InfiniteGridPlugin);
         }
+        if !app.is_plugin_added::<StylesPlugin>() {
+            app.add_plugins(StylesPlugin);
+        }
+        if !app.is_plugin_added::<UndoPlugin>() {
+            app.add_plugins(UndoPlugin);
+        }
 
-        app.add_plugins((DefaultEditorCamPlugins, ViewGizmoPlugin, OutlineGizmoPlugin))
-            .add_systems(Startup, setup)
-            .add_systems(
-                PreUpdate,
+        app.add_plugins((
+            DefaultEditorCamPlugins,
+            CameraBookmarksPlugin,
+            CursorCoordinatesPlugin,
+            FocusSelectionPlugin,
+            ViewGizmoPlugin,
+            OutlineGizmoPlugin,
+            SelectionBoundsPlugin,
+            SnapToGroundPlugin,
+            PlaceOnSurfacePlugin,
+            MeasurePlugin,
+            BoxSelectPlugin,
+            LookThroughPlugin,
+        ))
+        .init_resource::<ViewportClipPlanes>()
+        .init_resource::<ViewportActivationSettings>()
+        .register_type::<ViewportRenderFeatures>()
+        .init_resource::<ViewportRenderFeatures>()
+        .init_resource::<EditorRenderLayers>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (
+                update_theme.run_if(resource_changed::<Theme>),
+                update_clip_planes.run_if(resource_changed::<ViewportClipPlanes>),
+                update_render_features.run_if(resource_changed::<ViewportRenderFeatures>),
+                update_viewport_shadows.run_if(
+                    resource_changed::<ViewportRenderFeatures>
+                        .or(any_match_filter::<Or<(Added<Camera3d>, AddedLight)>>),
+                ),
+                update_render_layers.run_if(resource_changed::<EditorRenderLayers>),
+            ),
+        )
+        .add_systems(
+            PreUpdate,
+            (
+                deactivate_viewports,
                 render_target_picking_passthrough.in_set(PickingSystems::Last),
             )
-            .add_systems(
-                PostUpdate,
-                update_render_target_size.after(ui_layout_system),
-            )
-            .add_observer(
-                |trigger: On<Remove, Bevy3dViewport>,
-                 mut commands: Commands,
-                 query: Query<&Bevy3dViewport>| {
-                    // Despawn the viewport camera
-                    commands
-                        .entity(query.get(trigger.target()).unwrap().camera_id)
-                        .despawn();
-                },
-            );
+                .chain(),
+        )
+        .add_systems(
+            PostUpdate,
+            update_render_target_size.after(ui_layout_system),
+        )
+        .add_observer(despawn_viewport_camera);
 
-        app.register_pane("Viewport 3D", on_pane_creation);
+        app.register_pane_with_tab("Viewport 3D", PaneTab::new(icons::BOX), on_pane_creation);
     }
 }
 
 #[derive(Component)]
 struct Active;
 
+/// The time at which the pointer left an [`Active`] viewport.
+#[derive(Component)]
+struct PendingDeactivation(Duration);
+
+/// The camera of the viewport the pointer is over, if any.
+pub(crate) fn active_viewport_camera(
+    viewports: &Query<(Entity, &Bevy3dViewport)>,
+    children_query: &Query<&Children>,
+    active_query: &Query<(), With<Active>>,
+) -> Option<Entity> {
+    viewports.iter().find_map(|(pane_root, viewport)| {
+        children_query
+            .iter_descendants(pane_root)
+            .any(|entity| active_query.contains(entity))
+            .then_some(viewport.camera_id)
+    })
+}
+
+/// Activates the viewport the pointer entered. The viewport it came from is deactivated right away,
+/// so only one viewport gets pointer input, the deactivation delay only applies when the pointer
+/// leaves for other UI.
+fn activate_viewport(
+    trigger: On<Pointer<Over>>,
+    mut commands: Commands,
+    active_query: Query<Entity, With<Active>>,
+) {
+    let target = trigger.target();
+    for viewport in active_query.iter().filter(|&viewport| viewport != target) {
+        commands
+            .entity(viewport)
+            .remove::<(Active, PendingDeactivation)>();
+    }
+    commands
+        .entity(target)
+        .insert(Active)
+        .remove::<PendingDeactivation>();
+}
+
+/// Removes [`Active`] from viewports the pointer left for longer than the deactivation delay.
+fn deactivate_viewports(
+    mut commands: Commands,
+    settings: Res<ViewportActivationSettings>,
+    time: Res<Time<Real>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    pending: Query<(Entity, &PendingDeactivation)>,
+) {
+    let dragging =
+        settings.keep_active_while_dragging && mouse_buttons.get_pressed().next().is_some();
+
+    for (entity, pending) in &pending {
+        if dragging || time.elapsed() - pending.0 < settings.deactivation_delay {
+            continue;
+        }
+        commands
+            .entity(entity)
+            .remove::<(Active, PendingDeactivation)>();
+    }
+}
+
 // TODO This does not properly handle multiple windows.
 /// Copies picking events and moves pointers through render-targets.
 fn render_target_picking_passthrough(
@@ -129,9 +324,14 @@ fn render_target_picking_passthrough(
     }
 }
 
-fn setup(mut commands: Commands, theme: Res<Theme>) {
+/// Marker for the grid spawned by the 3D viewport, to tell it apart from grids in user content.
+#[derive(Component)]
+struct ViewportGrid;
+
+fn setup(mut commands: Commands, theme: Res<Theme>, render_layers: Res<EditorRenderLayers>) {
     commands.spawn((
         InfiniteGrid,
+        ViewportGrid,
         InfiniteGridSettings {
             x_axis_color: theme.viewport.x_axis_color,
             z_axis_color: theme.viewport.z_axis_color,
@@ -139,15 +339,168 @@ fn setup(mut commands: Commands, theme: Res<Theme>) {
             minor_line_color: theme.viewport.grid_minor_line_color,
             ..default()
         },
-        RenderLayers::layer(1),
+        RenderLayers::layer(render_layers.grid_3d),
     ));
 }
 
+/// Applies the current [`Theme`] to the viewport grid and cameras, so theme changes take effect
+/// without reopening the viewports.
+fn update_theme(
+    theme: Res<Theme>,
+    mut grids: Query<&mut InfiniteGridSettings, With<ViewportGrid>>,
+    viewports: Query<&Bevy3dViewport>,
+    mut cameras: Query<&mut Camera>,
+) {
+    for mut settings in &mut grids {
+        settings.x_axis_color = theme.viewport.x_axis_color;
+        settings.z_axis_color = theme.viewport.z_axis_color;
+        settings.major_line_color = theme.viewport.grid_major_line_color;
+        settings.minor_line_color = theme.viewport.grid_minor_line_color;
+    }
+
+    for viewport in &viewports {
+        if let Ok(mut camera) = cameras.get_mut(viewport.camera_id) {
+            camera.clear_color = ClearColorConfig::Custom(theme.viewport.background_color);
+        }
+    }
+}
+
+fn update_render_layers(
+    render_layers: Res<EditorRenderLayers>,
+    mut grids: Query<&mut RenderLayers, With<ViewportGrid>>,
+    viewports: Query<&Bevy3dViewport>,
+    mut cameras: Query<&mut RenderLayers, Without<ViewportGrid>>,
+) {
+    for mut layers in &mut grids {
+        *layers = RenderLayers::layer(render_layers.grid_3d);
+    }
+
+    for viewport in &viewports {
+        if let Ok(mut layers) = cameras.get_mut(viewport.camera_id) {
+            *layers = render_layers.viewport_3d_camera();
+        }
+    }
+}
+
+fn update_clip_planes(
+    clip_planes: Res<ViewportClipPlanes>,
+    viewports: Query<&Bevy3dViewport>,
+    mut cameras: Query<&mut EditorCam>,
+) {
+    for viewport in &viewports {
+        if let Ok(mut editor_cam) = cameras.get_mut(viewport.camera_id) {
+            apply_clip_planes(&mut editor_cam, &clip_planes);
+        }
+    }
+}
+
+fn apply_clip_planes(editor_cam: &mut EditorCam, clip_planes: &ViewportClipPlanes) {
+    let near_limits = &mut editor_cam.perspective.near_clip_limits;
+    near_limits.end = clip_planes.near;
+    near_limits.start = near_limits.start.min(clip_planes.near);
+    editor_cam.perspective.far_clip = clip_planes.far;
+}
+
+fn update_render_features(
+    mut commands: Commands,
+    features: Res<ViewportRenderFeatures>,
+    viewports: Query<&Bevy3dViewport>,
+) {
+    for viewport in &viewports {
+        if let Ok(mut camera) = commands.get_entity(viewport.camera_id) {
+            apply_render_features(&mut camera, &features);
+        }
+    }
+}
+
+fn apply_render_features(camera: &mut EntityCommands, features: &ViewportRenderFeatures) {
+    if features.bloom {
+        camera.insert(Bloom::NATURAL);
+    } else {
+        camera.remove::<Bloom>();
+    }
+
+    if features.post_processing {
+        camera.insert((Tonemapping::default(), DebandDither::Enabled));
+    } else {
+        camera.insert((Tonemapping::None, DebandDither::Disabled));
+    }
+}
+
+/// Marks lights whose shadows were turned off by [`ViewportRenderFeatures::shadows`], so turning
+/// shadows back on only restores those.
+#[derive(Component)]
+struct ViewportShadowsDisabled;
+
+/// Filter for newly spawned lights that can cast shadows.
+type AddedLight = Or<(Added<DirectionalLight>, Added<PointLight>, Added<SpotLight>)>;
+
+/// Turns the shadows of the lights only rendered by the viewports off or back on.
+fn update_viewport_shadows(
+    features: Res<ViewportRenderFeatures>,
+    render_layers: Res<EditorRenderLayers>,
+    mut commands: Commands,
+    viewports: Query<&Bevy3dViewport>,
+    cameras: Query<(Entity, Option<&RenderLayers>), With<Camera3d>>,
+    mut lights: Query<(
+        Entity,
+        Option<&RenderLayers>,
+        Has<ViewportShadowsDisabled>,
+        AnyOf<(&mut DirectionalLight, &mut PointLight, &mut SpotLight)>,
+    )>,
+) {
+    let viewport_layers = render_layers.viewport_3d_camera();
+    let scene_camera_layers: Vec<RenderLayers> = cameras
+        .iter()
+        .filter(|(camera, _)| !viewports.iter().any(|v| v.camera_id == *camera))
+        .map(|(_, layers)| layers.cloned().unwrap_or_default())
+        .collect();
+
+    for (entity, layers, disabled, (directional, point, spot)) in &mut lights {
+        let mut shadows_enabled = match (directional, point, spot) {
+            (Some(light), ..) => light.map_unchanged(|light| &mut light.shadows_enabled),
+            (_, Some(light), _) => light.map_unchanged(|light| &mut light.shadows_enabled),
+            (.., Some(light)) => light.map_unchanged(|light| &mut light.shadows_enabled),
+            _ => continue,
+        };
+
+        let layers = layers.cloned().unwrap_or_default();
+        let hide = !features.shadows
+            && layers.intersects(&viewport_layers)
+            && !scene_camera_layers.iter().any(|l| l.intersects(&layers));
+        if hide && !disabled && *shadows_enabled {
+            *shadows_enabled = false;
+            commands.entity(entity).insert(ViewportShadowsDisabled);
+        } else if !hide && disabled {
+            *shadows_enabled = true;
+            commands.entity(entity).remove::<ViewportShadowsDisabled>();
+        }
+    }
+}
+
+/// Despawns the camera of a removed viewport.
+fn despawn_viewport_camera(
+    trigger: On<Remove, Bevy3dViewport>,
+    mut commands: Commands,
+    query: Query<&Bevy3dViewport>,
+) {
+    let Ok(viewport) = query.get(trigger.target()) else {
+        return;
+    };
+    // The pane can be removed before its camera is spawned, or along with it
+    if viewport.camera_id != Entity::PLACEHOLDER {
+        commands.entity(viewport.camera_id).try_despawn();
+    }
+}
+
 fn on_pane_creation(
     structure: In<PaneStructure>,
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
     theme: Res<Theme>,
+    clip_planes: Res<ViewportClipPlanes>,
+    render_features: Res<ViewportRenderFeatures>,
+    render_layers: Res<EditorRenderLayers>,
 ) {
     let mut image = Image::default();
 
@@ -156,7 +509,7 @@ fn on_pane_creation(
 
     let image_handle = images.add(image);
 
-    commands
+    let viewport_image = commands
         .spawn((
             ImageNode::new(image_handle.clone()),
             Node {
@@ -172,27 +525,37 @@ fn on_pane_creation(
         .with_children(|parent| {
             spawn_view_gizmo_target_texture(images, parent);
         })
-        .observe(|trigger: On<Pointer<Over>>, mut commands: Commands| {
-            commands.entity(trigger.target()).insert(Active);
-        })
-        .observe(|trigger: On<Pointer<Out>>, mut commands: Commands| {
-            commands.entity(trigger.target()).remove::<Active>();
-        });
-
-    let camera_id = commands
-        .spawn((
-            Camera3d::default(),
-            Camera {
-                target: RenderTarget::Image(image_handle.into()),
-                clear_color: ClearColorConfig::Custom(theme.viewport.background_color),
-                ..default()
+        .observe(activate_viewport)
+        .observe(
+            |trigger: On<Pointer<Out>>, mut commands: Commands, time: Res<Time<Real>>| {
+                commands
+                    .entity(trigger.target())
+                    .insert(PendingDeactivation(time.elapsed()));
             },
-            EditorCam::default(),
-            Transform::from_translation(Vec3::ONE * 5.).looking_at(Vec3::ZERO, Vec3::Y),
-            RenderLayers::from_layers(&[0, 1]),
-        ))
+        )
+        .observe(drop_asset_on_viewport)
         .id();
 
+    let mut editor_cam = EditorCam::default();
+    apply_clip_planes(&mut editor_cam, &clip_planes);
+
+    let mut camera = commands.spawn((
+        Camera3d::default(),
+        Camera {
+            target: RenderTarget::Image(image_handle.into()),
+            clear_color: ClearColorConfig::Custom(theme.viewport.background_color),
+            ..default()
+        },
+        editor_cam,
+        Transform::from_translation(Vec3::ONE * 5.).looking_at(Vec3::ZERO, Vec3::Y),
+        render_layers.viewport_3d_camera(),
+    ));
+    apply_render_features(&mut camera, &render_features);
+    let camera_id = camera.id();
+    spawn_cursor_coordinates(&mut commands, &theme, viewport_image, camera_id);
+    spawn_measurement_label(&mut commands, &theme, viewport_image, camera_id);
+    spawn_box_select_rect(&mut commands, &theme, viewport_image, camera_id);
+
     commands
         .entity(structure.root)
         .insert(Bevy3dViewport { camera_id });
@@ -229,3 +592,293 @@ fn update_render_target_size(
         images.get_mut(image_handle).unwrap().resize(size);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{picking::backend::HitData, time::TimeUpdateStrategy};
+    use bevy_pane_layout::{PaneLayoutPlugin, RootPaneLayoutNode};
+
+    use super::*;
+
+    #[test]
+    fn remapped_grid_layer_follows_to_cameras() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<EditorRenderLayers>()
+            .add_systems(
+                Update,
+                update_render_layers.run_if(resource_changed::<EditorRenderLayers>),
+            );
+
+        let world = app.world_mut();
+        let grid = world.spawn((ViewportGrid, RenderLayers::layer(1))).id();
+        let camera = world.spawn(RenderLayers::from_layers(&[0, 1])).id();
+        world.spawn(Bevy3dViewport { camera_id: camera });
+        // A camera of the game using layer 1 for its own content
+        let game_camera = world.spawn(RenderLayers::layer(1)).id();
+        app.update();
+
+        app.world_mut().resource_mut::<EditorRenderLayers>().grid_3d = 5;
+        app.update();
+
+        let world = app.world();
+        assert_eq!(
+            world.get::<RenderLayers>(grid),
+            Some(&RenderLayers::layer(5))
+        );
+        let camera_layers = world.get::<RenderLayers>(camera).unwrap();
+        assert!(camera_layers.intersects(&RenderLayers::layer(5)));
+        assert!(!camera_layers.intersects(&RenderLayers::layer(1)));
+        assert_eq!(
+            world.get::<RenderLayers>(game_camera),
+            Some(&RenderLayers::layer(1))
+        );
+    }
+
+    #[test]
+    fn render_features_only_change_viewport_cameras() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ViewportRenderFeatures>()
+            .add_systems(
+                Update,
+                update_render_features.run_if(resource_changed::<ViewportRenderFeatures>),
+            );
+
+        let world = app.world_mut();
+        let editor_camera = world.spawn_empty().id();
+        world.spawn(Bevy3dViewport {
+            camera_id: editor_camera,
+        });
+        let scene_camera = world.spawn((Tonemapping::default(), Bloom::NATURAL)).id();
+
+        *world.resource_mut::<ViewportRenderFeatures>() = ViewportRenderFeatures {
+            bloom: true,
+            post_processing: false,
+            shadows: true,
+        };
+        app.update();
+
+        let world = app.world();
+        assert!(world.entity(editor_camera).contains::<Bloom>());
+        assert_eq!(
+            world.get::<Tonemapping>(editor_camera),
+            Some(&Tonemapping::None)
+        );
+
+        app.world_mut()
+            .resource_mut::<ViewportRenderFeatures>()
+            .bloom = false;
+        app.update();
+
+        let world = app.world();
+        assert!(!world.entity(editor_camera).contains::<Bloom>());
+        assert!(world.entity(scene_camera).contains::<Bloom>());
+        assert_eq!(
+            world.get::<Tonemapping>(scene_camera),
+            Some(&Tonemapping::default())
+        );
+    }
+
+    #[test]
+    fn shadows_are_only_disabled_for_lights_of_the_viewports() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ViewportRenderFeatures>()
+            .init_resource::<EditorRenderLayers>()
+            .add_systems(Update, update_viewport_shadows);
+
+        let world = app.world_mut();
+        let editor_camera = world
+            .spawn((Camera3d::default(), RenderLayers::from_layers(&[0, 1])))
+            .id();
+        world.spawn(Bevy3dViewport {
+            camera_id: editor_camera,
+        });
+        // A camera of the scene rendering layer 3
+        world.spawn((Camera3d::default(), RenderLayers::layer(3)));
+        let shadows = |shadows_enabled| PointLight {
+            shadows_enabled,
+            ..default()
+        };
+        let viewport_light = world.spawn(shadows(true)).id();
+        let unshadowed_light = world.spawn(shadows(false)).id();
+        let scene_light = world
+            .spawn((shadows(true), RenderLayers::from_layers(&[0, 3])))
+            .id();
+        app.update();
+
+        let shadows_enabled = |app: &App, light| {
+            app.world()
+                .get::<PointLight>(light)
+                .unwrap()
+                .shadows_enabled
+        };
+        assert!(shadows_enabled(&app, viewport_light));
+
+        app.world_mut()
+            .resource_mut::<ViewportRenderFeatures>()
+            .shadows = false;
+        app.update();
+        assert!(!shadows_enabled(&app, viewport_light));
+        assert!(shadows_enabled(&app, scene_light));
+
+        app.world_mut()
+            .resource_mut::<ViewportRenderFeatures>()
+            .shadows = true;
+        app.update();
+        assert!(shadows_enabled(&app, viewport_light));
+        assert!(!shadows_enabled(&app, unshadowed_light));
+        assert!(shadows_enabled(&app, scene_light));
+    }
+
+    #[test]
+    fn viewport_stays_active_briefly_after_pointer_leaves() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ViewportActivationSettings>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(Update, deactivate_viewports);
+        app.update();
+
+        let world = app.world_mut();
+        let left_at = world.resource::<Time<Real>>().elapsed();
+        let viewport = world.spawn((Active, PendingDeactivation(left_at))).id();
+
+        // Still within the deactivation delay
+        app.update();
+        assert!(app.world().entity(viewport).contains::<Active>());
+
+        // Dragging keeps the viewport active
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        app.update();
+        assert!(app.world().entity(viewport).contains::<Active>());
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        app.update();
+        let entity = app.world().entity(viewport);
+        assert!(!entity.contains::<Active>());
+        assert!(!entity.contains::<PendingDeactivation>());
+    }
+
+    #[test]
+    fn entering_viewport_deactivates_the_previous_one() {
+        let mut app = App::new();
+        let world = app.world_mut();
+        let previous = world
+            .spawn((Active, PendingDeactivation(Duration::ZERO)))
+            .id();
+        let entered = world.spawn_empty().observe(activate_viewport).id();
+        world.flush();
+
+        let over = Pointer::new(
+            PointerId::Mouse,
+            Location {
+                target: NormalizedRenderTarget::Image(Handle::<Image>::default().into()),
+                position: Vec2::ZERO,
+            },
+            Over {
+                hit: HitData::new(Entity::PLACEHOLDER, 0., None, None),
+            },
+        );
+        world.trigger_targets(over, entered);
+        world.flush();
+
+        // The viewport that was left doesn't wait for the deactivation delay
+        assert!(!world.entity(previous).contains::<Active>());
+        assert!(!world.entity(previous).contains::<PendingDeactivation>());
+        assert!(world.entity(entered).contains::<Active>());
+    }
+
+    #[test]
+    fn removing_viewport_despawns_its_camera() {
+        let mut app = App::new();
+        app.add_observer(despawn_viewport_camera);
+
+        let world = app.world_mut();
+        let camera_id = world.spawn_empty().id();
+        let pane = world.spawn(Bevy3dViewport { camera_id }).id();
+        // Removed before the pane was created, the camera was never spawned
+        let new_pane = world.spawn(Bevy3dViewport::default()).id();
+        world.entity_mut(new_pane).remove::<Bevy3dViewport>();
+        world.despawn(pane);
+        app.update();
+
+        assert!(app.world().get_entity(camera_id).is_err());
+    }
+
+    #[test]
+    fn closing_viewport_pane_despawns_its_camera_and_image() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_asset::<Image>()
+            .init_resource::<Theme>()
+            .init_resource::<ViewportClipPlanes>()
+            .init_resource::<ViewportRenderFeatures>()
+            .init_resource::<EditorRenderLayers>()
+            .add_plugins(PaneLayoutPlugin)
+            .add_observer(despawn_viewport_camera)
+            .register_pane_with_tab("Viewport 3D", PaneTab::new(icons::BOX), on_pane_creation);
+        app.world_mut().spawn(RootPaneLayoutNode);
+        app.update();
+
+        let world = app.world_mut();
+        let (viewport, structure) = world
+            .query::<(&Bevy3dViewport, &PaneStructure)>()
+            .single(world)
+            .map(|(viewport, structure)| (viewport.camera_id, *structure))
+            .unwrap();
+        let image = world
+            .get::<Camera>(viewport)
+            .and_then(|camera| camera.target.as_image())
+            .map(Handle::id)
+            .unwrap();
+        assert!(world.resource::<Assets<Image>>().contains(image));
+
+        let is_in_header = |world: &World, mut entity: Entity| loop {
+            if entity == structure.header {
+                return true;
+            }
+            match world.get::<ChildOf>(entity) {
+                Some(child_of) => entity = child_of.parent(),
+                None => return false,
+            }
+        };
+        let close_button = world
+            .query::<(Entity, &Text)>()
+            .iter(world)
+            .find(|(entity, text)| text.0 == icons::X && is_in_header(world, *entity))
+            .map(|(entity, _)| entity)
+            .unwrap();
+        let click = Pointer::new(
+            PointerId::Mouse,
+            Location {
+                target: NormalizedRenderTarget::Image(Handle::<Image>::default().into()),
+                position: Vec2::ZERO,
+            },
+            Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(Entity::PLACEHOLDER, 0., None, None),
+                duration: Duration::ZERO,
+            },
+        );
+        world.trigger_targets(click, close_button);
+        app.update();
+
+        let world = app.world();
+        assert!(world.get_entity(structure.root).is_err());
+        assert!(world.get_entity(structure.content).is_err());
+        assert!(world.get_entity(viewport).is_err());
+        // The pane image was only kept alive by the pane and its camera
+        assert!(!world.resource::<Assets<Image>>().contains(image));
+    }
+}
@@ -0,0 +1,238 @@
+//! Looking through the selected camera, so the viewport camera flies it to frame a shot.
+
+use bevy::prelude::*;
+use bevy_editor_cam::prelude::{motion::CurrentMotion, EditorCam};
+use bevy_editor_core::{actions, EditorKeybindings, SelectedEntity};
+
+use crate::{active_viewport_camera, Active, Bevy3dViewport};
+
+pub(crate) struct LookThroughPlugin;
+
+impl Plugin for LookThroughPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LookThrough>()
+            .add_systems(Update, look_through_shortcut)
+            .add_systems(PostUpdate, pilot_camera.before(TransformSystems::Propagate));
+    }
+}
+
+/// The camera being looked through, if any.
+#[derive(Resource, Default)]
+struct LookThrough(Option<Piloting>);
+
+struct Piloting {
+    /// The camera of the viewport looking through [`target`](Self::target).
+    viewport_camera: Entity,
+    /// The camera moved along with the viewport camera.
+    target: Entity,
+    /// The pose of the viewport camera before looking through the target, restored after.
+    editor_pose: Transform,
+    /// The field of view of the viewport camera before it took the one of the target.
+    editor_fov: Option<f32>,
+}
+
+/// The look through action, `Numpad 0` by default, makes the hovered viewport look through the
+/// selected camera, and back to its own pose when pressed again.
+fn look_through_shortcut(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    look_through: Res<LookThrough>,
+    selected_entity: Res<SelectedEntity>,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+) {
+    if !keybindings.is_action_pressed(actions::LOOK_THROUGH, &keyboard) {
+        return;
+    }
+    if look_through.0.is_some() {
+        commands.run_system_cached(stop_look_through);
+        return;
+    }
+
+    let Some(target) = selected_entity.0 else {
+        return;
+    };
+    if let Some(viewport_camera) =
+        active_viewport_camera(&viewports, &children_query, &active_query)
+    {
+        commands.run_system_cached_with(start_look_through, (viewport_camera, target));
+    }
+}
+
+/// Moves `viewport_camera` to the pose of the `target` camera, which then follows the viewport
+/// camera until [`stop_look_through`].
+fn start_look_through(
+    In((viewport_camera, target)): In<(Entity, Entity)>,
+    mut look_through: ResMut<LookThrough>,
+    targets: Query<(&GlobalTransform, Option<&Projection>), (With<Camera>, Without<EditorCam>)>,
+    mut cameras: Query<(&mut Transform, Option<&mut Projection>, &mut EditorCam)>,
+) {
+    let Ok((target_transform, target_projection)) = targets.get(target) else {
+        return;
+    };
+    let Ok((mut transform, projection, mut editor_cam)) = cameras.get_mut(viewport_camera) else {
+        return;
+    };
+
+    // Stop any ongoing camera motion so it doesn't move the camera away from the target pose
+    editor_cam.end_move();
+    editor_cam.current_motion = CurrentMotion::Stationary;
+
+    let mut editor_fov = None;
+    if let (
+        Some(Projection::Perspective(target_perspective)),
+        Some(Projection::Perspective(perspective)),
+    ) = (target_projection, projection.map(Mut::into_inner))
+    {
+        editor_fov = Some(perspective.fov);
+        perspective.fov = target_perspective.fov;
+    }
+
+    look_through.0 = Some(Piloting {
+        viewport_camera,
+        target,
+        editor_pose: *transform,
+        editor_fov,
+    });
+    let (_, rotation, translation) = target_transform.to_scale_rotation_translation();
+    transform.translation = translation;
+    transform.rotation = rotation;
+}
+
+/// Moves the viewport camera back to the pose it had before looking through a camera.
+/// The camera looked through keeps the pose it was flown to.
+fn stop_look_through(
+    mut look_through: ResMut<LookThrough>,
+    mut cameras: Query<(&mut Transform, Option<&mut Projection>)>,
+) {
+    let Some(piloting) = look_through.0.take() else {
+        return;
+    };
+    let Ok((mut transform, projection)) = cameras.get_mut(piloting.viewport_camera) else {
+        return;
+    };
+
+    *transform = piloting.editor_pose;
+    if let (Some(fov), Some(Projection::Perspective(perspective))) =
+        (piloting.editor_fov, projection.map(Mut::into_inner))
+    {
+        perspective.fov = fov;
+    }
+}
+
+/// Moves the camera looked through along with the viewport camera.
+fn pilot_camera(
+    mut commands: Commands,
+    look_through: Res<LookThrough>,
+    viewport_cameras: Query<&Transform, With<EditorCam>>,
+    mut targets: Query<(&mut Transform, Option<&ChildOf>), Without<EditorCam>>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    let Some(piloting) = &look_through.0 else {
+        return;
+    };
+    let (Ok(viewport_transform), Ok((mut target_transform, child_of))) = (
+        viewport_cameras.get(piloting.viewport_camera),
+        targets.get_mut(piloting.target),
+    ) else {
+        // The viewport or the camera looked through is gone
+        commands.run_system_cached(stop_look_through);
+        return;
+    };
+
+    let pose = viewport_transform.with_scale(target_transform.scale);
+    let pose = match child_of.and_then(|child_of| global_transforms.get(child_of.parent()).ok()) {
+        Some(parent) => GlobalTransform::from(pose).reparented_to(parent),
+        None => pose,
+    };
+    target_transform.set_if_neq(pose);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looking_through_flies_the_selected_camera() {
+        let mut app = App::new();
+        app.init_resource::<LookThrough>()
+            .add_systems(Update, pilot_camera);
+
+        let editor_pose = Transform::from_xyz(0., 5., 10.).looking_at(Vec3::ZERO, Vec3::Y);
+        let viewport_camera = app
+            .world_mut()
+            .spawn((
+                editor_pose,
+                EditorCam::default(),
+                Projection::Perspective(PerspectiveProjection::default()),
+            ))
+            .id();
+
+        let rig = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0., 10., 0.),
+                GlobalTransform::from_xyz(0., 10., 0.),
+            ))
+            .id();
+        let game_pose = Transform::from_xyz(3., 12., 0.);
+        let game_camera = app
+            .world_mut()
+            .spawn((
+                Camera::default(),
+                Projection::Perspective(PerspectiveProjection {
+                    fov: 1.2,
+                    ..default()
+                }),
+                Transform::from_xyz(3., 2., 0.),
+                GlobalTransform::from(game_pose),
+                ChildOf(rig),
+            ))
+            .id();
+
+        app.world_mut()
+            .run_system_cached_with(start_look_through, (viewport_camera, game_camera))
+            .unwrap();
+        let world = app.world();
+        assert_eq!(
+            world.get::<Transform>(viewport_camera).unwrap().translation,
+            game_pose.translation
+        );
+        assert!(matches!(
+            world.get::<Projection>(viewport_camera),
+            Some(Projection::Perspective(perspective)) if perspective.fov == 1.2
+        ));
+
+        // Flying the viewport camera moves the game camera, relative to its parent
+        let framed = Transform::from_xyz(-4., 11., 6.).looking_at(Vec3::ZERO, Vec3::Y);
+        *app.world_mut()
+            .get_mut::<Transform>(viewport_camera)
+            .unwrap() = framed;
+        app.update();
+        let local = *app.world().get::<Transform>(game_camera).unwrap();
+        assert!(local.translation.abs_diff_eq(Vec3::new(-4., 1., 6.), 1e-4));
+        assert!(local.rotation.angle_between(framed.rotation) < 1e-4);
+
+        app.world_mut()
+            .run_system_cached(stop_look_through)
+            .unwrap();
+        app.update();
+        let world = app.world();
+        assert_eq!(
+            *world.get::<Transform>(viewport_camera).unwrap(),
+            editor_pose
+        );
+        assert!(matches!(
+            world.get::<Projection>(viewport_camera),
+            Some(Projection::Perspective(perspective))
+                if perspective.fov == PerspectiveProjection::default().fov
+        ));
+        assert!(world
+            .get::<Transform>(game_camera)
+            .unwrap()
+            .translation
+            .abs_diff_eq(Vec3::new(-4., 1., 6.), 1e-4));
+    }
+}
@@ -0,0 +1,327 @@
+//! Measuring the distance between two points in the 3D viewport.
+
+use bevy::{
+    picking::{
+        mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings},
+        pointer::{PointerId, PointerLocation},
+    },
+    prelude::*,
+    render::view::RenderLayers,
+};
+use bevy_editor_core::{actions, EditorKeybindings, EditorRenderLayers};
+use bevy_editor_styles::Theme;
+
+use crate::{active_viewport_camera, Active, Bevy3dViewport};
+
+/// How close a clicked point has to be to a vertex or pivot to snap to it, in world units.
+const MEASURE_SNAP_DISTANCE: f32 = 0.15;
+
+pub(crate) struct MeasurePlugin;
+
+impl Plugin for MeasurePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Measurement>()
+            .init_gizmo_group::<MeasureGizmos>()
+            .add_systems(
+                Update,
+                (
+                    update_measure_gizmo_layers.run_if(resource_changed::<EditorRenderLayers>),
+                    toggle_measuring,
+                    pick_measurement_point,
+                    draw_measurement,
+                    update_measurement_labels,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The measurement made in the 3D viewports.
+///
+/// While measuring, each click in a viewport places a point, and the distance between the last
+/// two points is shown. A click after a finished measurement starts a new one.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Measurement {
+    /// Whether clicks in the viewports place measurement points.
+    pub measuring: bool,
+    /// The first point of the measurement.
+    pub start: Option<Vec3>,
+    /// The second point of the measurement.
+    pub end: Option<Vec3>,
+}
+
+impl Measurement {
+    /// Places the next point, starting a new measurement if the current one is finished.
+    pub fn add_point(&mut self, point: Vec3) {
+        match (self.start, self.end) {
+            (Some(_), None) => self.end = Some(point),
+            _ => {
+                self.start = Some(point);
+                self.end = None;
+            }
+        }
+    }
+
+    /// The measured distance, once both points are placed.
+    pub fn distance(&self) -> Option<f32> {
+        Some(self.start?.distance(self.end?))
+    }
+}
+
+/// The gizmos of the measurement, drawn on the 3D grid layer so only the viewports show them.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct MeasureGizmos;
+
+/// Keeps the measurement gizmos on the 3D grid layer of [`EditorRenderLayers`].
+fn update_measure_gizmo_layers(
+    render_layers: Res<EditorRenderLayers>,
+    mut config_store: ResMut<GizmoConfigStore>,
+) {
+    let (config, _) = config_store.config_mut::<MeasureGizmos>();
+    config.render_layers = RenderLayers::layer(render_layers.grid_3d);
+}
+
+/// The label showing the measured distance in the viewport rendered by `camera`.
+#[derive(Component)]
+struct MeasurementLabel {
+    camera: Entity,
+}
+
+/// Spawns the distance label for the viewport rendered by `camera` in `viewport_image`.
+pub(crate) fn spawn_measurement_label(
+    commands: &mut Commands,
+    theme: &Theme,
+    viewport_image: Entity,
+    camera: Entity,
+) {
+    commands.spawn((
+        MeasurementLabel { camera },
+        Text::default(),
+        TextFont {
+            font: theme.text.font.clone(),
+            font_size: 12.,
+            ..default()
+        },
+        TextColor(theme.text.high_priority),
+        Node {
+            position_type: PositionType::Absolute,
+            display: Display::None,
+            ..default()
+        },
+        Pickable::IGNORE,
+        ChildOf(viewport_image),
+    ));
+}
+
+/// The measure action, `M` by default, turns measuring on and off.
+fn toggle_measuring(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    mut measurement: ResMut<Measurement>,
+) {
+    if keybindings.is_action_pressed(actions::MEASURE, &keyboard) {
+        *measurement = Measurement {
+            measuring: !measurement.measuring,
+            ..default()
+        };
+    }
+}
+
+/// While measuring, clicking in a viewport places a point on the mesh under the pointer, snapped
+/// to the hit triangle's vertices or the mesh's pivot when close enough.
+fn pick_measurement_point(
+    mut measurement: ResMut<Measurement>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut ray_cast: MeshRayCast,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    if !measurement.measuring || !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // Rays go from the camera of the hovered viewport through the pointer
+    let Some((camera, camera_transform)) =
+        active_viewport_camera(&viewports, &children_query, &active_query)
+            .and_then(|camera| cameras.get(camera).ok())
+    else {
+        return;
+    };
+    let Some(ray) = pointers
+        .iter()
+        .find(|(id, _)| id.is_mouse())
+        .and_then(|(_, location)| location.location())
+        .and_then(|location| {
+            camera
+                .viewport_to_world(camera_transform, location.position)
+                .ok()
+        })
+    else {
+        return;
+    };
+
+    let point = match ray_cast
+        .cast_ray(ray, &MeshRayCastSettings::default())
+        .first()
+    {
+        Some((entity, hit)) => {
+            let pivot = global_transforms
+                .get(*entity)
+                .map(GlobalTransform::translation)
+                .ok();
+            let candidates = hit.triangle.into_iter().flatten().chain(pivot);
+            snap_point(hit.point, candidates)
+        }
+        // Fall back to the grid plane
+        None => match ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y)) {
+            Some(distance) => ray.get_point(distance),
+            None => return,
+        },
+    };
+
+    measurement.add_point(point);
+}
+
+/// Snaps `point` to the closest of `candidates` within [`MEASURE_SNAP_DISTANCE`].
+fn snap_point(point: Vec3, candidates: impl IntoIterator<Item = Vec3>) -> Vec3 {
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, candidate.distance(point)))
+        .filter(|(_, distance)| *distance <= MEASURE_SNAP_DISTANCE)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(point, |(candidate, _)| candidate)
+}
+
+fn draw_measurement(
+    measurement: Res<Measurement>,
+    theme: Res<Theme>,
+    mut gizmos: Gizmos<MeasureGizmos>,
+) {
+    let color = theme.text.high_priority;
+    for point in [measurement.start, measurement.end].into_iter().flatten() {
+        gizmos.sphere(Isometry3d::from_translation(point), 0.05, color);
+    }
+    if let (Some(start), Some(end)) = (measurement.start, measurement.end) {
+        gizmos.line(start, end, color);
+    }
+}
+
+fn update_measurement_labels(
+    measurement: Res<Measurement>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut labels: Query<(&MeasurementLabel, &mut Text, &mut Node)>,
+) {
+    for (label, mut text, mut node) in &mut labels {
+        let position = measurement
+            .start
+            .zip(measurement.end)
+            .and_then(|(start, end)| {
+                let (camera, camera_transform) = cameras.get(label.camera).ok()?;
+                camera
+                    .world_to_viewport(camera_transform, start.midpoint(end))
+                    .ok()
+            });
+
+        match (position, measurement.distance()) {
+            (Some(position), Some(distance)) => {
+                node.display = Display::Flex;
+                node.left = Val::Px(position.x);
+                node.top = Val::Px(position.y);
+                let new_text = format!("{distance:.3}");
+                if text.0 != new_text {
+                    text.0 = new_text;
+                }
+            }
+            _ if node.display != Display::None => node.display = Display::None,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_editor_core::Keybinding;
+
+    use super::*;
+
+    #[test]
+    fn two_points_measure_their_distance() {
+        let mut measurement = Measurement::default();
+        measurement.add_point(Vec3::new(1., 0., 0.));
+        assert_eq!(measurement.distance(), None);
+
+        measurement.add_point(Vec3::new(4., 4., 0.));
+        assert_eq!(measurement.distance(), Some(5.));
+
+        // The next click starts a new measurement
+        measurement.add_point(Vec3::ZERO);
+        assert_eq!(measurement.start, Some(Vec3::ZERO));
+        assert_eq!(measurement.distance(), None);
+    }
+
+    #[test]
+    fn points_snap_to_nearby_vertices() {
+        let vertices = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        assert_eq!(snap_point(Vec3::new(0.95, 0.05, 0.), vertices), Vec3::X);
+        let far = Vec3::new(0.5, 0.3, 0.);
+        assert_eq!(snap_point(far, vertices), far);
+    }
+
+    #[test]
+    fn remapped_measure_action_only_fires_on_new_key() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EditorKeybindings>()
+            .init_resource::<Measurement>()
+            .add_systems(Update, toggle_measuring);
+        app.world_mut()
+            .resource_mut::<EditorKeybindings>()
+            .set(actions::MEASURE, Keybinding::new(KeyCode::KeyG).shift());
+
+        let mut press = |keys: &[KeyCode]| {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.reset_all();
+            for key in keys {
+                keyboard.press(*key);
+            }
+            app.update();
+            app.world().resource::<Measurement>().measuring
+        };
+
+        // The old key no longer toggles measuring
+        assert!(!press(&[KeyCode::KeyM]));
+        assert!(press(&[KeyCode::ShiftLeft, KeyCode::KeyG]));
+        assert!(!press(&[KeyCode::ShiftLeft, KeyCode::KeyG]));
+    }
+
+    #[test]
+    fn measure_gizmos_follow_grid_layer() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<EditorRenderLayers>()
+            .init_gizmo_group::<MeasureGizmos>()
+            .add_systems(
+                Update,
+                update_measure_gizmo_layers.run_if(resource_changed::<EditorRenderLayers>),
+            );
+        let layers = |app: &App| {
+            let (config, _) = app
+                .world()
+                .resource::<GizmoConfigStore>()
+                .config::<MeasureGizmos>();
+            config.render_layers.clone()
+        };
+
+        app.update();
+        assert_eq!(layers(&app), RenderLayers::layer(1));
+
+        app.world_mut().resource_mut::<EditorRenderLayers>().grid_3d = 5;
+        app.update();
+        assert_eq!(layers(&app), RenderLayers::layer(5));
+    }
+}
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use bevy_editor_core::SelectedEntity;
+use bevy_editor_core::{is_locked, Locked, SelectedEntities};
 
 pub struct OutlineGizmoPlugin;
 impl Plugin for OutlineGizmoPlugin {
@@ -21,13 +21,18 @@ struct GizmoToggleText;
 pub fn outline_gizmo_system(
     show: Res<ShowOutlines>,
     query: Query<&Transform>,
-    selected_entity: Res<SelectedEntity>,
+    selected_entities: Res<SelectedEntities>,
+    locked_query: Query<&Locked>,
+    parent_query: Query<&ChildOf>,
     mut gizmos: Gizmos,
 ) {
     if !show.0 {
         return;
     }
-    if let Some(entity) = selected_entity.0 {
+    for &entity in &selected_entities.0 {
+        if is_locked(entity, &locked_query, &parent_query) {
+            continue;
+        }
         if let Ok(transform) = query.get(entity) {
             gizmos.cuboid(*transform, Color::srgb(1.0, 0.0, 0.0));
         }
@@ -0,0 +1,192 @@
+//! Placing the selected entity on the surface under the pointer.
+
+use bevy::{
+    picking::{
+        mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings},
+        pointer::{PointerId, PointerLocation},
+    },
+    prelude::*,
+};
+use bevy_editor_core::{actions, is_locked, EditorKeybindings, Locked, SelectedEntity};
+use bevy_undo::{ComponentChange, NewChange};
+
+use crate::{active_viewport_camera, Active, Bevy3dViewport};
+
+pub(crate) struct PlaceOnSurfacePlugin;
+
+impl Plugin for PlaceOnSurfacePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, place_on_surface_shortcut);
+    }
+}
+
+/// The place on surface action, `P` by default, moves the selected entity to the surface under
+/// the pointer and aligns its up axis to the surface. `Alt + P` keeps its rotation.
+fn place_on_surface_shortcut(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    selected_entity: Res<SelectedEntity>,
+    viewports: Query<(Entity, &Bevy3dViewport)>,
+    children_query: Query<&Children>,
+    active_query: Query<(), With<Active>>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    locked_query: Query<&Locked>,
+    parent_query: Query<&ChildOf>,
+) {
+    let align_to_normal = if keybindings.is_action_pressed(actions::PLACE_ON_SURFACE, &keyboard) {
+        true
+    } else if keybindings.is_action_pressed(actions::PLACE_ON_SURFACE_KEEP_ROTATION, &keyboard) {
+        false
+    } else {
+        return;
+    };
+    let Some(entity) = selected_entity.0 else {
+        return;
+    };
+    if is_locked(entity, &locked_query, &parent_query) {
+        return;
+    }
+
+    // Rays go from the camera of the hovered viewport through the pointer
+    let Some((camera, camera_transform)) =
+        active_viewport_camera(&viewports, &children_query, &active_query)
+            .and_then(|camera| cameras.get(camera).ok())
+    else {
+        return;
+    };
+    let Some(ray) = pointers
+        .iter()
+        .find(|(id, _)| id.is_mouse())
+        .and_then(|(_, location)| location.location())
+        .and_then(|location| {
+            camera
+                .viewport_to_world(camera_transform, location.position)
+                .ok()
+        })
+    else {
+        return;
+    };
+
+    commands.run_system_cached_with(place_on_surface, (entity, ray, align_to_normal));
+}
+
+/// Moves `entity` to where `ray` first hits a mesh other than the entity itself.
+/// When `align_to_normal` is set, the entity is also rotated so its up axis matches the surface
+/// normal. The move is recorded for undo.
+pub(crate) fn place_on_surface(
+    In((entity, ray, align_to_normal)): In<(Entity, Ray3d, bool)>,
+    mut ray_cast: MeshRayCast,
+    mut transform_query: Query<(&mut Transform, &GlobalTransform)>,
+    global_transform_query: Query<&GlobalTransform>,
+    parent_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let excluded: Vec<Entity> = std::iter::once(entity)
+        .chain(children_query.iter_descendants(entity))
+        .collect();
+    let filter = |hit: Entity| !excluded.contains(&hit);
+    let settings = MeshRayCastSettings::default().with_filter(&filter);
+
+    let Some((_, hit)) = ray_cast.cast_ray(ray, &settings).first() else {
+        return;
+    };
+    let (point, normal) = (hit.point, hit.normal.normalize_or(Vec3::Y));
+
+    let Ok((_, global_transform)) = transform_query.get(entity) else {
+        return;
+    };
+    let world_rotation = global_transform.rotation();
+    let new_rotation = if align_to_normal {
+        Quat::from_rotation_arc(world_rotation * Vec3::Y, normal) * world_rotation
+    } else {
+        world_rotation
+    };
+
+    // Convert back to the entity's local space
+    let parent_transform = parent_query
+        .get(entity)
+        .ok()
+        .and_then(|child_of| global_transform_query.get(child_of.parent()).ok())
+        .copied()
+        .unwrap_or_default();
+
+    let (mut transform, _) = transform_query.get_mut(entity).unwrap();
+    let old_transform = *transform;
+    transform.translation = parent_transform.affine().inverse().transform_point3(point);
+    transform.rotation = parent_transform.rotation().inverse() * new_rotation;
+    new_changes.write(NewChange::new(ComponentChange::new(
+        entity,
+        old_transform,
+        *transform,
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_6;
+
+    use bevy::render::primitives::Aabb;
+
+    use super::*;
+
+    #[test]
+    fn placed_entity_lands_on_plane_and_aligns_to_it() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_event::<NewChange>();
+
+        let world = app.world_mut();
+        let plane = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Plane3d::default().mesh().size(10., 10.));
+        // Ray casts only hit meshes that are visible in a view
+        let mut view_visibility = ViewVisibility::HIDDEN;
+        view_visibility.set();
+        let tilt = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_6));
+        world.spawn((
+            Mesh3d(plane),
+            tilt,
+            GlobalTransform::from(tilt),
+            Aabb::from_min_max(Vec3::new(-5., -0.01, -5.), Vec3::new(5., 0.01, 5.)),
+            InheritedVisibility::VISIBLE,
+            view_visibility,
+        ));
+        let entity = world
+            .spawn((
+                Transform::from_xyz(1., 5., 0.),
+                GlobalTransform::from_xyz(1., 5., 0.),
+            ))
+            .id();
+
+        let ray = Ray3d::new(Vec3::new(1., 5., 0.), Dir3::NEG_Y);
+        world
+            .run_system_cached_with(place_on_surface, (entity, ray, true))
+            .unwrap();
+
+        let transform = world.get::<Transform>(entity).unwrap();
+        let normal = Vec3::new(-FRAC_PI_6.sin(), FRAC_PI_6.cos(), 0.);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1., FRAC_PI_6.tan(), 0.), 1e-4));
+        assert!((transform.rotation * Vec3::Y).abs_diff_eq(normal, 1e-4));
+        assert_eq!(world.resource::<Events<NewChange>>().len(), 1);
+
+        // Keeping the rotation only moves the entity
+        world.entity_mut(entity).insert((
+            Transform::from_xyz(1., 5., 0.),
+            GlobalTransform::from_xyz(1., 5., 0.),
+        ));
+        world
+            .run_system_cached_with(place_on_surface, (entity, ray, false))
+            .unwrap();
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1., FRAC_PI_6.tan(), 0.), 1e-4));
+        assert_eq!(transform.rotation, Quat::IDENTITY);
+    }
+}
@@ -0,0 +1,161 @@
+//! Drawing the bounding box of the selection.
+
+use bevy::{
+    math::bounding::{Aabb3d, BoundingVolume},
+    prelude::*,
+    render::primitives::Aabb,
+};
+use bevy_editor_core::SelectedEntities;
+
+pub(crate) struct SelectionBoundsPlugin;
+
+impl Plugin for SelectionBoundsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectionBoundsMode>()
+            .add_systems(Update, draw_selection_bounds);
+    }
+}
+
+/// How the bounding box of the selected entities is drawn in the 3D viewports.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionBoundsMode {
+    /// No bounding box is drawn.
+    Off,
+    /// A single box enclosing all selected entities.
+    #[default]
+    Combined,
+    /// A box around each selected entity.
+    PerEntity,
+}
+
+fn draw_selection_bounds(
+    mode: Res<SelectionBoundsMode>,
+    selected_entities: Res<SelectedEntities>,
+    bounds_query: Query<(&Aabb, &GlobalTransform)>,
+    children_query: Query<&Children>,
+    mut gizmos: Gizmos,
+) {
+    for bounds in selection_bounds(*mode, &selected_entities, &bounds_query, &children_query) {
+        let center = Vec3::from(bounds.center());
+        let size = Vec3::from(bounds.max - bounds.min);
+        gizmos.cuboid(
+            Transform::from_translation(center).with_scale(size),
+            Color::srgb(1.0, 0.8, 0.0),
+        );
+    }
+}
+
+/// The world-space bounds to draw for the selection, from the mesh bounds of the selected
+/// entities and their descendants.
+pub(crate) fn selection_bounds(
+    mode: SelectionBoundsMode,
+    selected_entities: &SelectedEntities,
+    bounds_query: &Query<(&Aabb, &GlobalTransform)>,
+    children_query: &Query<&Children>,
+) -> Vec<Aabb3d> {
+    let bounds = selected_entities
+        .0
+        .iter()
+        .filter_map(|&entity| entity_bounds(entity, bounds_query, children_query));
+
+    match mode {
+        SelectionBoundsMode::Off => Vec::new(),
+        SelectionBoundsMode::Combined => bounds.reduce(|a, b| a.merge(&b)).into_iter().collect(),
+        SelectionBoundsMode::PerEntity => bounds.collect(),
+    }
+}
+
+/// The world-space bounds of the meshes of `entity` and its descendants, if it has any.
+pub(crate) fn entity_bounds(
+    entity: Entity,
+    bounds_query: &Query<(&Aabb, &GlobalTransform)>,
+    children_query: &Query<&Children>,
+) -> Option<Aabb3d> {
+    std::iter::once(entity)
+        .chain(children_query.iter_descendants(entity))
+        .filter_map(|entity| bounds_query.get(entity).ok())
+        .map(|(aabb, global_transform)| world_bounds(aabb, global_transform))
+        .reduce(|a, b| a.merge(&b))
+}
+
+/// The axis-aligned box enclosing `aabb` once transformed to world space.
+fn world_bounds(aabb: &Aabb, global_transform: &GlobalTransform) -> Aabb3d {
+    let affine = global_transform.affine();
+    let center = affine.transform_point3a(aabb.center);
+    let half_size = (0..3)
+        .map(|axis| affine.matrix3.col(axis).abs() * aabb.half_extents[axis])
+        .sum();
+    Aabb3d {
+        min: center - half_size,
+        max: center + half_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn bounds(world: &mut World, mode: SelectionBoundsMode) -> Vec<Aabb3d> {
+        world
+            .run_system_once(
+                move |selected_entities: Res<SelectedEntities>,
+                      bounds_query: Query<(&Aabb, &GlobalTransform)>,
+                      children_query: Query<&Children>| {
+                    selection_bounds(mode, &selected_entities, &bounds_query, &children_query)
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn combined_bounds_enclose_all_selected_entities() {
+        let mut world = World::new();
+        let unit_cube = Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5));
+        let a = world
+            .spawn((unit_cube, GlobalTransform::from_xyz(-2., 0., 0.)))
+            .id();
+        let b = world
+            .spawn((
+                unit_cube,
+                GlobalTransform::from(Transform::from_xyz(3., 1., 0.).with_scale(Vec3::splat(2.))),
+            ))
+            .id();
+        // Not selected, so not part of the bounds
+        world.spawn((unit_cube, GlobalTransform::from_xyz(10., 0., 0.)));
+        world.insert_resource(SelectedEntities(vec![a, b]));
+
+        let combined = bounds(&mut world, SelectionBoundsMode::Combined);
+        assert_eq!(combined.len(), 1);
+        assert_eq!(Vec3::from(combined[0].min), Vec3::new(-2.5, -0.5, -1.));
+        assert_eq!(Vec3::from(combined[0].max), Vec3::new(4., 2., 1.));
+
+        let per_entity = bounds(&mut world, SelectionBoundsMode::PerEntity);
+        assert_eq!(per_entity.len(), 2);
+        assert_eq!(Vec3::from(per_entity[0].max), Vec3::new(-1.5, 0.5, 0.5));
+
+        assert!(bounds(&mut world, SelectionBoundsMode::Off).is_empty());
+    }
+
+    #[test]
+    fn bounds_include_descendants_and_rotation() {
+        let mut world = World::new();
+        let unit_cube = Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5));
+        let parent = world.spawn(GlobalTransform::IDENTITY).id();
+        world.spawn((
+            unit_cube,
+            GlobalTransform::from(
+                Transform::from_xyz(0., 2., 0.)
+                    .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_4)),
+            ),
+            ChildOf(parent),
+        ));
+        world.insert_resource(SelectedEntities(vec![parent]));
+
+        let combined = bounds(&mut world, SelectionBoundsMode::Combined);
+        let half_diagonal = 0.5 * std::f32::consts::SQRT_2;
+        assert!(Vec3::from(combined[0].max)
+            .abs_diff_eq(Vec3::new(half_diagonal, 2.5, half_diagonal), 1e-5));
+    }
+}
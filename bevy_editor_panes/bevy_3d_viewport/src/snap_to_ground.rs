@@ -0,0 +1,184 @@
+//! Snapping the selected entities down onto the surface below them.
+
+use bevy::{
+    picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings},
+    prelude::*,
+    render::primitives::Aabb,
+};
+use bevy_editor_core::{actions, is_locked, EditorKeybindings, Locked, SelectedEntities};
+use bevy_undo::{ComponentChange, NewChange};
+
+use crate::Active;
+
+pub(crate) struct SnapToGroundPlugin;
+
+impl Plugin for SnapToGroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, snap_to_ground_shortcut);
+    }
+}
+
+/// Pressing `End` while hovering a viewport snaps the selected entities to the ground,
+/// `Shift + End` also aligns it to the surface normal. Both can be remapped in
+/// [`EditorKeybindings`].
+fn snap_to_ground_shortcut(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    selected_entities: Res<SelectedEntities>,
+    active_viewports: Query<(), With<Active>>,
+    locked_query: Query<&Locked>,
+    parent_query: Query<&ChildOf>,
+) {
+    let align_to_normal = if keybindings.is_action_pressed(actions::SNAP_TO_GROUND, &keyboard) {
+        false
+    } else if keybindings.is_action_pressed(actions::SNAP_TO_GROUND_ALIGNED, &keyboard) {
+        true
+    } else {
+        return;
+    };
+    if active_viewports.is_empty() {
+        return;
+    }
+    for &entity in &selected_entities.0 {
+        if !is_locked(entity, &locked_query, &parent_query) {
+            commands.run_system_cached_with(snap_to_ground, (entity, align_to_normal));
+        }
+    }
+}
+
+/// Drops an entity straight down onto the first mesh below it, or onto the grid plane when there is
+/// no mesh, so that the bottom of its bounding box rests on the hit point.
+/// When `align_to_normal` is set, the entity is also rotated so its up axis matches the surface normal.
+/// The move is recorded for undo.
+pub(crate) fn snap_to_ground(
+    In((entity, align_to_normal)): In<(Entity, bool)>,
+    mut ray_cast: MeshRayCast,
+    mut transform_query: Query<(&mut Transform, &GlobalTransform, Option<&Aabb>)>,
+    global_transform_query: Query<&GlobalTransform>,
+    parent_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let Ok((_, global_transform, aabb)) = transform_query.get(entity) else {
+        return;
+    };
+    let aabb = aabb.copied().unwrap_or_default();
+    let matrix = Mat3::from(global_transform.affine().matrix3);
+    let origin = global_transform.translation();
+
+    // Cast from the bottom of the entity, ignoring the entity itself
+    let excluded: Vec<Entity> = std::iter::once(entity)
+        .chain(children_query.iter_descendants(entity))
+        .collect();
+    let filter = |hit: Entity| !excluded.contains(&hit);
+    let settings = MeshRayCastSettings::default().with_filter(&filter);
+
+    let bottom = origin + bottom_offset(matrix, &aabb, Vec3::Y);
+    let ray = Ray3d::new(bottom, Dir3::NEG_Y);
+
+    let (point, normal) = match ray_cast.cast_ray(ray, &settings).first() {
+        Some((_, hit)) => (hit.point, hit.normal),
+        // Fall back to the grid plane
+        None if bottom.y > 0. => (bottom.with_y(0.), Vec3::Y),
+        None => return,
+    };
+
+    let world_rotation = global_transform.rotation();
+    let (alignment, up) = if align_to_normal {
+        let normal = normal.normalize_or(Vec3::Y);
+        let up = world_rotation * Vec3::Y;
+        (Quat::from_rotation_arc(up, normal), normal)
+    } else {
+        (Quat::IDENTITY, Vec3::Y)
+    };
+
+    let new_origin = point - bottom_offset(Mat3::from_quat(alignment) * matrix, &aabb, up);
+
+    // Convert back to the entity's local space
+    let parent_transform = parent_query
+        .get(entity)
+        .ok()
+        .and_then(|child_of| global_transform_query.get(child_of.parent()).ok())
+        .copied()
+        .unwrap_or_default();
+    let parent_rotation = parent_transform.rotation();
+    let parent_inverse = parent_transform.affine().inverse();
+
+    let (mut transform, ..) = transform_query.get_mut(entity).unwrap();
+    let old_transform = *transform;
+    transform.translation += parent_inverse.transform_vector3(new_origin - origin);
+    if align_to_normal {
+        transform.rotation = parent_rotation.inverse() * alignment * world_rotation;
+    }
+    new_changes.write(NewChange::new(ComponentChange::new(
+        entity,
+        old_transform,
+        *transform,
+    )));
+}
+
+/// The offset from the entity origin to the lowest point of its bounding box along `up`,
+/// with `matrix` being the world-space rotation and scale of the entity.
+fn bottom_offset(matrix: Mat3, aabb: &Aabb, up: Vec3) -> Vec3 {
+    let half_extents = Vec3::from(aabb.half_extents);
+    let extent = (0..3)
+        .map(|axis| matrix.col(axis).dot(up).abs() * half_extents[axis])
+        .sum::<f32>();
+    matrix * Vec3::from(aabb.center) - up * extent
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::primitives::Aabb;
+
+    use super::*;
+
+    #[test]
+    fn floating_mesh_settles_onto_plane_below() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .add_event::<NewChange>();
+
+        let world = app.world_mut();
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let plane = meshes.add(Plane3d::default().mesh().size(10., 10.));
+        let cube = meshes.add(Cuboid::default());
+        // Ray casts only hit meshes that are visible in a view
+        let mut view_visibility = ViewVisibility::HIDDEN;
+        view_visibility.set();
+        let ground = Transform::from_xyz(0., 1., 0.);
+        world.spawn((
+            Mesh3d(plane),
+            ground,
+            GlobalTransform::from(ground),
+            Aabb::from_min_max(Vec3::new(-5., -0.01, -5.), Vec3::new(5., 0.01, 5.)),
+            InheritedVisibility::VISIBLE,
+            view_visibility,
+        ));
+        let floating = Transform::from_xyz(2., 4., 1.);
+        let entity = world
+            .spawn((
+                Mesh3d(cube),
+                floating,
+                GlobalTransform::from(floating),
+                Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)),
+                InheritedVisibility::VISIBLE,
+                view_visibility,
+            ))
+            .id();
+
+        world
+            .run_system_cached_with(snap_to_ground, (entity, false))
+            .unwrap();
+
+        // The bottom of the cube now rests on the plane
+        let transform = *world.get::<Transform>(entity).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(2., 1.5, 1.), 1e-4));
+        assert_eq!(transform.rotation, Quat::IDENTITY);
+        assert_eq!(world.resource::<Events<NewChange>>().len(), 1);
+    }
+}
@@ -11,12 +11,12 @@ use bevy::{
         view::RenderLayers,
     },
 };
-use bevy_editor_cam::prelude::EditorCam;
+use bevy_editor_core::EditorRenderLayers;
+
+use crate::Bevy3dViewport;
 
 // That value was picked arbitrarily
 pub const VIEW_GIZMO_TEXTURE_SIZE: u32 = 125;
-// TODO we really shouldn't just hardcode view layers like that
-pub const VIEW_GIZMO_LAYER: usize = 22;
 
 const GIZMO_CAMERA_ZOOM: f32 = 3.5;
 
@@ -28,12 +28,20 @@ impl Plugin for ViewGizmoPlugin {
     }
 }
 
+/// The camera rendering the view gizmo of a viewport.
 #[derive(Component)]
-pub struct ViewGizmoCamera;
+pub struct ViewGizmoCamera {
+    /// The camera of the viewport the gizmo is shown in, whose rotation the gizmo follows.
+    pub viewport_camera: Entity,
+}
 
 #[derive(Component)]
 pub struct ViewGizmoCameraTarget(pub Handle<Image>);
 
+/// Marks a [`ViewGizmoCameraTarget`] whose camera was spawned.
+#[derive(Component)]
+struct HasViewGizmoCamera;
+
 pub fn spawn_view_gizmo_target_texture(
     mut images: ResMut<'_, Assets<Image>>,
     parent: &mut RelatedSpawnerCommands<ChildOf>,
@@ -79,9 +87,10 @@ fn setup_view_gizmo(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
+    render_layers: Res<EditorRenderLayers>,
 ) {
     info!("Spawning View Gizmo");
-    let view_gizmo_pass_layer = RenderLayers::layer(VIEW_GIZMO_LAYER);
+    let view_gizmo_pass_layer = RenderLayers::layer(render_layers.view_gizmo);
     let sphere = meshes.add(Sphere::new(0.2).mesh().uv(32, 18));
 
     for axis in [
@@ -133,12 +142,23 @@ fn setup_view_gizmo(
     ));
 }
 
+/// Spawns the camera of each view gizmo target in a viewport. Targets that aren't in a viewport yet
+/// are tried again on the next frame.
 fn spawn_view_gizmo_camera(
     mut commands: Commands,
-    q: Query<&ViewGizmoCameraTarget, Added<ViewGizmoCameraTarget>>,
+    q: Query<(Entity, &ViewGizmoCameraTarget), Without<HasViewGizmoCamera>>,
+    parent_query: Query<&ChildOf>,
+    viewports: Query<&Bevy3dViewport>,
+    render_layers: Res<EditorRenderLayers>,
 ) {
-    let view_gizmo_pass_layer = RenderLayers::layer(VIEW_GIZMO_LAYER);
-    for target in &q {
+    let view_gizmo_pass_layer = RenderLayers::layer(render_layers.view_gizmo);
+    for (entity, target) in &q {
+        let Some(viewport) = parent_query
+            .iter_ancestors(entity)
+            .find_map(|ancestor| viewports.get(ancestor).ok())
+        else {
+            continue;
+        };
         commands.spawn((
             Camera3d::default(),
             Camera {
@@ -148,19 +168,108 @@ fn spawn_view_gizmo_camera(
             },
             Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)).looking_at(Vec3::ZERO, Vec3::Y),
             view_gizmo_pass_layer.clone(),
-            ViewGizmoCamera,
+            ViewGizmoCamera {
+                viewport_camera: viewport.camera_id,
+            },
         ));
+        commands.entity(entity).insert(HasViewGizmoCamera);
     }
 }
 
+/// Rotates each view gizmo camera like the camera of its viewport, and despawns the gizmo
+/// cameras of removed viewports.
 fn update_view_gizmo(
-    mut view_cube_camera: Query<&mut Transform, (With<ViewGizmoCamera>, With<Camera3d>)>,
-    viewport_camera: Query<&Transform, (Without<ViewGizmoCamera>, With<Camera3d>, With<EditorCam>)>,
+    mut commands: Commands,
+    mut view_cube_camera: Query<(Entity, &ViewGizmoCamera, &mut Transform)>,
+    viewport_camera: Query<&Transform, Without<ViewGizmoCamera>>,
 ) {
-    for mut transform in &mut view_cube_camera {
-        if let Ok(viewport_camera_transform) = viewport_camera.single() {
-            transform.translation = viewport_camera_transform.back() * GIZMO_CAMERA_ZOOM;
-            transform.rotation = viewport_camera_transform.rotation;
+    for (entity, gizmo_camera, mut transform) in &mut view_cube_camera {
+        let Ok(viewport_camera_transform) = viewport_camera.get(gizmo_camera.viewport_camera)
+        else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        transform.translation = viewport_camera_transform.back() * GIZMO_CAMERA_ZOOM;
+        transform.rotation = viewport_camera_transform.rotation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_view_gizmo_follows_its_own_viewport() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_resource::<EditorRenderLayers>()
+            .add_systems(Update, (spawn_view_gizmo_camera, update_view_gizmo).chain());
+
+        let world = app.world_mut();
+        let cameras = [
+            Transform::from_xyz(0., 10., 0.).looking_at(Vec3::ZERO, Vec3::Z),
+            Transform::from_xyz(5., 5., 5.).looking_at(Vec3::ZERO, Vec3::Y),
+        ]
+        .map(|transform| world.spawn(transform).id());
+        for camera_id in cameras {
+            let pane = world.spawn(Bevy3dViewport { camera_id }).id();
+            let viewport_image = world.spawn(ChildOf(pane)).id();
+            let image = world.resource_mut::<Assets<Image>>().add(Image::default());
+            world.spawn((ViewGizmoCameraTarget(image), ChildOf(viewport_image)));
+        }
+        app.update();
+
+        let world = app.world_mut();
+        let mut gizmo_cameras = world.query::<(&ViewGizmoCamera, &Camera, &Transform)>();
+        let gizmo_cameras: Vec<(Entity, Handle<Image>, Quat)> = gizmo_cameras
+            .iter(world)
+            .map(|(gizmo_camera, camera, transform)| {
+                (
+                    gizmo_camera.viewport_camera,
+                    camera.target.as_image().unwrap().clone(),
+                    transform.rotation,
+                )
+            })
+            .collect();
+        assert_eq!(gizmo_cameras.len(), 2);
+        assert_ne!(gizmo_cameras[0].0, gizmo_cameras[1].0);
+        assert_ne!(gizmo_cameras[0].1, gizmo_cameras[1].1);
+        for (viewport_camera, _, rotation) in &gizmo_cameras {
+            let camera_rotation = world.get::<Transform>(*viewport_camera).unwrap().rotation;
+            assert!(rotation.abs_diff_eq(camera_rotation, 1e-5));
         }
+
+        // Removing a viewport camera removes its gizmo camera
+        world.despawn(cameras[0]);
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&ViewGizmoCamera>().iter(world).count(), 1);
+    }
+
+    #[test]
+    fn target_added_before_its_viewport_gets_a_gizmo_camera() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_resource::<EditorRenderLayers>()
+            .add_systems(Update, spawn_view_gizmo_camera);
+
+        let world = app.world_mut();
+        let image = world.resource_mut::<Assets<Image>>().add(Image::default());
+        let target = world.spawn(ViewGizmoCameraTarget(image)).id();
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&ViewGizmoCamera>().iter(world).count(), 0);
+
+        let camera_id = world.spawn(Transform::default()).id();
+        let pane = world.spawn(Bevy3dViewport { camera_id }).id();
+        world.entity_mut(target).insert(ChildOf(pane));
+        app.update();
+        app.update();
+
+        // Only one camera is spawned once the target is in the viewport
+        let world = app.world_mut();
+        assert_eq!(world.query::<&ViewGizmoCamera>().iter(world).count(), 1);
     }
 }
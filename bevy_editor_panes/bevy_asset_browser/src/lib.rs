@@ -11,6 +11,7 @@ use bevy::{
     },
     prelude::*,
 };
+use bevy_editor_styles::icons;
 use bevy_pane_layout::prelude::*;
 use bevy_scroll_box::ScrollBoxPlugin;
 use ui::top_bar::location_as_changed;
@@ -27,7 +28,11 @@ impl Plugin for AssetBrowserPanePlugin {
         embedded_asset!(app, "assets/source_icon.png");
         embedded_asset!(app, "assets/file_icon.png");
 
-        app.register_pane("Asset Browser", ui::on_pane_creation);
+        app.register_pane_with_tab(
+            "Asset Browser",
+            PaneTab::new(icons::FOLDER),
+            ui::on_pane_creation,
+        );
 
         // Fetch the AssetPlugin file path, this is used to create assets at the correct location
         let default_source_absolute_file_path = {
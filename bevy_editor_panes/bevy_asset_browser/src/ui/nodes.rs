@@ -2,12 +2,16 @@
 
 use atomicow::CowArc;
 use bevy::{
-    asset::io::{AssetSource, AssetSourceBuilders, AssetSourceId},
+    asset::{
+        io::{AssetSource, AssetSourceBuilders, AssetSourceId},
+        AssetPath,
+    },
     prelude::*,
     window::SystemCursorIcon,
     winit::cursor::CursorIcon,
 };
 use bevy_context_menu::{ContextMenu, ContextMenuOption};
+use bevy_editor_core::DraggableAsset;
 use bevy_editor_styles::Theme;
 
 use crate::{io, ui::source_id_to_string, AssetBrowserLocation};
@@ -157,6 +161,12 @@ pub(crate) fn spawn_file_node<'a>(
 ) -> EntityCommands<'a> {
     let base_node = {
         let mut ec = spawn_base_node(commands, theme);
+        // Files can be dragged onto the viewport to spawn them
+        if let Some(source_id) = &location.source_id {
+            let path =
+                AssetPath::from(location.path.join(&file_name)).with_source(source_id.clone());
+            ec.insert(DraggableAsset(path.into_owned()));
+        }
         if location.source_id == Some(AssetSourceId::Default) {
             ec.insert(ContextMenu::new([
                 // ContextMenuOption::new("Rename", |mut commands, entity| {
@@ -0,0 +1,250 @@
+//! A pane listing the undo history of the editor.
+//!
+//! Each recorded change is shown with its [`description`](EditorChange::description), and
+//! clicking an entry undoes or redoes every change needed to get back to that point at once.
+
+use bevy::prelude::*;
+use bevy_editor_styles::{icons, Theme};
+use bevy_i_cant_believe_its_not_bsn::{on, template, Template, TemplateEntityCommandsExt};
+use bevy_pane_layout::prelude::{PaneAppExt, PaneStructure, PaneTab};
+use bevy_undo::{ChangeChain, EditorChange, UndoPlugin, UndoRedo, UndoSet};
+
+/// The entry standing for the scene before any recorded change, jumping to it undoes everything.
+const INITIAL_STATE: &str = "Initial State";
+
+/// Plugin for the editor history pane.
+pub struct HistoryPanePlugin;
+
+impl Plugin for HistoryPanePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<UndoPlugin>() {
+            app.add_plugins(UndoPlugin);
+        }
+
+        app.register_pane_with_tab("History", PaneTab::new(icons::HISTORY), setup_pane)
+            .add_systems(PostUpdate, update_history_pane.after(UndoSet::Global));
+    }
+}
+
+/// The node listing the entries of the undo history.
+#[derive(Component)]
+struct HistoryList;
+
+fn setup_pane(pane: In<PaneStructure>, mut commands: Commands) {
+    commands.entity(pane.content).insert((
+        HistoryList,
+        Node {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.0,
+            row_gap: Val::Px(2.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            overflow: Overflow::clip(),
+            ..Default::default()
+        },
+    ));
+}
+
+/// Lists the applied changes followed by the undone ones, which can still be redone.
+fn update_history_pane(
+    mut commands: Commands,
+    lists: Query<Entity, With<HistoryList>>,
+    new_lists: Query<(), Added<HistoryList>>,
+    change_chain: Res<ChangeChain>,
+    theme: Res<Theme>,
+) {
+    if !change_chain.is_changed() && new_lists.is_empty() {
+        return;
+    }
+
+    let applied = change_chain.changes.len();
+    for list in &lists {
+        let rows: Template = std::iter::once(INITIAL_STATE.to_string())
+            .chain(
+                change_chain
+                    .changes
+                    .iter()
+                    .map(|change| change.description()),
+            )
+            .chain(
+                change_chain
+                    .changes_for_redo
+                    .iter()
+                    .rev()
+                    .map(|change| change.description()),
+            )
+            .enumerate()
+            .flat_map(|(index, description)| history_row(index, &description, applied, &theme))
+            .collect();
+
+        commands.entity(list).build_children(rows);
+    }
+}
+
+/// The entry reached after applying `index` changes, highlighted when it is the current one.
+fn history_row(index: usize, description: &str, applied: usize, theme: &Theme) -> Template {
+    let jump_on_click = move |mut trigger: On<Pointer<Click>>,
+                              mut undo_redo: EventWriter<UndoRedo>| {
+        trigger.propagate(false);
+        if trigger.event().button == PointerButton::Primary {
+            undo_redo.write(UndoRedo::JumpTo(index));
+        }
+    };
+    let text_color = if index <= applied {
+        theme.text.text_color
+    } else {
+        // Undone changes that would be redone by jumping to them
+        theme.text.low_priority
+    };
+
+    template! {
+        {index}: (
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                ..Default::default()
+            },
+            if index == applied { theme.context_menu.hover_color } else { BackgroundColor(Color::NONE) },
+            theme.context_menu.option_border_radius,
+        ) => [
+            on(jump_on_click);
+            (
+                Text(description.into()),
+                TextFont {
+                    font: theme.text.font.clone(),
+                    font_size: 12.0,
+                    ..Default::default()
+                },
+                TextColor(text_color),
+                Pickable::IGNORE,
+            );
+        ];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy::platform::collections::HashMap;
+    use bevy_undo::{ChangeResult, NewChange};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Value(u32);
+
+    struct SetValue {
+        name: &'static str,
+        old: u32,
+        new: u32,
+    }
+
+    impl EditorChange for SetValue {
+        fn revert(
+            &self,
+            world: &mut World,
+            _entity_remap: &HashMap<Entity, Entity>,
+        ) -> Result<ChangeResult, String> {
+            world.resource_mut::<Value>().0 = self.old;
+            Ok(ChangeResult::Success)
+        }
+
+        fn debug_text(&self) -> String {
+            format!("SetValue from {} to {}", self.old, self.new)
+        }
+
+        fn description(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+            Arc::new(SetValue {
+                name: self.name,
+                old: self.new,
+                new: self.old,
+            })
+        }
+    }
+
+    fn rendered_rows(world: &World, list: Entity) -> Vec<(String, Color)> {
+        world
+            .get::<Children>(list)
+            .unwrap()
+            .iter()
+            .map(|row| {
+                let label = world.get::<Children>(row).unwrap()[0];
+                (
+                    world.get::<Text>(label).unwrap().0.clone(),
+                    world.get::<TextColor>(label).unwrap().0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn jumping_to_first_change_reverts_later_ones() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .init_resource::<Value>()
+            .add_plugins(HistoryPanePlugin);
+        let list = app.world_mut().spawn(HistoryList).id();
+
+        for (name, new) in [
+            ("Move Node", 1),
+            ("Add Component Transform", 2),
+            ("Rename", 3),
+        ] {
+            app.world_mut().resource_mut::<Value>().0 = new;
+            app.world_mut().send_event(NewChange::new(SetValue {
+                name,
+                old: new - 1,
+                new,
+            }));
+            // Changes are recorded on the first frame without new ones, so each gets its own entry
+            app.update();
+            app.update();
+        }
+        let labels: Vec<String> = rendered_rows(app.world(), list)
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                INITIAL_STATE,
+                "Move Node",
+                "Add Component Transform",
+                "Rename"
+            ]
+        );
+
+        app.world_mut().send_event(UndoRedo::JumpTo(1));
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.resource::<Value>().0, 1);
+        let change_chain = world.resource::<ChangeChain>();
+        assert_eq!(change_chain.changes.len(), 1);
+        assert_eq!(change_chain.changes_for_redo.len(), 2);
+
+        // The reverted changes are still listed, now dimmed as they can be redone
+        let theme = world.resource::<Theme>();
+        let rows = rendered_rows(world, list);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1], ("Move Node".to_string(), theme.text.text_color));
+        assert_eq!(
+            rows[2],
+            (
+                "Add Component Transform".to_string(),
+                theme.text.low_priority
+            )
+        );
+        assert_eq!(rows[3], ("Rename".to_string(), theme.text.low_priority));
+
+        // Jumping past the end redoes everything
+        app.world_mut().send_event(UndoRedo::JumpTo(usize::MAX));
+        app.update();
+        assert_eq!(app.world().resource::<Value>().0, 3);
+    }
+}
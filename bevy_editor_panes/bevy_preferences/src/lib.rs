@@ -1,17 +1,317 @@
 //! A ui for setting preferences in bevy editor.
+//!
+//! The settings pane lists the resources marked with a [`SettingsType`]. `bool` settings are
+//! toggled by clicking them and `f32` settings are changed by dragging them horizontally. The
+//! `EditorSettingsPlugin` saves the workspace settings changed here.
 
-/// an add function that adds two numbers
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    reflect::{GetPath, ReflectFromPtr, ReflectRef, TypeInfo},
+};
+use bevy_editor_settings::SettingsType;
+use bevy_editor_styles::{icons, Theme};
+use bevy_i_cant_believe_its_not_bsn::{
+    on, template, Callback, Template, TemplateEntityCommandsExt,
+};
+use bevy_pane_layout::prelude::{PaneAppExt, PaneStructure, PaneTab};
+
+/// The change of `f32` settings per logical pixel dragged.
+const DRAG_SENSITIVITY: f32 = 0.01;
+
+/// Plugin for the editor settings pane.
+pub struct PreferencesPanePlugin;
+
+impl Plugin for PreferencesPanePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_pane_with_tab(
+            "Settings",
+            PaneTab::new(icons::SLIDERS_HORIZONTAL),
+            setup_pane,
+        )
+        .add_systems(PostUpdate, update_settings_pane);
+    }
+}
+
+/// The node listing the settings.
+#[derive(Component)]
+struct SettingsList;
+
+fn setup_pane(pane: In<PaneStructure>, mut commands: Commands) {
+    commands.entity(pane.content).insert((
+        SettingsList,
+        Node {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.0,
+            row_gap: Val::Px(6.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            overflow: Overflow::clip(),
+            ..Default::default()
+        },
+    ));
+}
+
+fn update_settings_pane(
+    lists: Query<Entity, With<SettingsList>>,
+    world: &World,
+    mut commands: Commands,
+) {
+    for list in &lists {
+        commands.entity(list).build_children(settings_list(world));
+    }
+}
+
+/// A section per settings resource, with a row per field.
+fn settings_list(world: &World) -> Template {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let theme = world.get_resource::<Theme>();
+    let text_color = theme.map_or(Color::WHITE, |theme| theme.text.text_color);
+    let font = theme
+        .map(|theme| theme.text.font.clone())
+        .unwrap_or_default();
+
+    let mut sections: Vec<(&str, Template)> = world
+        .iter_resources()
+        .filter_map(|(info, ptr)| {
+            let type_id = info.type_id()?;
+            let registration = registry.get(type_id)?;
+            let TypeInfo::Struct(struct_info) = registration.type_info() else {
+                return None;
+            };
+            struct_info.custom_attributes().get::<SettingsType>()?;
+            let reflect_from_ptr = registration.data::<ReflectFromPtr>()?;
+            #[allow(unsafe_code)]
+            // SAFETY: `ptr` points to the resource of the type the `ReflectFromPtr` was created for
+            let resource = unsafe { reflect_from_ptr.as_reflect(ptr) };
+            let ReflectRef::Struct(strct) = resource.reflect_ref() else {
+                return None;
+            };
+
+            let fields = (0..strct.field_len())
+                .filter_map(|i| setting_row(type_id, strct.name_at(i)?, strct.field_at(i)?))
+                .flatten()
+                .collect();
+            Some((struct_info.type_path_table().short_path(), fields))
+        })
+        .collect();
+    // Resources aren't stored in a stable order, sort them so the sections don't move around
+    sections.sort_by_key(|(name, _)| *name);
+
+    sections
+        .into_iter()
+        .flat_map(|(name, fields)| {
+            template! {
+                {name}: Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    ..Default::default()
+                } => [
+                    (
+                        Text(name.into()),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 13.0,
+                            ..Default::default()
+                        },
+                        TextColor(text_color),
+                    );
+                    @{ fields };
+                ];
+            }
+        })
+        .collect()
+}
+
+/// The row changing the field `name` of the settings resource `type_id`, or `None` when the
+/// field isn't a `bool` or an `f32`.
+fn setting_row(type_id: TypeId, name: &str, value: &dyn PartialReflect) -> Option<Template> {
+    let handle = if let Some(value) = value.try_downcast_ref::<bool>() {
+        toggle_handle(type_id, name, *value)
+    } else if let Some(value) = value.try_downcast_ref::<f32>() {
+        drag_handle(type_id, name, *value)
+    } else {
+        return None;
+    };
+
+    Some(template! {
+        {name}: Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(6.0),
+            ..Default::default()
+        } => [
+            (
+                Text(name.into()),
+                TextFont::from_font_size(12.0),
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            );
+            @{ handle };
+        ];
+    })
+}
+
+/// A `bool` setting that is toggled when clicked.
+fn toggle_handle(type_id: TypeId, name: &str, value: bool) -> Template {
+    let field = name.to_string();
+    let toggle_on_click = move |mut trigger: On<Pointer<Click>>, mut commands: Commands| {
+        trigger.propagate(false);
+        if trigger.event().button == PointerButton::Primary {
+            commands.run_system_cached_with(toggle_setting, (type_id, field.clone()));
+        }
+    };
+
+    value_handle(value.to_string(), on(toggle_on_click))
+}
+
+/// An `f32` setting that changes when dragged horizontally.
+fn drag_handle(type_id: TypeId, name: &str, value: f32) -> Template {
+    let field = name.to_string();
+    let change_on_drag = move |mut trigger: On<Pointer<Drag>>, mut commands: Commands| {
+        trigger.propagate(false);
+        if trigger.event().button == PointerButton::Primary {
+            let delta = trigger.event().delta.x * DRAG_SENSITIVITY;
+            commands.run_system_cached_with(drag_setting, (type_id, field.clone(), delta));
+        }
+    };
+
+    value_handle(format!("{value:.3}"), on(change_on_drag))
+}
+
+/// The value of a setting, changed by the observer of `callback`.
+fn value_handle(text: String, callback: Callback) -> Template {
+    template! {
+        (
+            Node {
+                padding: UiRect::horizontal(Val::Px(3.0)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(2.0)),
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+        ) => [
+            callback;
+            (
+                Text(text),
+                TextFont::from_font_size(10.0),
+                TextColor(Color::WHITE),
+                Pickable::IGNORE,
+            );
+        ];
+    }
+}
+
+/// Flips the `bool` field `name` of the settings resource `type_id`.
+fn toggle_setting(In((type_id, name)): In<(TypeId, String)>, world: &mut World) {
+    change_setting(world, type_id, &name, |field| {
+        if let Some(value) = field.try_downcast_mut::<bool>() {
+            *value = !*value;
+        }
+    });
+}
+
+/// Changes the `f32` field `name` of the settings resource `type_id` by `delta`.
+fn drag_setting(In((type_id, name, delta)): In<(TypeId, String, f32)>, world: &mut World) {
+    change_setting(world, type_id, &name, |field| {
+        if let Some(value) = field.try_downcast_mut::<f32>() {
+            *value += delta;
+        }
+    });
+}
+
+/// Applies `change` to the field `name` of the settings resource `type_id`, marking it changed so
+/// it gets saved.
+fn change_setting(
+    world: &mut World,
+    type_id: TypeId,
+    name: &str,
+    change: impl FnOnce(&mut dyn PartialReflect),
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let Some(reflect_from_ptr) = registry.get_type_data::<ReflectFromPtr>(type_id) else {
+        return;
+    };
+    let Some(resource_id) = world
+        .iter_resources()
+        .find(|(info, _)| info.type_id() == Some(type_id))
+        .map(|(info, _)| info.id())
+    else {
+        return;
+    };
+    let Some(mut ptr) = world.get_resource_mut_by_id(resource_id) else {
+        return;
+    };
+    #[allow(unsafe_code)]
+    // SAFETY: `ptr` points to the resource of the type the `ReflectFromPtr` was created for
+    let resource = unsafe { reflect_from_ptr.as_reflect_mut(ptr.as_mut()) };
+    if let Ok(field) = resource.reflect_path_mut(name) {
+        change(field);
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use bevy_editor_settings::SettingKey;
+
     use super::*;
 
+    #[derive(Resource, Reflect)]
+    #[reflect(Resource, @SettingsType::Workspace, @SettingKey("snapping"))]
+    struct Snapping {
+        enabled: bool,
+        increment: f32,
+        label: String,
+    }
+
+    /// The texts shown under `entity`, depth first.
+    fn rendered_texts(world: &World, entity: Entity) -> Vec<String> {
+        let mut texts = Vec::new();
+        if let Some(text) = world.get::<Text>(entity) {
+            texts.push(text.0.clone());
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            for &child in children {
+                texts.extend(rendered_texts(world, child));
+            }
+        }
+        texts
+    }
+
     #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    fn settings_are_listed_and_changed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .register_type::<Snapping>()
+            .insert_resource(Snapping {
+                enabled: false,
+                increment: 0.5,
+                label: "grid".into(),
+            })
+            .add_systems(PostUpdate, update_settings_pane);
+        let list = app.world_mut().spawn(SettingsList).id();
+        app.update();
+
+        // Only the fields that can be changed are listed
+        let texts = rendered_texts(app.world(), list);
+        assert_eq!(
+            texts,
+            ["Snapping", "enabled", "false", "increment", "0.500"]
+        );
+
+        let type_id = TypeId::of::<Snapping>();
+        let world = app.world_mut();
+        world
+            .run_system_cached_with(toggle_setting, (type_id, "enabled".into()))
+            .unwrap();
+        world
+            .run_system_cached_with(drag_setting, (type_id, "increment".into(), 0.25))
+            .unwrap();
+        let snapping = world.resource::<Snapping>();
+        assert!(snapping.enabled);
+        assert_eq!(snapping.increment, 0.75);
+        assert_eq!(snapping.label, "grid");
+
+        app.update();
+        let texts = rendered_texts(app.world(), list);
+        assert_eq!(texts, ["Snapping", "enabled", "true", "increment", "0.750"]);
     }
 }
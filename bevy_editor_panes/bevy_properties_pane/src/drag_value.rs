@@ -0,0 +1,341 @@
+//! Numeric fields that can be scrubbed by dragging them horizontally.
+//!
+//! `f32` fields are dragged directly, `Vec2` and `Vec3` fields per axis and `Quat` fields per
+//! Euler angle in degrees. A whole drag gesture is recorded as a single undo step once released.
+//! Fields shared by several selected entities change on all of them at once. Dragged [`Transform`]s
+//! snap to the increments of [`GizmoSnapSettings`] and [`GridSnapConfig`] when the drag ends.
+
+use std::any::TypeId;
+
+use bevy::{prelude::*, reflect::GetPath};
+use bevy_editor_core::is_locked_in_world;
+use bevy_i_cant_believe_its_not_bsn::{on, template, Template};
+use bevy_transform_gizmos::{GizmoSnapSettings, GridSnapConfig};
+use bevy_undo::{DynamicComponentChange, NewChange};
+
+/// How much numeric fields change when dragged.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct DragValueSettings {
+    /// The change of the value per logical pixel dragged.
+    pub sensitivity: f32,
+    /// The change of rotations in degrees per logical pixel dragged.
+    pub rotation_sensitivity: f32,
+    /// The factor applied to the change while `Ctrl` is held, for fine control.
+    pub fine_factor: f32,
+}
+
+impl Default for DragValueSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.01,
+            rotation_sensitivity: 0.5,
+            fine_factor: 0.1,
+        }
+    }
+}
+
+impl DragValueSettings {
+    /// The change of the value dragged by `pixels`, in degrees for rotations.
+    pub fn delta(&self, pixels: f32, rotation: bool, fine: bool) -> f32 {
+        let sensitivity = if rotation {
+            self.rotation_sensitivity
+        } else {
+            self.sensitivity
+        };
+        let factor = if fine { self.fine_factor } else { 1.0 };
+        pixels * sensitivity * factor
+    }
+}
+
+/// The numeric value a drag handle changes.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DragTarget {
+    /// The entities whose component holds the value, each changed by the same amount.
+    pub(crate) entities: Vec<Entity>,
+    /// The type of the component holding the value.
+    pub(crate) type_id: TypeId,
+    /// The reflection path of the value in the component, like `translation.x`.
+    pub(crate) path: String,
+    /// The Euler angle changed when the value is a rotation.
+    pub(crate) euler_axis: Option<usize>,
+}
+
+/// The drag gesture in progress, with the value of the component of each dragged entity when it
+/// started.
+#[derive(Resource, Default)]
+pub(crate) struct DragGesture(Option<(DragTarget, Vec<(Entity, Box<dyn PartialReflect>)>)>);
+
+/// Drag handles for the field `name` of the component `type_id` of `entities`, or `None` when
+/// the field isn't numeric. `value` is the value shown on the handles.
+pub(crate) fn numeric_field(
+    entities: &[Entity],
+    type_id: TypeId,
+    name: &str,
+    value: &dyn PartialReflect,
+) -> Option<Template> {
+    let target = |path: String, euler_axis| DragTarget {
+        entities: entities.to_vec(),
+        type_id,
+        path,
+        euler_axis,
+    };
+    let axes = ["x", "y", "z"];
+
+    let handles: Template = if let Some(value) = value.try_downcast_ref::<f32>() {
+        drag_handle(format!("{value:.3}"), target(name.to_string(), None))
+    } else if let Some(value) = value.try_downcast_ref::<Vec2>() {
+        axes.iter()
+            .zip(value.to_array())
+            .flat_map(|(axis, value)| {
+                drag_handle(
+                    format!("{axis} {value:.3}"),
+                    target(format!("{name}.{axis}"), None),
+                )
+            })
+            .collect()
+    } else if let Some(value) = value.try_downcast_ref::<Vec3>() {
+        axes.iter()
+            .zip(value.to_array())
+            .flat_map(|(axis, value)| {
+                drag_handle(
+                    format!("{axis} {value:.3}"),
+                    target(format!("{name}.{axis}"), None),
+                )
+            })
+            .collect()
+    } else if let Some(value) = value.try_downcast_ref::<Quat>() {
+        let (x, y, z) = value.to_euler(EulerRot::XYZ);
+        axes.iter()
+            .zip([x, y, z])
+            .enumerate()
+            .flat_map(|(index, (axis, angle))| {
+                drag_handle(
+                    format!("{axis} {:.1}°", angle.to_degrees()),
+                    target(name.to_string(), Some(index)),
+                )
+            })
+            .collect()
+    } else {
+        return None;
+    };
+
+    Some(template! {
+        Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(4.0),
+            ..Default::default()
+        } => [ @{ handles }; ];
+    })
+}
+
+/// A value that changes when dragged horizontally, `Ctrl` slows the change down.
+fn drag_handle(text: String, target: DragTarget) -> Template {
+    let begin_on_drag_start = {
+        let target = target.clone();
+        move |mut trigger: On<Pointer<DragStart>>, mut commands: Commands| {
+            trigger.propagate(false);
+            if trigger.event().button == PointerButton::Primary {
+                commands.run_system_cached_with(begin_drag, target.clone());
+            }
+        }
+    };
+    let change_on_drag = {
+        let target = target.clone();
+        move |mut trigger: On<Pointer<Drag>>,
+              mut commands: Commands,
+              keyboard: Res<ButtonInput<KeyCode>>,
+              settings: Res<DragValueSettings>| {
+            trigger.propagate(false);
+            if trigger.event().button != PointerButton::Primary {
+                return;
+            }
+            let fine = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+            let delta = settings.delta(trigger.event().delta.x, target.euler_axis.is_some(), fine);
+            commands.run_system_cached_with(drag_value, (target.clone(), delta));
+        }
+    };
+    let commit_on_drag_end = move |mut trigger: On<Pointer<DragEnd>>, mut commands: Commands| {
+        trigger.propagate(false);
+        if trigger.event().button == PointerButton::Primary {
+            commands.run_system_cached(end_drag);
+        }
+    };
+
+    template! {
+        (
+            Node {
+                padding: UiRect::horizontal(Val::Px(3.0)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(2.0)),
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+        ) => [
+            on(begin_on_drag_start);
+            on(change_on_drag);
+            on(commit_on_drag_end);
+            (
+                Text(text),
+                TextFont::from_font_size(10.0),
+                TextColor(Color::WHITE),
+                Pickable::IGNORE,
+            );
+        ];
+    }
+}
+
+/// Starts a drag gesture on `target`, remembering the values of its components so the whole
+/// gesture can be undone at once. Locked entities are left out of the gesture.
+pub(crate) fn begin_drag(In(target): In<DragTarget>, world: &mut World) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(target.type_id)
+    else {
+        return;
+    };
+    let old_values: Vec<(Entity, Box<dyn PartialReflect>)> = target
+        .entities
+        .iter()
+        .filter(|&&entity| !is_locked_in_world(world, entity))
+        .filter_map(|&entity| {
+            let value = reflect_component.reflect(world.get_entity(entity).ok()?)?;
+            Some((entity, value.to_dynamic()))
+        })
+        .collect();
+    if old_values.is_empty() {
+        return;
+    }
+
+    world.resource_mut::<DragGesture>().0 = Some((target, old_values));
+}
+
+/// Changes the value of `target` by `delta` on each dragged entity, in degrees for rotations.
+/// Does nothing unless a drag gesture was started on `target`.
+pub(crate) fn drag_value(In((target, delta)): In<(DragTarget, f32)>, world: &mut World) {
+    let Some(entities) = world
+        .resource::<DragGesture>()
+        .0
+        .as_ref()
+        .filter(|(gesture_target, _)| *gesture_target == target)
+        .map(|(_, old_values)| {
+            old_values
+                .iter()
+                .map(|(entity, _)| *entity)
+                .collect::<Vec<_>>()
+        })
+    else {
+        return;
+    };
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(target.type_id)
+    else {
+        return;
+    };
+
+    for entity in entities {
+        let Ok(entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        let Some(mut component) = reflect_component.reflect_mut(entity_mut) else {
+            continue;
+        };
+        if let Ok(field) = component.reflect_path_mut(target.path.as_str()) {
+            change_field(field, target.euler_axis, delta);
+        }
+    }
+}
+
+/// Changes a numeric `field` by `delta`, or the Euler angle `euler_axis` of a rotation by `delta`
+/// degrees.
+fn change_field(field: &mut dyn PartialReflect, euler_axis: Option<usize>, delta: f32) {
+    match euler_axis {
+        Some(axis) => {
+            if let Some(rotation) = field.try_downcast_mut::<Quat>() {
+                let (x, y, z) = rotation.to_euler(EulerRot::XYZ);
+                let mut angles = [x, y, z];
+                angles[axis] += delta.to_radians();
+                *rotation = Quat::from_euler(EulerRot::XYZ, angles[0], angles[1], angles[2]);
+            }
+        }
+        None => {
+            if let Some(value) = field.try_downcast_mut::<f32>() {
+                *value += delta;
+            }
+        }
+    }
+}
+
+/// Ends the drag gesture in progress, recording the changes of the components for undo.
+/// The changes are sent together, so a gesture on several entities is undone as one step.
+pub(crate) fn end_drag(world: &mut World) {
+    let Some((target, old_values)) = world.resource_mut::<DragGesture>().0.take() else {
+        return;
+    };
+    let snapping = (target.type_id == TypeId::of::<Transform>())
+        .then(|| active_snapping(world))
+        .flatten();
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(target.type_id)
+    else {
+        return;
+    };
+
+    for (entity, old_value) in old_values {
+        if let Some((snap_settings, grid)) = &snapping {
+            snap_dragged_transform(world, entity, old_value.as_ref(), snap_settings, grid);
+        }
+        let Some(new_value) = world
+            .get_entity(entity)
+            .ok()
+            .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+            .map(|value| value.to_dynamic())
+        else {
+            continue;
+        };
+        if old_value
+            .reflect_partial_eq(new_value.as_ref())
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        world.send_event(NewChange::new(DynamicComponentChange {
+            entity,
+            type_id: target.type_id,
+            old_value,
+            new_value,
+        }));
+    }
+}
+
+/// The snapping increments to apply to a dragged [`Transform`], or `None` when snapping isn't
+/// active.
+fn active_snapping(world: &World) -> Option<(GizmoSnapSettings, GridSnapConfig)> {
+    let snap_settings = *world.get_resource::<GizmoSnapSettings>()?;
+    let keyboard = world.get_resource::<ButtonInput<KeyCode>>()?;
+    let grid = world
+        .get_resource::<GridSnapConfig>()
+        .copied()
+        .unwrap_or_default();
+    snap_settings
+        .is_active(keyboard)
+        .then_some((snap_settings, grid))
+}
+
+/// Snaps the [`Transform`] of `entity` dragged away from `start`.
+fn snap_dragged_transform(
+    world: &mut World,
+    entity: Entity,
+    start: &dyn PartialReflect,
+    snap_settings: &GizmoSnapSettings,
+    grid: &GridSnapConfig,
+) {
+    let Some(start) = Transform::from_reflect(start) else {
+        return;
+    };
+    if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+        let snapped = snap_settings.snap_transform(grid, &start, &transform);
+        *transform = snapped;
+    }
+}
@@ -0,0 +1,385 @@
+//! Finding all entities with a component value matching a query, such as
+//! `Transform.translation.y < 0`.
+
+use std::cmp::Ordering;
+
+use bevy::{color::palettes::tailwind, prelude::*, reflect::GetPath};
+use bevy_editor_core::{SelectedEntities, SelectedEntity};
+use bevy_i_cant_believe_its_not_bsn::{on, template, Template};
+
+/// The maximum number of matching entities listed below the query box.
+const MAX_QUERY_RESULTS: usize = 50;
+
+/// A query for the entities whose component value matches a predicate.
+///
+/// Queries are written as `Component.field.path <op> value`, with `<op>` one of `<`, `<=`, `>`,
+/// `>=`, `==` or `!=`, and the value a number, `true`, `false` or a quoted string.
+/// A query with only a component name matches every entity with that component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntityQuery {
+    /// The short type path of the component, e.g. `Transform`.
+    pub component: String,
+    /// The predicate on the component value, if any.
+    pub predicate: Option<QueryPredicate>,
+}
+
+/// A comparison between a field of a component and a value, see [`EntityQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryPredicate {
+    /// The reflection path of the field, e.g. `translation.y`. Empty for the component itself.
+    pub path: String,
+    /// How the field is compared to the value.
+    pub comparison: Comparison,
+    /// The value the field is compared to.
+    pub value: QueryValue,
+}
+
+/// A comparison operator of a [`QueryPredicate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+}
+
+impl Comparison {
+    /// Operators in the order they are tried, so `<=` isn't read as `<`.
+    const OPERATORS: [(&'static str, Comparison); 6] = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::NotEqual => ordering.is_ne(),
+        }
+    }
+}
+
+/// The value on the right-hand side of a [`QueryPredicate`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryValue {
+    /// Compared to any integer or floating point field.
+    Number(f64),
+    /// Compared to `bool` fields.
+    Bool(bool),
+    /// Compared to `String` fields and to [`Name`].
+    String(String),
+}
+
+impl EntityQuery {
+    /// Parse a query, returning `None` if it isn't valid.
+    pub fn parse(source: &str) -> Option<Self> {
+        let source = source.trim();
+        // The first operator in the query splits the field from the value
+        let Some((operator_start, operator, comparison)) =
+            source.char_indices().find_map(|(start, _)| {
+                Comparison::OPERATORS
+                    .iter()
+                    .find(|(operator, _)| source[start..].starts_with(operator))
+                    .map(|(operator, comparison)| (start, *operator, *comparison))
+            })
+        else {
+            return is_identifier(source).then(|| EntityQuery {
+                component: source.to_string(),
+                predicate: None,
+            });
+        };
+
+        let target = source[..operator_start].trim();
+        let value = source[operator_start + operator.len()..].trim();
+        let (component, path) = target.split_once('.').unwrap_or((target, ""));
+        if !is_identifier(component) {
+            return None;
+        }
+
+        Some(EntityQuery {
+            component: component.to_string(),
+            predicate: Some(QueryPredicate {
+                path: path.to_string(),
+                comparison,
+                value: parse_value(value)?,
+            }),
+        })
+    }
+
+    /// All entities matching the query, in the order they are stored in the world.
+    pub fn matching_entities(&self, world: &World) -> Vec<Entity> {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let Some(registration) = type_registry.iter().find(|registration| {
+            registration.type_info().type_path_table().short_path() == self.component
+        }) else {
+            return Vec::new();
+        };
+        let (Some(reflect_component), Some(component_id)) = (
+            registration.data::<ReflectComponent>(),
+            world.components().get_id(registration.type_id()),
+        ) else {
+            return Vec::new();
+        };
+
+        world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(component_id))
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+            .filter(|&entity| {
+                let Some(predicate) = &self.predicate else {
+                    return true;
+                };
+                world
+                    .get_entity(entity)
+                    .ok()
+                    .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+                    .is_some_and(|component| predicate.matches(component.as_partial_reflect()))
+            })
+            .collect()
+    }
+}
+
+impl QueryPredicate {
+    /// Returns `true` if the field at [`path`](Self::path) of `component` satisfies the predicate.
+    /// Fields that don't exist or can't be compared to the value never match.
+    pub fn matches(&self, component: &dyn PartialReflect) -> bool {
+        let field = if self.path.is_empty() {
+            component
+        } else {
+            match component.reflect_path(self.path.as_str()) {
+                Ok(field) => field,
+                Err(_) => return false,
+            }
+        };
+
+        compare(field, &self.value).is_some_and(|ordering| self.comparison.matches(ordering))
+    }
+}
+
+fn is_identifier(source: &str) -> bool {
+    !source.is_empty()
+        && source
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_')
+}
+
+fn parse_value(source: &str) -> Option<QueryValue> {
+    match source {
+        "true" => Some(QueryValue::Bool(true)),
+        "false" => Some(QueryValue::Bool(false)),
+        _ => match source
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+        {
+            Some(string) => Some(QueryValue::String(string.to_string())),
+            None => source.parse().ok().map(QueryValue::Number),
+        },
+    }
+}
+
+/// Orders `field` relative to `value`, or `None` if they can't be compared.
+fn compare(field: &dyn PartialReflect, value: &QueryValue) -> Option<Ordering> {
+    match value {
+        QueryValue::Number(number) => as_number(field)?.partial_cmp(number),
+        QueryValue::Bool(bool) => Some(field.try_downcast_ref::<bool>()?.cmp(bool)),
+        QueryValue::String(string) => {
+            let field = field
+                .try_downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| field.try_downcast_ref::<Name>().map(Name::as_str))?;
+            Some(field.cmp(string.as_str()))
+        }
+    }
+}
+
+fn as_number(field: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! downcast {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = field.try_downcast_ref::<$ty>() {
+                return Some(*value as f64);
+            })*
+        };
+    }
+    downcast!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    None
+}
+
+/// The current contents of the entity query box of a properties pane.
+#[derive(Component, Default)]
+pub(crate) struct EntityQueryText(pub(crate) String);
+
+/// Lists the entities matching `source`, with buttons to select each of them or all at once.
+pub(crate) fn entity_query_results(source: &str, world: &World) -> Template {
+    if source.trim().is_empty() {
+        return template! {};
+    }
+    let Some(query) = EntityQuery::parse(source) else {
+        return template! {
+            (
+                Text("Invalid query".into()),
+                TextFont::from_font_size(12.0),
+                TextColor(tailwind::RED_400.into()),
+            );
+        };
+    };
+
+    let entities = query.matching_entities(world);
+    let select_all_entities = entities.clone();
+    let select_all_on_click = move |mut trigger: On<Pointer<Click>>, mut commands: Commands| {
+        trigger.propagate(false);
+        commands.run_system_cached_with(select_entities, select_all_entities.clone());
+    };
+
+    let summary = template! {
+        Node {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            ..Default::default()
+        } => [
+            (
+                Text(format!("{} matching entities", entities.len())),
+                TextFont::from_font_size(12.0),
+                TextColor(tailwind::NEUTRAL_300.into()),
+            );
+            (
+                Text("Select all".into()),
+                TextFont::from_font_size(12.0),
+                TextColor(Color::WHITE),
+            ) => [
+                on(select_all_on_click);
+            ];
+        ];
+    };
+
+    let rows = entities
+        .into_iter()
+        .take(MAX_QUERY_RESULTS)
+        .flat_map(|entity| {
+            let label = world
+                .get::<Name>(entity)
+                .map_or_else(|| entity.to_string(), |name| format!("{name} ({entity})"));
+            let select_on_click = move |mut trigger: On<Pointer<Click>>, mut commands: Commands| {
+                trigger.propagate(false);
+                commands.run_system_cached_with(select_entities, vec![entity]);
+            };
+
+            template! {
+                (
+                    Text(label),
+                    TextFont::from_font_size(12.0),
+                    TextColor(Color::WHITE),
+                ) => [
+                    on(select_on_click);
+                ];
+            }
+        })
+        .collect::<Template>();
+
+    summary.into_iter().chain(rows).collect()
+}
+
+/// Replaces the selection with `entities`, the first one being the primary selection.
+fn select_entities(
+    In(entities): In<Vec<Entity>>,
+    mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
+) {
+    selected_entity.0 = entities.first().copied();
+    selected_entities.0 = entities;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Enemy {
+        health: u32,
+        boss: bool,
+    }
+
+    #[test]
+    fn parse_queries() {
+        assert_eq!(
+            EntityQuery::parse("Transform.translation.y <= -0.5"),
+            Some(EntityQuery {
+                component: "Transform".into(),
+                predicate: Some(QueryPredicate {
+                    path: "translation.y".into(),
+                    comparison: Comparison::LessOrEqual,
+                    value: QueryValue::Number(-0.5),
+                }),
+            })
+        );
+        assert_eq!(
+            EntityQuery::parse(" Name == \"Door\" ").and_then(|query| query.predicate),
+            Some(QueryPredicate {
+                path: String::new(),
+                comparison: Comparison::Equal,
+                value: QueryValue::String("Door".into()),
+            })
+        );
+        assert_eq!(
+            EntityQuery::parse("Enemy").map(|query| query.predicate),
+            Some(None)
+        );
+        assert_eq!(EntityQuery::parse("Transform.translation.y < ground"), None);
+        assert_eq!(EntityQuery::parse("Global Transform"), None);
+    }
+
+    #[test]
+    fn find_entities_below_the_ground() {
+        let mut app = App::new();
+        app.register_type::<Transform>().register_type::<Enemy>();
+
+        let world = app.world_mut();
+        let below = world.spawn(Transform::from_xyz(0., -1., 0.)).id();
+        world.spawn(Transform::from_xyz(0., 2., 0.));
+        let boss = world
+            .spawn((
+                Transform::from_xyz(3., -0.1, 0.),
+                Enemy {
+                    health: 100,
+                    boss: true,
+                },
+            ))
+            .id();
+        world.spawn(Enemy {
+            health: 10,
+            boss: false,
+        });
+
+        let query = EntityQuery::parse("Transform.translation.y < 0").unwrap();
+        let mut found = query.matching_entities(world);
+        found.sort();
+        let mut expected = vec![below, boss];
+        expected.sort();
+        assert_eq!(found, expected);
+
+        let query = EntityQuery::parse("Enemy.boss == true").unwrap();
+        assert_eq!(query.matching_entities(world), vec![boss]);
+        let query = EntityQuery::parse("Enemy.health >= 50").unwrap();
+        assert_eq!(query.matching_entities(world), vec![boss]);
+
+        // Fields that don't exist never match
+        let query = EntityQuery::parse("Enemy.mana > 0").unwrap();
+        assert!(query.matching_entities(world).is_empty());
+    }
+}
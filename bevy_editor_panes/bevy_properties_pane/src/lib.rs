@@ -2,18 +2,59 @@
 //!
 //! Data can be viewed and modified in real-time, with changes being reflected in the application.
 
-use bevy::{color::palettes::tailwind, prelude::*, reflect::*};
-use bevy_editor_core::SelectedEntity;
-use bevy_i_cant_believe_its_not_bsn::{template, Template, TemplateEntityCommandsExt};
-use bevy_pane_layout::prelude::{PaneAppExt, PaneStructure};
+use std::any::TypeId;
+
+use bevy::{color::palettes::tailwind, ecs::component::ComponentId, prelude::*, reflect::*};
+use bevy_asset_preview::{AssetPreviewPlugin, AssetPreviews, PreviewSource, PREVIEW_SIZE};
+use bevy_editor_core::{is_locked_in_world, SelectedEntities, SelectedEntity};
+use bevy_editor_styles::{icons, Theme};
+use bevy_i_cant_believe_its_not_bsn::{on, template, Template, TemplateEntityCommandsExt};
+use bevy_pane_layout::prelude::{PaneAppExt, PaneStructure, PaneTab};
+use bevy_text_editing::{EditableTextLine, EditableTextLinePlugin, TextChanged};
+use bevy_transform_gizmos::TransformGizmoPlugin;
+use bevy_undo::{DynamicAddedComponent, DynamicRemovedComponent, NewChange, UndoPlugin};
+use drag_value::{numeric_field, DragGesture};
+use entity_query::{entity_query_results, EntityQueryText};
+
+pub use drag_value::DragValueSettings;
+pub use entity_query::{Comparison, EntityQuery, QueryPredicate, QueryValue};
+
+mod drag_value;
+mod entity_query;
+
+/// The maximum number of component types suggested when searching for a component to add.
+const MAX_COMPONENT_SUGGESTIONS: usize = 10;
 
 /// Plugin for the editor properties pane.
 pub struct PropertiesPanePlugin;
 
 impl Plugin for PropertiesPanePlugin {
     fn build(&self, app: &mut App) {
-        app.register_pane("Properties", setup_pane)
-            .add_systems(PostUpdate, update_properties_pane);
+        if !app.is_plugin_added::<EditableTextLinePlugin>() {
+            app.add_plugins(EditableTextLinePlugin);
+        }
+        if !app.is_plugin_added::<UndoPlugin>() {
+            app.add_plugins(UndoPlugin);
+        }
+        if !app.is_plugin_added::<AssetPreviewPlugin>() {
+            app.add_plugins(AssetPreviewPlugin);
+        }
+        if !app.is_plugin_added::<TransformGizmoPlugin>() {
+            app.add_plugins(TransformGizmoPlugin);
+        }
+
+        app.register_pane_with_tab(
+            "Properties",
+            PaneTab::new(icons::SLIDERS_HORIZONTAL),
+            setup_pane,
+        )
+        .init_resource::<DragValueSettings>()
+        .init_resource::<DragGesture>()
+        .add_systems(Update, request_asset_previews)
+        .add_systems(
+            PostUpdate,
+            (update_properties_pane, update_entity_query_results),
+        );
     }
 }
 
@@ -21,36 +62,240 @@ impl Plugin for PropertiesPanePlugin {
 #[derive(Component)]
 struct PropertiesPaneRoot;
 
+/// The current contents of the "Add component" search box of a properties pane.
+#[derive(Component, Default)]
+struct AddComponentFilter(String);
+
+/// The entity a properties pane is pinned to, shown instead of the selection while set.
+#[derive(Component, Default)]
+struct PinnedEntity(Option<Entity>);
+
 fn setup_pane(pane: In<PaneStructure>, mut commands: Commands) {
     commands.entity(pane.content).insert((
-        PropertiesPaneRoot,
         Node {
             flex_direction: FlexDirection::Column,
             flex_grow: 1.0,
-            column_gap: Val::Px(4.0),
+            row_gap: Val::Px(4.0),
             padding: UiRect::all(Val::Px(8.0)),
             ..Default::default()
         },
         BackgroundColor(tailwind::NEUTRAL_600.into()),
     ));
+
+    let pane_root = commands
+        .spawn((
+            PropertiesPaneRoot,
+            AddComponentFilter::default(),
+            PinnedEntity::default(),
+            Node {
+                flex_direction: FlexDirection::Column,
+                column_gap: Val::Px(4.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    let search_box = commands
+        .spawn((
+            EditableTextLine::new(""),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(20.0),
+                padding: UiRect::horizontal(Val::Px(4.0)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(4.0)),
+            BackgroundColor(tailwind::NEUTRAL_700.into()),
+            TextFont::from_font_size(11.0),
+        ))
+        .observe(
+            move |trigger: On<TextChanged>, mut filters: Query<&mut AddComponentFilter>| {
+                if let Ok(mut filter) = filters.get_mut(pane_root) {
+                    filter.0 = trigger.event().new_text.clone();
+                }
+            },
+        )
+        .id();
+
+    let query_results = commands
+        .spawn((
+            EntityQueryText::default(),
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    let query_box = commands
+        .spawn((
+            EditableTextLine::new(""),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(20.0),
+                padding: UiRect::horizontal(Val::Px(4.0)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(4.0)),
+            BackgroundColor(tailwind::NEUTRAL_700.into()),
+            TextFont::from_font_size(11.0),
+        ))
+        .observe(
+            move |trigger: On<TextChanged>, mut queries: Query<&mut EntityQueryText>| {
+                if let Ok(mut query) = queries.get_mut(query_results) {
+                    query.0 = trigger.event().new_text.clone();
+                }
+            },
+        )
+        .id();
+
+    commands
+        .entity(pane.content)
+        .add_children(&[pane_root, search_box, query_box, query_results]);
 }
 
 fn update_properties_pane(
-    panes: Query<Entity, With<PropertiesPaneRoot>>,
+    panes: Query<(Entity, &AddComponentFilter, &PinnedEntity), With<PropertiesPaneRoot>>,
     selected_entity: Res<SelectedEntity>,
     world: &World,
     mut commands: Commands,
 ) {
-    for pane in &panes {
+    for (pane, filter, pinned) in &panes {
+        let contents = match pinned.0 {
+            Some(pinned) => pinned_entity(pane, pinned, &filter.0, world),
+            None => properties_pane(pane, &selected_entity, &filter.0, world),
+        };
+        commands.entity(pane).build_children(contents);
+    }
+}
+
+/// Lists the entities matching the query typed in the entity query box, see [`EntityQuery`].
+fn update_entity_query_results(
+    results: Query<(Entity, &EntityQueryText)>,
+    world: &World,
+    mut commands: Commands,
+) {
+    for (results, query) in &results {
         commands
-            .entity(pane)
-            .build_children(properties_pane(&selected_entity, world));
+            .entity(results)
+            .build_children(entity_query_results(&query.0, world));
     }
 }
 
-fn properties_pane(selected_entity: &SelectedEntity, world: &World) -> Template {
-    match selected_entity.0 {
-        Some(selected_entity) => component_list(selected_entity, world),
+/// Shows the entity `pane` is pinned to, with a button to unpin it, whatever is selected.
+fn pinned_entity(pane: Entity, entity: Entity, filter: &str, world: &World) -> Template {
+    let unpin_on_click = move |mut trigger: On<Pointer<Click>>,
+                               mut pins: Query<&mut PinnedEntity>| {
+        trigger.propagate(false);
+        if let Ok(mut pin) = pins.get_mut(pane) {
+            pin.0 = None;
+        }
+    };
+    let name = world
+        .get::<Name>(entity)
+        .map_or_else(|| entity.to_string(), |name| name.to_string());
+
+    let header = template! {
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::SpaceBetween,
+            ..Default::default()
+        } => [
+            (
+                Text(format!("Pinned: {name}")),
+                TextFont::from_font_size(12.0),
+                TextColor(tailwind::AMBER_300.into()),
+            );
+            (
+                Text("Unpin".into()),
+                TextFont::from_font_size(12.0),
+                TextColor(Color::WHITE),
+            ) => [
+                on(unpin_on_click);
+            ];
+        ];
+    };
+
+    let contents = if world.get_entity(entity).is_err() {
+        template! {
+            (
+                Text("Entity no longer exists".into()),
+                TextFont::from_font_size(14.0),
+                TextColor(tailwind::NEUTRAL_300.into()),
+            );
+        }
+    } else if is_locked_in_world(world, entity) {
+        component_list(entity, false, world)
+    } else {
+        component_list(entity, true, world)
+            .into_iter()
+            .chain(add_component_list(entity, filter, world))
+            .collect()
+    };
+
+    header.into_iter().chain(contents).collect()
+}
+
+/// A button pinning `pane` to `entity`, so it keeps showing it when the selection changes.
+fn pin_button(pane: Entity, entity: Entity) -> Template {
+    let pin_on_click = move |mut trigger: On<Pointer<Click>>,
+                             mut pins: Query<&mut PinnedEntity>| {
+        trigger.propagate(false);
+        if let Ok(mut pin) = pins.get_mut(pane) {
+            pin.0 = Some(entity);
+        }
+    };
+
+    template! {
+        (
+            Text("Pin".into()),
+            TextFont::from_font_size(12.0),
+            TextColor(Color::WHITE),
+        ) => [
+            on(pin_on_click);
+        ];
+    }
+}
+
+fn properties_pane(
+    pane: Entity,
+    selected_entity: &SelectedEntity,
+    filter: &str,
+    world: &World,
+) -> Template {
+    let selection = world
+        .get_resource::<SelectedEntities>()
+        .map(|selected_entities| selected_entities.0.as_slice())
+        .unwrap_or_default();
+
+    // The selection is reset after this runs when the selected entity is despawned, show nothing
+    // selected in the meantime
+    match selected_entity
+        .0
+        .filter(|&entity| world.get_entity(entity).is_ok())
+    {
+        Some(selected_entity) if selection.len() > 1 => {
+            multi_component_list(selected_entity, selection, world)
+        }
+        // Locked entities are shown read-only
+        Some(selected_entity) if is_locked_in_world(world, selected_entity) => template! {
+            (
+                Text("Locked".into()),
+                TextFont::from_font_size(12.0),
+                TextColor(tailwind::NEUTRAL_300.into()),
+            );
+        }
+        .into_iter()
+        .chain(pin_button(pane, selected_entity))
+        .chain(component_list(selected_entity, false, world))
+        .collect(),
+        Some(selected_entity) => pin_button(pane, selected_entity)
+            .into_iter()
+            .chain(component_list(selected_entity, true, world))
+            .chain(add_component_list(selected_entity, filter, world))
+            .collect(),
         None => template! {
             Node {
                 flex_direction: FlexDirection::Column,
@@ -66,11 +311,19 @@ fn properties_pane(selected_entity: &SelectedEntity, world: &World) -> Template
     }
 }
 
-fn component_list(entity: Entity, world: &World) -> Template {
+/// Lists the components of `entity`, with buttons to remove them when `editable` is set.
+/// Lists nothing for a despawned entity.
+fn component_list(entity: Entity, editable: bool, world: &World) -> Template {
+    let (Ok(entity_ref), Ok(components)) = (world.get_entity(entity), world.inspect_entity(entity))
+    else {
+        return template! {};
+    };
     let type_registry = world.resource::<AppTypeRegistry>().read();
-    world
-        .inspect_entity(entity)
-        .unwrap()
+    let icon_font = world
+        .get_resource::<Theme>()
+        .map(|theme| theme.icon.font.clone())
+        .unwrap_or_default();
+    components
         .flat_map(|component_info| {
             let type_info = component_info
                 .type_id()
@@ -79,13 +332,33 @@ fn component_list(entity: Entity, world: &World) -> Template {
                 || "<unknown>".to_string(),
                 |type_info| type_info.type_path_table().short_path().to_string(),
             );
+            let can_remove = editable
+                && component_info.type_id().is_some_and(|type_id| {
+                    type_registry
+                        .get_type_data::<ReflectComponent>(type_id)
+                        .is_some()
+                });
+            let remove_on_click = {
+                let type_id = component_info.type_id();
+                move |mut trigger: On<Pointer<Click>>, mut commands: Commands| {
+                    trigger.propagate(false);
+                    if let Some(type_id) = type_id {
+                        commands.run_system_cached_with(remove_component, (entity, type_id));
+                    }
+                }
+            };
+
+            // Numeric fields can be dragged unless the entity is shown read-only
+            let drag_target = component_info
+                .type_id()
+                .filter(|_| editable)
+                .map(|type_id| (std::slice::from_ref(&entity), type_id));
 
             // Get the reflected component value from the world
             let reflect: Option<&dyn Reflect> = component_info.type_id().and_then(|type_id| {
                 let registration = type_registry.get(type_id)?;
                 let reflect_component = registration.data::<ReflectComponent>()?;
-                let entity_ref = world.get_entity(entity);
-                reflect_component.reflect(entity_ref.unwrap())
+                reflect_component.reflect(entity_ref)
             });
 
             template! {
@@ -99,6 +372,7 @@ fn component_list(entity: Entity, world: &World) -> Template {
                     Node {
                         flex_direction: FlexDirection::Row,
                         align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
                         ..Default::default()
                     } => [
                         (
@@ -106,10 +380,28 @@ fn component_list(entity: Entity, world: &World) -> Template {
                             TextFont::from_font_size(14.0),
                             TextColor(Color::WHITE),
                         );
+                        @{ if can_remove {
+                            template! {
+                                (
+                                    Text(icons::X.into()),
+                                    TextFont {
+                                        font: icon_font.clone(),
+                                        font_size: 14.0,
+                                        ..Default::default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ) => [
+                                    on(remove_on_click);
+                                ];
+                            }
+                        } else {
+                            template! {}
+                        } };
                     ];
+                    @{ asset_preview(entity, component_info.type_id(), world) };
                     // Component fields
                     @{ match reflect {
-                        Some(reflect) => component(type_info, reflect),
+                        Some(reflect) => component(type_info, reflect, drag_target),
                         None => template! {
                             Node {
                                 flex_direction: FlexDirection::Row,
@@ -129,25 +421,410 @@ fn component_list(entity: Entity, world: &World) -> Template {
         .collect()
 }
 
-fn component(type_info: Option<&TypeInfo>, reflect: &dyn Reflect) -> Template {
+/// Lists the components of all `entities`, showing the values of the `primary` entity.
+/// Dragging a field of a component they all have changes it on every entity that isn't locked.
+/// Components that only some of the entities have are greyed out and can be added to the others.
+fn multi_component_list(primary: Entity, entities: &[Entity], world: &World) -> Template {
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let summary = template! {
+        (
+            Text(format!("{} entities selected", entities.len())),
+            TextFont::from_font_size(12.0),
+            TextColor(tailwind::NEUTRAL_300.into()),
+        );
+    };
+
+    let components = component_presence(entities, world)
+        .into_iter()
+        .flat_map(|(component_id, count)| {
+            let type_id = world
+                .components()
+                .get_info(component_id)
+                .and_then(|info| info.type_id());
+            let type_info = type_id.and_then(|type_id| type_registry.get_type_info(type_id));
+            let name = type_info.map_or_else(
+                || "<unknown>".to_string(),
+                |type_info| type_info.type_path_table().short_path().to_string(),
+            );
+
+            // Show the values of the primary entity, or of the first entity with the component
+            let reflect: Option<&dyn Reflect> = type_id.and_then(|type_id| {
+                let reflect_component = type_registry.get_type_data::<ReflectComponent>(type_id)?;
+                std::iter::once(primary)
+                    .chain(entities.iter().copied())
+                    .filter_map(|entity| world.get_entity(entity).ok())
+                    .find_map(|entity_ref| reflect_component.reflect(entity_ref))
+            });
+
+            if count == entities.len() {
+                let drag_target = type_id.map(|type_id| (entities, type_id));
+                return template! {
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        margin: UiRect::all(Val::Px(4.0)),
+                        ..Default::default()
+                    } => [
+                        (
+                            Text(format!("⯆ {name}")),
+                            TextFont::from_font_size(14.0),
+                            TextColor(Color::WHITE),
+                        );
+                        @{ reflect.map(|reflect| component(type_info, reflect, drag_target)).unwrap_or_default() };
+                    ];
+                };
+            }
+
+            let add_to_all_on_click = move |mut trigger: On<Pointer<Click>>,
+                                            mut commands: Commands| {
+                trigger.propagate(false);
+                if let Some(type_id) = type_id {
+                    commands.run_system_cached_with(add_component_to_selection, type_id);
+                }
+            };
+            let can_copy = reflect.is_some();
+
+            template! {
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    margin: UiRect::all(Val::Px(4.0)),
+                    ..Default::default()
+                } => [
+                    (
+                        Text(format!("{name} (partial, {count} of {})", entities.len())),
+                        TextFont::from_font_size(14.0),
+                        TextColor(tailwind::NEUTRAL_400.into()),
+                    );
+                    @{ if can_copy {
+                        template! {
+                            (
+                                Text("Add to all".into()),
+                                TextFont::from_font_size(12.0),
+                                TextColor(Color::WHITE),
+                            ) => [
+                                on(add_to_all_on_click);
+                            ];
+                        }
+                    } else {
+                        template! {}
+                    } };
+                ];
+            }
+        })
+        .collect::<Template>();
+
+    summary.into_iter().chain(components).collect()
+}
+
+/// Each component found on `entities`, in the order they are first found, along with the number
+/// of entities that have it.
+fn component_presence(entities: &[Entity], world: &World) -> Vec<(ComponentId, usize)> {
+    let mut presence: Vec<(ComponentId, usize)> = Vec::new();
+    for &entity in entities {
+        let Ok(components) = world.inspect_entity(entity) else {
+            continue;
+        };
+        for info in components {
+            match presence.iter_mut().find(|(id, _)| *id == info.id()) {
+                Some((_, count)) => *count += 1,
+                None => presence.push((info.id(), 1)),
+            }
+        }
+    }
+    presence
+}
+
+/// Copies the component of the registered type `type_id` from the primary selected entity, or
+/// the first selected entity that has it, to the selected entities that don't have it yet.
+/// Locked entities are left alone. The changes are recorded for undo.
+fn add_component_to_selection(In(type_id): In<TypeId>, world: &mut World) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(type_id) else {
+        return;
+    };
+
+    let selection: Vec<Entity> = world
+        .resource::<SelectedEntity>()
+        .0
+        .into_iter()
+        .chain(world.resource::<SelectedEntities>().0.iter().copied())
+        .collect();
+    let Some(value) = selection
+        .iter()
+        .filter_map(|entity| world.get_entity(*entity).ok())
+        .find_map(|entity_ref| reflect_component.reflect(entity_ref))
+        .map(|value| value.to_dynamic())
+    else {
+        return;
+    };
+
+    for entity in selection {
+        if is_locked_in_world(world, entity) {
+            continue;
+        }
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        if entity_mut.contains_type_id(type_id) {
+            continue;
+        }
+        reflect_component.insert(&mut entity_mut, value.as_ref(), &type_registry);
+
+        world.send_event(NewChange::new(DynamicAddedComponent {
+            entity,
+            type_id,
+            new_value: value.to_dynamic(),
+        }));
+    }
+}
+
+/// The component types matching `filter` that can be added to `entity`.
+/// Types that can't be constructed without a value are shown greyed out.
+fn add_component_list(entity: Entity, filter: &str, world: &World) -> Template {
+    let filter = filter.trim().to_lowercase();
+    if filter.is_empty() {
+        return template! {};
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return template! {};
+    };
+
+    let mut candidates: Vec<(String, TypeId, bool)> = type_registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectComponent>().is_some())
+        .filter(|registration| !entity_ref.contains_type_id(registration.type_id()))
+        .map(|registration| {
+            (
+                registration
+                    .type_info()
+                    .type_path_table()
+                    .short_path()
+                    .to_string(),
+                registration.type_id(),
+                can_construct(registration),
+            )
+        })
+        .filter(|(name, ..)| name.to_lowercase().contains(&filter))
+        .collect();
+    candidates.sort();
+
+    candidates
+        .into_iter()
+        .take(MAX_COMPONENT_SUGGESTIONS)
+        .flat_map(|(name, type_id, can_add)| {
+            let add_on_click = move |mut trigger: On<Pointer<Click>>, mut commands: Commands| {
+                trigger.propagate(false);
+                commands.run_system_cached_with(add_component, (entity, type_id));
+            };
+
+            if can_add {
+                template! {
+                    Node {
+                        padding: UiRect::all(Val::Px(2.0)),
+                        ..Default::default()
+                    } => [
+                        on(add_on_click);
+                        (
+                            Text(format!("+ {name}")),
+                            TextFont::from_font_size(12.0),
+                            TextColor(Color::WHITE),
+                            Pickable::IGNORE,
+                        );
+                    ];
+                }
+            } else {
+                template! {
+                    Node {
+                        padding: UiRect::all(Val::Px(2.0)),
+                        ..Default::default()
+                    } => [
+                        (
+                            Text(format!("+ {name}")),
+                            TextFont::from_font_size(12.0),
+                            TextColor(tailwind::NEUTRAL_400.into()),
+                        );
+                    ];
+                }
+            }
+        })
+        .collect()
+}
+
+/// Whether a default value can be created for the registered type, see [`add_component`].
+fn can_construct(registration: &TypeRegistration) -> bool {
+    registration.data::<ReflectDefault>().is_some()
+        || registration.data::<ReflectFromWorld>().is_some()
+}
+
+/// Inserts a default-constructed component of the registered type `type_id` on `entity`,
+/// recording the change for undo.
+fn add_component(In((entity, type_id)): In<(Entity, TypeId)>, world: &mut World) {
+    if is_locked_in_world(world, entity) {
+        return;
+    }
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let Some(registration) = type_registry.get(type_id) else {
+        return;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        return;
+    };
+
+    let value = if let Some(reflect_default) = registration.data::<ReflectDefault>() {
+        reflect_default.default()
+    } else if let Some(reflect_from_world) = registration.data::<ReflectFromWorld>() {
+        reflect_from_world.from_world(world)
+    } else {
+        warn!(
+            "Can't add {}, it has no default value",
+            registration.type_info().type_path()
+        );
+        return;
+    };
+
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if entity_mut.contains_type_id(type_id) {
+        return;
+    }
+    reflect_component.insert(&mut entity_mut, value.as_partial_reflect(), &type_registry);
+
+    world.send_event(NewChange::new(DynamicAddedComponent {
+        entity,
+        type_id,
+        new_value: value.into_partial_reflect(),
+    }));
+}
+
+/// Removes the component of the registered type `type_id` from `entity`, recording the change
+/// for undo.
+fn remove_component(In((entity, type_id)): In<(Entity, TypeId)>, world: &mut World) {
+    if is_locked_in_world(world, entity) {
+        return;
+    }
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let Some(reflect_component) = type_registry.get_type_data::<ReflectComponent>(type_id) else {
+        return;
+    };
+
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    let Some(old_value) = reflect_component
+        .reflect(&entity_mut)
+        .map(|value| value.to_dynamic())
+    else {
+        return;
+    };
+    reflect_component.remove(&mut entity_mut);
+
+    world.send_event(NewChange::new(DynamicRemovedComponent {
+        entity,
+        type_id,
+        old_value,
+    }));
+}
+
+/// Requests previews of the mesh and material of the selected and pinned entities, shown with
+/// their components.
+fn request_asset_previews(
+    selected_entity: Res<SelectedEntity>,
+    pinned: Query<&PinnedEntity>,
+    assets: Query<(Option<&Mesh3d>, Option<&MeshMaterial3d<StandardMaterial>>)>,
+    mut previews: ResMut<AssetPreviews>,
+) {
+    let shown = selected_entity
+        .0
+        .into_iter()
+        .chain(pinned.iter().filter_map(|pinned| pinned.0));
+    for (mesh, material) in shown.filter_map(|entity| assets.get(entity).ok()) {
+        if let Some(mesh) = mesh {
+            previews.request(PreviewSource::Mesh(mesh.0.clone()));
+        }
+        if let Some(material) = material {
+            previews.request(PreviewSource::Material(material.0.clone()));
+        }
+    }
+}
+
+/// The asset a preview is shown for with the component of `type_id` on `entity`, if any.
+fn preview_source(entity: Entity, type_id: TypeId, world: &World) -> Option<PreviewSource> {
+    if type_id == TypeId::of::<Mesh3d>() {
+        let mesh = world.get::<Mesh3d>(entity)?;
+        Some(PreviewSource::Mesh(mesh.0.clone()))
+    } else if type_id == TypeId::of::<MeshMaterial3d<StandardMaterial>>() {
+        let material = world.get::<MeshMaterial3d<StandardMaterial>>(entity)?;
+        Some(PreviewSource::Material(material.0.clone()))
+    } else {
+        None
+    }
+}
+
+/// A thumbnail of the mesh or material asset of a component, once its preview was requested.
+fn asset_preview(entity: Entity, type_id: Option<TypeId>, world: &World) -> Template {
+    let image = type_id
+        .and_then(|type_id| preview_source(entity, type_id, world))
+        .and_then(|source| world.get_resource::<AssetPreviews>()?.get(&source));
+    let Some(image) = image else {
+        return template! {};
+    };
+
+    template! {
+        (
+            ImageNode::new(image),
+            Node {
+                width: Val::Px(PREVIEW_SIZE as f32),
+                height: Val::Px(PREVIEW_SIZE as f32),
+                margin: UiRect::vertical(Val::Px(2.0)),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// The fields of a component, numeric fields can be dragged when `drag_target` is the entities and
+/// type of the component.
+fn component(
+    type_info: Option<&TypeInfo>,
+    reflect: &dyn Reflect,
+    drag_target: Option<(&[Entity], TypeId)>,
+) -> Template {
     match type_info {
-        Some(TypeInfo::Struct(struct_info)) => reflected_struct(struct_info, reflect),
+        Some(TypeInfo::Struct(struct_info)) => reflected_struct(struct_info, reflect, drag_target),
         Some(TypeInfo::TupleStruct(tuple_struct_info)) => reflected_tuple_struct(tuple_struct_info),
         Some(TypeInfo::Enum(enum_info)) => reflected_enum(enum_info),
         _ => template! {},
     }
 }
-fn reflected_struct(struct_info: &StructInfo, reflect: &dyn Reflect) -> Template {
+fn reflected_struct(
+    struct_info: &StructInfo,
+    reflect: &dyn Reflect,
+    drag_target: Option<(&[Entity], TypeId)>,
+) -> Template {
     let fields = struct_info
         .iter()
         .enumerate()
         .flat_map(|(i, field)| {
-            let value = reflect
+            let field_value = reflect
                 .reflect_ref()
                 .as_struct()
-                .map(|s| s.field_at(i))
+                .ok()
+                .and_then(|s| s.field_at(i));
+            let value = field_value
                 .map(|v| format!("{v:?}"))
                 .unwrap_or("<unavailable>".to_string());
+            let drag_handles = drag_target
+                .zip(field_value)
+                .and_then(|((entities, type_id), v)| {
+                    numeric_field(entities, type_id, field.name(), v)
+                });
 
             template! {
                 Node {
@@ -160,12 +837,14 @@ fn reflected_struct(struct_info: &StructInfo, reflect: &dyn Reflect) -> Template
                         TextFont::from_font_size(12.0),
                         TextColor(Color::srgb(0.8, 0.8, 0.8)),
                     );
-                    (
-                        // Value (use reflection to get value as string)
-                        Text(value),
-                        TextFont::from_font_size(10.0),
-                        TextColor(Color::WHITE),
-                    );
+                    @{ drag_handles.unwrap_or_else(|| template! {
+                        (
+                            // Value (use reflection to get value as string)
+                            Text(value),
+                            TextFont::from_font_size(10.0),
+                            TextColor(Color::WHITE),
+                        );
+                    }) };
                 ];
             }
         })
@@ -216,3 +895,332 @@ fn reflected_enum(enum_info: &EnumInfo) -> Template {
         } => [ @{ variants }; ];
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_editor_core::Locked;
+    use bevy_transform_gizmos::{GizmoSnapSettings, GridSnapConfig};
+    use bevy_undo::{ChangeChain, UndoRedo};
+
+    use super::{
+        drag_value::{begin_drag, drag_value, end_drag, DragTarget},
+        *,
+    };
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, Default)]
+    struct Health(f32);
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Target(u32);
+
+    fn undo(app: &mut App) {
+        app.update();
+        app.update();
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+    }
+
+    #[test]
+    fn multi_selection_shows_partial_components_and_adds_them_to_all() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .init_resource::<SelectedEntity>()
+            .init_resource::<SelectedEntities>()
+            .register_type::<Health>()
+            .register_type::<Target>();
+
+        let world = app.world_mut();
+        let a = world.spawn((Health(1.), Target(7))).id();
+        let b = world.spawn(Health(2.)).id();
+        let locked = world.spawn((Health(3.), Locked::default())).id();
+        world.resource_mut::<SelectedEntity>().0 = Some(a);
+        world.resource_mut::<SelectedEntities>().0 = vec![a, b, locked];
+
+        let health = world.component_id::<Health>().unwrap();
+        let target = world.component_id::<Target>().unwrap();
+        let presence = component_presence(&[a, b], world);
+        assert!(presence.contains(&(health, 2)));
+        assert!(presence.contains(&(target, 1)));
+
+        world
+            .run_system_cached_with(add_component_to_selection, TypeId::of::<Target>())
+            .unwrap();
+        assert_eq!(world.get::<Target>(b).map(|target| target.0), Some(7));
+        assert_eq!(world.get::<Target>(a).map(|target| target.0), Some(7));
+        // Components the entities already have are left alone, and so are locked entities
+        assert_eq!(world.get::<Health>(b), Some(&Health(2.)));
+        assert!(world.get::<Target>(locked).is_none());
+
+        undo(&mut app);
+        assert!(app.world().get::<Target>(b).is_none());
+        assert!(app.world().get::<Target>(a).is_some());
+    }
+
+    #[test]
+    fn add_and_remove_component_with_undo() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .register_type::<Health>()
+            .register_type::<Target>();
+
+        let entity = app.world_mut().spawn_empty().id();
+        let health = TypeId::of::<Health>();
+        let target = TypeId::of::<Target>();
+
+        let world = app.world_mut();
+        world
+            .run_system_cached_with(add_component, (entity, health))
+            .unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health(0.)));
+
+        // Components without a default value can't be added
+        world
+            .run_system_cached_with(add_component, (entity, target))
+            .unwrap();
+        assert!(world.get::<Target>(entity).is_none());
+
+        undo(&mut app);
+        assert!(app.world().get::<Health>(entity).is_none());
+
+        let world = app.world_mut();
+        world.entity_mut(entity).insert(Health(5.));
+        app.update();
+
+        let world = app.world_mut();
+        world
+            .run_system_cached_with(remove_component, (entity, health))
+            .unwrap();
+        assert!(world.get::<Health>(entity).is_none());
+
+        undo(&mut app);
+        assert_eq!(app.world().get::<Health>(entity), Some(&Health(5.)));
+    }
+
+    #[test]
+    fn dragging_translation_records_one_undo_step() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .init_resource::<DragGesture>()
+            .register_type::<Transform>();
+
+        let entity = app.world_mut().spawn(Transform::from_xyz(1., 2., 3.)).id();
+        let target = DragTarget {
+            entities: vec![entity],
+            type_id: TypeId::of::<Transform>(),
+            path: "translation.x".into(),
+            euler_axis: None,
+        };
+        let settings = DragValueSettings::default();
+        assert!((settings.delta(10., false, true) - 0.01).abs() < 1e-6);
+
+        app.world_mut()
+            .run_system_cached_with(begin_drag, target.clone())
+            .unwrap();
+        // The gesture spans several frames, which would record separate steps if each drag did
+        for pixels in [10., 20., 20.] {
+            app.world_mut()
+                .run_system_cached_with(
+                    drag_value,
+                    (target.clone(), settings.delta(pixels, false, false)),
+                )
+                .unwrap();
+            app.update();
+            app.update();
+        }
+        app.world_mut().run_system_cached(end_drag).unwrap();
+        app.update();
+        app.update();
+
+        let world = app.world();
+        let translation = world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(1.5, 2., 3.), 1e-5));
+        assert_eq!(world.resource::<ChangeChain>().changes.len(), 1);
+
+        undo(&mut app);
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(1., 2., 3.), 1e-5));
+    }
+
+    #[test]
+    fn dragging_shared_component_changes_all_selected_entities() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .init_resource::<DragGesture>()
+            .register_type::<Health>();
+
+        let world = app.world_mut();
+        let a = world.spawn(Health(1.)).id();
+        let b = world.spawn(Health(4.)).id();
+        let locked = world.spawn((Health(7.), Locked::default())).id();
+        let target = DragTarget {
+            entities: vec![a, b, locked],
+            type_id: TypeId::of::<Health>(),
+            path: ".0".into(),
+            euler_axis: None,
+        };
+
+        world
+            .run_system_cached_with(begin_drag, target.clone())
+            .unwrap();
+        world
+            .run_system_cached_with(drag_value, (target, 0.5))
+            .unwrap();
+        world.run_system_cached(end_drag).unwrap();
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.get::<Health>(a), Some(&Health(1.5)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(4.5)));
+        assert_eq!(world.get::<Health>(locked), Some(&Health(7.)));
+        // Both entities are changed in a single undo step
+        assert_eq!(world.resource::<ChangeChain>().changes.len(), 1);
+
+        undo(&mut app);
+        let world = app.world();
+        assert_eq!(world.get::<Health>(a), Some(&Health(1.)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(4.)));
+    }
+
+    #[test]
+    fn dragged_translation_snaps_to_grid_increment() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin, TransformGizmoPlugin))
+            .init_resource::<DragGesture>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(GizmoSnapSettings {
+                enabled: true,
+                ..Default::default()
+            })
+            .insert_resource(GridSnapConfig { increment: 2. })
+            .register_type::<Transform>();
+
+        let world = app.world_mut();
+        let entity = world.spawn(Transform::from_xyz(0., 4., 0.)).id();
+        let target = DragTarget {
+            entities: vec![entity],
+            type_id: TypeId::of::<Transform>(),
+            path: "translation.x".into(),
+            euler_axis: None,
+        };
+
+        world
+            .run_system_cached_with(begin_drag, target.clone())
+            .unwrap();
+        world
+            .run_system_cached_with(drag_value, (target, 1.3))
+            .unwrap();
+        world.run_system_cached(end_drag).unwrap();
+
+        let translation = world.get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(2., 4., 0.), 1e-5));
+    }
+
+    #[test]
+    fn dragged_rotation_snaps_while_snap_modifier_is_held() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin, TransformGizmoPlugin))
+            .init_resource::<DragGesture>()
+            .register_type::<Transform>();
+
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        keyboard.press(KeyCode::ShiftLeft);
+        let world = app.world_mut();
+        world.insert_resource(keyboard);
+        let entity = world.spawn(Transform::default()).id();
+        let target = DragTarget {
+            entities: vec![entity],
+            type_id: TypeId::of::<Transform>(),
+            path: "rotation".into(),
+            euler_axis: Some(1),
+        };
+
+        world
+            .run_system_cached_with(begin_drag, target.clone())
+            .unwrap();
+        world
+            .run_system_cached_with(drag_value, (target, 20.))
+            .unwrap();
+        world.run_system_cached(end_drag).unwrap();
+        app.update();
+        app.update();
+
+        // 20 degrees snap to the default increment of 15 degrees, and the snapped value is undone
+        let world = app.world();
+        let rotation = world.get::<Transform>(entity).unwrap().rotation;
+        assert!(rotation.angle_between(Quat::from_rotation_y(15f32.to_radians())) < 1e-4);
+        assert_eq!(world.resource::<ChangeChain>().changes.len(), 1);
+
+        undo(&mut app);
+        let rotation = app.world().get::<Transform>(entity).unwrap().rotation;
+        assert!(rotation.angle_between(Quat::IDENTITY) < 1e-4);
+    }
+
+    /// The texts shown under `entity`, depth first.
+    fn rendered_texts(world: &World, entity: Entity) -> Vec<String> {
+        let children = world
+            .get::<Children>(entity)
+            .into_iter()
+            .flat_map(|children| children.iter());
+        world
+            .get::<Text>(entity)
+            .map(|text| text.0.clone())
+            .into_iter()
+            .chain(children.flat_map(|child| rendered_texts(world, child)))
+            .collect()
+    }
+
+    #[test]
+    fn pinned_pane_keeps_showing_entity_when_selection_changes() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<SelectedEntity>()
+            .register_type::<Health>()
+            .register_type::<Target>()
+            .add_systems(Update, update_properties_pane);
+
+        let world = app.world_mut();
+        let pinned = world.spawn((Name::new("Player"), Health(3.))).id();
+        let other = world.spawn(Target(1)).id();
+        let pane = world
+            .spawn((
+                PropertiesPaneRoot,
+                AddComponentFilter::default(),
+                PinnedEntity::default(),
+            ))
+            .id();
+        world.resource_mut::<SelectedEntity>().0 = Some(pinned);
+        app.update();
+        assert!(rendered_texts(app.world(), pane).contains(&"Pin".to_string()));
+
+        app.world_mut().get_mut::<PinnedEntity>(pane).unwrap().0 = Some(pinned);
+        app.world_mut().resource_mut::<SelectedEntity>().0 = Some(other);
+        app.update();
+
+        let texts = rendered_texts(app.world(), pane);
+        assert!(texts.contains(&"Pinned: Player".to_string()));
+        assert!(texts.contains(&"Unpin".to_string()));
+        assert!(texts.contains(&"⯆ Health".to_string()));
+        assert!(!texts.contains(&"⯆ Target".to_string()));
+
+        app.world_mut().despawn(pinned);
+        app.update();
+        let texts = rendered_texts(app.world(), pane);
+        assert!(texts.contains(&"Entity no longer exists".to_string()));
+        assert!(!texts.contains(&"⯆ Health".to_string()));
+
+        // Unpinning follows the selection again
+        app.world_mut().get_mut::<PinnedEntity>(pane).unwrap().0 = None;
+        app.update();
+        assert!(rendered_texts(app.world(), pane).contains(&"⯆ Target".to_string()));
+
+        // A despawned selection is shown as nothing selected until the selection is reset
+        app.world_mut().despawn(other);
+        app.update();
+        let texts = rendered_texts(app.world(), pane);
+        assert!(texts.contains(&"Select an entity to inspect".to_string()));
+    }
+}
@@ -1,16 +1,46 @@
 //! An interactive, collapsible tree view for hierarchical ECS data in Bevy.
 
-use bevy::{app::Plugin, color::palettes::tailwind, prelude::*};
-use bevy_editor_core::SelectedEntity;
+use std::time::Duration;
+
+use bevy::{
+    app::Plugin,
+    color::palettes::tailwind,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus},
+    platform::collections::HashSet,
+    prelude::*,
+};
+use bevy_command_palette::run_command;
+use bevy_context_menu::{ContextMenu, ContextMenuOption};
+use bevy_editor_core::{is_locked_in_world, Locked, SelectedEntities, SelectedEntity};
+use bevy_editor_styles::{icons, Theme};
 use bevy_i_cant_believe_its_not_bsn::{on, template, Template, TemplateEntityCommandsExt};
-use bevy_pane_layout::prelude::{PaneAppExt, PaneStructure};
+use bevy_pane_layout::prelude::{PaneAppExt, PaneStructure, PaneTab};
+use bevy_text_editing::{EditableTextLine, EditableTextLinePlugin, HasFocus, TextChanged};
+use bevy_undo::{
+    AddedEntity, ComponentChange, NewChange, RemovedEntityTree, RenamedEntity, ReparentedEntity,
+    UndoPlugin,
+};
+
+/// The maximum time between two clicks on a row for them to count as a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
 
 /// Plugin for the editor scene tree pane.
 pub struct SceneTreePlugin;
 
 impl Plugin for SceneTreePlugin {
     fn build(&self, app: &mut App) {
-        app.register_pane("Scene Tree", setup_pane)
+        if !app.is_plugin_added::<EditableTextLinePlugin>() {
+            app.add_plugins(EditableTextLinePlugin);
+        }
+        if !app.is_plugin_added::<UndoPlugin>() {
+            app.add_plugins(UndoPlugin);
+        }
+
+        app.init_resource::<LastRowClick>()
+            .init_resource::<SelectionAnchor>()
+            .register_pane_with_tab("Scene Tree", PaneTab::new(icons::LIST_TREE), setup_pane)
+            .add_systems(Update, cancel_rename_on_focus_loss)
             .add_systems(PostUpdate, update_scene_tree);
     }
 }
@@ -19,15 +49,53 @@ impl Plugin for SceneTreePlugin {
 #[derive(Component)]
 struct SceneTreeRoot;
 
+/// A row of the scene tree, showing the contained entity.
+#[derive(Component)]
+struct SceneTreeRow(Entity);
+
+/// The strip along the start of a [`SceneTreeRow`], dragged over rows to select them.
+#[derive(Component)]
+struct SceneTreeGutter;
+
+/// The label showing the name of the entity in a [`SceneTreeRow`].
+#[derive(Component)]
+struct SceneTreeRowLabel;
+
+/// The current contents of the search box of a scene tree.
+#[derive(Component, Default)]
+struct SceneTreeFilter(String);
+
+/// A text field replacing the label of a row while the contained entity is being renamed.
+#[derive(Component)]
+struct RenameField(Entity);
+
+/// The last clicked scene tree row and when it was clicked, used to detect double clicks.
+#[derive(Resource, Default)]
+struct LastRowClick(Option<(Entity, Duration)>);
+
+/// The entity a Shift-click range selection starts from.
+#[derive(Resource, Default)]
+struct SelectionAnchor(Option<Entity>);
+
+/// How clicking a row changes the selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SelectMode {
+    /// Select only the clicked entity, or nothing if it was the only selected entity.
+    Single,
+    /// Add the clicked entity to the selection, or remove it if it was already selected.
+    Toggle,
+    /// Select all rows between the anchor and the clicked row.
+    Range,
+}
+
 fn setup_pane(pane: In<PaneStructure>, mut commands: Commands) {
     commands
         .entity(pane.content)
         .insert((
-            SceneTreeRoot,
             Node {
                 flex_direction: FlexDirection::Column,
                 flex_grow: 1.0,
-                column_gap: Val::Px(2.0),
+                row_gap: Val::Px(4.0),
                 padding: UiRect::all(Val::Px(8.0)),
                 ..Default::default()
             },
@@ -38,56 +106,1090 @@ fn setup_pane(pane: In<PaneStructure>, mut commands: Commands) {
                 selected_entity.0 = None;
                 trigger.propagate(false);
             },
+        )
+        .observe(
+            |mut trigger: On<Pointer<DragDrop>>,
+             rows: Query<&SceneTreeRow>,
+             mut commands: Commands| {
+                trigger.propagate(false);
+                // Dropping a row on empty space moves the entity to the root
+                if let Ok(row) = rows.get(trigger.event().dropped) {
+                    commands.run_system_cached_with(reparent_entity, (row.0, None));
+                }
+            },
         );
+
+    let tree_root = commands
+        .spawn((
+            SceneTreeRoot,
+            SceneTreeFilter::default(),
+            Node {
+                flex_direction: FlexDirection::Column,
+                column_gap: Val::Px(2.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    let search_box = commands
+        .spawn((
+            EditableTextLine::new(""),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(20.0),
+                padding: UiRect::horizontal(Val::Px(4.0)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(4.0)),
+            BackgroundColor(tailwind::NEUTRAL_700.into()),
+            TextFont::from_font_size(11.0),
+        ))
+        .observe(
+            move |trigger: On<TextChanged>, mut filters: Query<&mut SceneTreeFilter>| {
+                if let Ok(mut filter) = filters.get_mut(tree_root) {
+                    filter.0 = trigger.event().new_text.clone();
+                }
+            },
+        )
+        .observe(|mut trigger: On<Pointer<Click>>| {
+            // Don't clear the selection when focusing the search box
+            trigger.propagate(false);
+        })
+        .id();
+
+    commands
+        .entity(pane.content)
+        .add_children(&[search_box, tree_root]);
 }
 
 fn update_scene_tree(
-    scene_trees: Query<Entity, With<SceneTreeRoot>>,
-    scene_entities: Query<(Entity, &Name)>,
-    selected_entity: Res<SelectedEntity>,
+    scene_trees: Query<(Entity, &SceneTreeFilter), With<SceneTreeRoot>>,
+    scene_entities: Query<(Entity, &Name, Option<&Locked>)>,
+    parent_query: Query<&ChildOf>,
+    visibility_query: Query<&Visibility>,
+    selected_entities: Res<SelectedEntities>,
+    theme: Res<Theme>,
+    rename_fields: Query<(), With<RenameField>>,
     mut commands: Commands,
 ) {
-    for scene_tree in &scene_trees {
+    // Rebuilding the rows would remove the rename field, so leave the tree as is while renaming
+    if !rename_fields.is_empty() {
+        return;
+    }
+
+    for (scene_tree, filter) in &scene_trees {
+        let visible = filter_entities(
+            &filter.0,
+            scene_entities
+                .iter()
+                .map(|(entity, name, _)| (entity, name)),
+            |entity| parent_query.get(entity).ok().map(ChildOf::parent),
+        );
+
         let tree_rows: Template = scene_entities
             .iter()
-            .flat_map(|(entity, name)| scene_tree_row_for_entity(entity, name, &selected_entity))
+            .filter(|(entity, ..)| {
+                visible
+                    .as_ref()
+                    .is_none_or(|visible| visible.contains(entity))
+            })
+            .flat_map(|(entity, name, locked)| {
+                let visibility = visibility_state(entity, &visibility_query, &parent_query);
+                scene_tree_row_for_entity(
+                    entity,
+                    name,
+                    locked,
+                    visibility,
+                    &selected_entities,
+                    &theme,
+                )
+            })
             .collect();
 
         commands.entity(scene_tree).build_children(tree_rows);
     }
 }
 
+/// Updates the selection after clicking `row`.
+/// Range selections follow the order the rows are displayed in, so hidden entities are skipped.
+fn select_row(
+    In((row, mode)): In<(Entity, SelectMode)>,
+    rows: Query<&SceneTreeRow>,
+    parent_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
+    mut anchor: ResMut<SelectionAnchor>,
+) {
+    let Ok(&SceneTreeRow(entity)) = rows.get(row) else {
+        return;
+    };
+
+    match mode {
+        SelectMode::Single => {
+            if selected_entities.0 == [entity] {
+                selected_entities.0.clear();
+            } else {
+                selected_entities.0 = vec![entity];
+            }
+            anchor.0 = Some(entity);
+        }
+        SelectMode::Toggle => {
+            if selected_entities.contains(entity) {
+                selected_entities.0.retain(|selected| *selected != entity);
+            } else {
+                selected_entities.0.push(entity);
+            }
+            anchor.0 = Some(entity);
+        }
+        SelectMode::Range => {
+            let displayed: Vec<Entity> = parent_query
+                .get(row)
+                .ok()
+                .and_then(|child_of| children_query.get(child_of.parent()).ok())
+                .into_iter()
+                .flat_map(|children| children.iter())
+                .filter_map(|row| rows.get(row).ok().map(|row| row.0))
+                .collect();
+
+            let start = anchor
+                .0
+                .or(selected_entity.0)
+                .and_then(|anchor| displayed.iter().position(|e| *e == anchor));
+            let end = displayed.iter().position(|e| *e == entity);
+
+            match (start, end) {
+                (Some(start), Some(end)) => {
+                    selected_entities.0 = displayed[start.min(end)..=start.max(end)].to_vec();
+                }
+                _ => {
+                    selected_entities.0 = vec![entity];
+                    anchor.0 = Some(entity);
+                }
+            }
+        }
+    }
+
+    selected_entity.0 = if selected_entities.contains(entity) {
+        Some(entity)
+    } else {
+        selected_entities.0.last().copied()
+    };
+}
+
+/// Starts a selection dragged from the gutter of the row of `entity`, by selecting only that
+/// entity. Rows dragged over are then selected like a Shift-click range.
+fn start_gutter_selection(
+    In(entity): In<Entity>,
+    mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
+    mut anchor: ResMut<SelectionAnchor>,
+) {
+    selected_entities.0 = vec![entity];
+    selected_entity.0 = Some(entity);
+    anchor.0 = Some(entity);
+}
+
+/// Whether an entity is shown, as displayed by the visibility toggle of its row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VisibilityState {
+    /// The entity has no [`Visibility`].
+    None,
+    Visible,
+    /// The entity itself is [`Visibility::Hidden`].
+    Hidden,
+    /// The entity is hidden because one of its ancestors is.
+    InheritedHidden,
+}
+
+fn visibility_state(
+    entity: Entity,
+    visibility_query: &Query<&Visibility>,
+    parent_query: &Query<&ChildOf>,
+) -> VisibilityState {
+    match visibility_query.get(entity) {
+        Err(_) => VisibilityState::None,
+        Ok(Visibility::Hidden) => VisibilityState::Hidden,
+        Ok(Visibility::Visible) => VisibilityState::Visible,
+        Ok(Visibility::Inherited) => {
+            // Walk up until an ancestor decides the visibility
+            for ancestor in parent_query.iter_ancestors(entity) {
+                match visibility_query.get(ancestor) {
+                    Ok(Visibility::Hidden) => return VisibilityState::InheritedHidden,
+                    Ok(Visibility::Visible) => return VisibilityState::Visible,
+                    _ => {}
+                }
+            }
+            VisibilityState::Visible
+        }
+    }
+}
+
+/// Hides `entity` or shows it again, recording the change for undo.
+/// Children with inherited visibility follow along.
+fn toggle_visibility(
+    In(entity): In<Entity>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let Ok(mut visibility) = visibility_query.get_mut(entity) else {
+        return;
+    };
+
+    let old_visibility = *visibility;
+    // Showing goes back to inheriting, so hidden ancestors still hide the entity
+    *visibility = match old_visibility {
+        Visibility::Hidden => Visibility::Inherited,
+        Visibility::Inherited | Visibility::Visible => Visibility::Hidden,
+    };
+
+    new_changes.write(NewChange::new(ComponentChange::new(
+        entity,
+        old_visibility,
+        *visibility,
+    )));
+}
+
+/// Locks or unlocks `entity`. Locks added with `include_children` also lock the descendants.
+fn toggle_lock(
+    In((entity, include_children)): In<(Entity, bool)>,
+    mut commands: Commands,
+    locked_query: Query<(), With<Locked>>,
+) {
+    if locked_query.contains(entity) {
+        commands.entity(entity).remove::<Locked>();
+    } else {
+        commands.entity(entity).insert(Locked { include_children });
+    }
+}
+
+/// Moves `entity` under `new_parent`, or to the root when `new_parent` is `None`, keeping its world
+/// transform and recording the change for undo.
+/// Moves that would make an entity its own ancestor are rejected.
+fn reparent_entity(
+    In((entity, new_parent)): In<(Entity, Option<Entity>)>,
+    mut commands: Commands,
+    parent_query: Query<&ChildOf>,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let old_parent = parent_query.get(entity).ok().map(ChildOf::parent);
+    if old_parent == new_parent {
+        return;
+    }
+
+    match new_parent {
+        Some(parent) => {
+            if parent == entity
+                || parent_query
+                    .iter_ancestors(parent)
+                    .any(|ancestor| ancestor == entity)
+            {
+                warn!("Can't move entity {entity} under its own descendant {parent}");
+                return;
+            }
+            commands.entity(entity).set_parent_in_place(parent);
+        }
+        None => {
+            commands.entity(entity).remove_parent_in_place();
+        }
+    }
+
+    new_changes.write(NewChange::new(ReparentedEntity {
+        entity,
+        old_parent,
+        new_parent,
+    }));
+}
+
+/// Replaces the label of `row` with a focused text field to rename `entity`.
+fn start_rename(
+    In((row, entity)): In<(Entity, Entity)>,
+    mut commands: Commands,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    mut labels: Query<&mut Node, With<SceneTreeRowLabel>>,
+    rename_fields: Query<(), With<RenameField>>,
+    mut input_focus: ResMut<InputFocus>,
+) {
+    if !rename_fields.is_empty() {
+        return;
+    }
+
+    for child in children
+        .get(row)
+        .into_iter()
+        .flat_map(|children| children.iter())
+    {
+        if let Ok(mut label) = labels.get_mut(child) {
+            label.display = Display::None;
+        }
+    }
+
+    let name = names.get(entity).map(Name::as_str).unwrap_or_default();
+    let field = commands
+        .spawn((
+            RenameField(entity),
+            EditableTextLine::new(name),
+            Node {
+                flex_grow: 1.0,
+                padding: UiRect::horizontal(Val::Px(2.0)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(2.0)),
+            BackgroundColor(tailwind::NEUTRAL_700.into()),
+            TextFont::from_font_size(11.0),
+        ))
+        .observe(on_rename_key_input)
+        .observe(|mut trigger: On<Pointer<Click>>| {
+            // Clicking inside the field shouldn't change the selection
+            trigger.propagate(false);
+        })
+        .id();
+    // In front of the lock toggle, where the label was
+    commands.entity(row).insert_children(0, &[field]);
+
+    input_focus.0 = Some(field);
+}
+
+/// Enter confirms the new name, Escape cancels the rename.
+fn on_rename_key_input(trigger: On<FocusedInput<KeyboardInput>>, mut commands: Commands) {
+    let input = &trigger.event().input;
+    if !input.state.is_pressed() {
+        return;
+    }
+
+    match input.key_code {
+        KeyCode::Enter | KeyCode::NumpadEnter => {
+            commands.run_system_cached_with(finish_rename, (trigger.target(), true));
+        }
+        KeyCode::Escape => {
+            commands.run_system_cached_with(finish_rename, (trigger.target(), false));
+        }
+        _ => {}
+    }
+}
+
+/// Clicking away from the rename field cancels the rename.
+fn cancel_rename_on_focus_loss(
+    rename_fields: Query<(Entity, Ref<HasFocus>), With<RenameField>>,
+    mut commands: Commands,
+) {
+    for (field, has_focus) in &rename_fields {
+        if has_focus.is_changed() && !has_focus.is_added() && !has_focus.0 {
+            commands.run_system_cached_with(finish_rename, (field, false));
+        }
+    }
+}
+
+/// Removes the rename `field`, applying the entered name when `confirm` is set.
+fn finish_rename(
+    In((field, confirm)): In<(Entity, bool)>,
+    mut commands: Commands,
+    rename_fields: Query<(&RenameField, &EditableTextLine)>,
+) {
+    let Ok((rename_field, text_line)) = rename_fields.get(field) else {
+        return;
+    };
+
+    if confirm {
+        commands.run_system_cached_with(rename_entity, (rename_field.0, text_line.text.clone()));
+    }
+    commands.entity(field).despawn();
+}
+
+/// Sets the [`Name`] of `entity`, inserting one if needed, and records the change for undo.
+/// Names that are empty once trimmed are rejected.
+fn rename_entity(
+    In((entity, name)): In<(Entity, String)>,
+    mut commands: Commands,
+    names: Query<&Name>,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let name = name.trim();
+    if name.is_empty() {
+        warn!("Can't rename entity {entity} to an empty name");
+        return;
+    }
+
+    let old_name = names.get(entity).ok().cloned();
+    if old_name
+        .as_ref()
+        .is_some_and(|old_name| old_name.as_str() == name)
+    {
+        return;
+    }
+
+    let new_name = Name::new(name.to_owned());
+    commands.entity(entity).insert(new_name.clone());
+    new_changes.write(NewChange::new(RenamedEntity {
+        entity,
+        old_name,
+        new_name: Some(new_name),
+    }));
+}
+
+/// The options of the context menu of the row of `entity`.
+///
+/// Delete and duplicate act on the whole selection when `entity` is part of it, and on `entity`
+/// alone otherwise.
+fn row_context_menu(entity: Entity) -> ContextMenu {
+    ContextMenu::new([
+        ContextMenuOption::new("Create Empty Child", move |mut commands, _| {
+            commands.run_system_cached_with(create_empty_child, entity);
+        }),
+        ContextMenuOption::new("Delete", move |mut commands, _| {
+            commands.queue(move |world: &mut World| {
+                let entities = action_targets(world, entity);
+                delete_entities(world, &entities);
+            });
+        }),
+        ContextMenuOption::new("Duplicate", move |mut commands, _| {
+            commands.queue(move |world: &mut World| {
+                select_unless_selected(world, entity);
+                run_command(world, "Duplicate Selection");
+            });
+        }),
+        ContextMenuOption::new("Focus Camera", move |mut commands, _| {
+            commands.queue(move |world: &mut World| {
+                select_unless_selected(world, entity);
+                run_command(world, "Focus Selection");
+            });
+        }),
+    ])
+}
+
+/// The selected entities when `entity` is one of them, or `entity` alone otherwise.
+fn action_targets(world: &World, entity: Entity) -> Vec<Entity> {
+    let selected_entities = world.resource::<SelectedEntities>();
+    if selected_entities.contains(entity) {
+        selected_entities.0.clone()
+    } else {
+        vec![entity]
+    }
+}
+
+/// Selects only `entity`, unless it is already part of the selection.
+fn select_unless_selected(world: &mut World, entity: Entity) {
+    if world.resource::<SelectedEntities>().contains(entity) {
+        return;
+    }
+    world.resource_mut::<SelectedEntities>().0 = vec![entity];
+    world.resource_mut::<SelectedEntity>().0 = Some(entity);
+}
+
+/// Spawns an empty entity under `parent` and selects it, recording the spawn for undo.
+fn create_empty_child(
+    In(parent): In<Entity>,
+    mut commands: Commands,
+    mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
+    mut new_changes: EventWriter<NewChange>,
+) {
+    let child = commands
+        .spawn((Name::new("Empty"), Transform::default(), ChildOf(parent)))
+        .id();
+
+    selected_entities.0 = vec![child];
+    selected_entity.0 = Some(child);
+    new_changes.write(NewChange::new(AddedEntity { entity: child }));
+}
+
+/// Despawns `entities` along with their descendants as a single undoable change.
+/// Locked entities are left alone.
+fn delete_entities(world: &mut World, entities: &[Entity]) {
+    let roots: Vec<Entity> = entities
+        .iter()
+        .copied()
+        .filter(|&entity| !is_locked_in_world(world, entity))
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+
+    let change = RemovedEntityTree::despawn(world, &roots);
+    world.send_event(NewChange::new(change));
+}
+
+/// Returns the entities matching `filter`, a case-insensitive substring of their [`Name`],
+/// along with all of their ancestors so the structure of the tree is kept.
+/// Returns `None` when the filter is empty and everything should be shown.
+fn filter_entities<'a>(
+    filter: &str,
+    entities: impl IntoIterator<Item = (Entity, &'a Name)>,
+    parent_of: impl Fn(Entity) -> Option<Entity>,
+) -> Option<HashSet<Entity>> {
+    let filter = filter.trim().to_lowercase();
+    if filter.is_empty() {
+        return None;
+    }
+
+    let mut visible = HashSet::default();
+    for (entity, name) in entities {
+        if !name.as_str().to_lowercase().contains(&filter) {
+            continue;
+        }
+
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            // Stop once we reach an ancestor that was already added
+            if !visible.insert(entity) {
+                break;
+            }
+            current = parent_of(entity);
+        }
+    }
+    Some(visible)
+}
+
 fn scene_tree_row_for_entity(
     entity: Entity,
     name: &Name,
-    selected_entity: &SelectedEntity,
+    locked: Option<&Locked>,
+    visibility: VisibilityState,
+    selected_entities: &SelectedEntities,
+    theme: &Theme,
 ) -> Template {
     let set_selected_entity_on_click =
-        move |mut trigger: On<Pointer<Click>>, mut selected_entity: ResMut<SelectedEntity>| {
-            if selected_entity.0 == Some(entity) {
-                selected_entity.0 = None;
+        move |mut trigger: On<Pointer<Click>>,
+              keyboard: Res<ButtonInput<KeyCode>>,
+              mut last_click: ResMut<LastRowClick>,
+              time: Res<Time<Real>>,
+              mut commands: Commands| {
+            trigger.propagate(false);
+            // Secondary clicks open the context menu and keep the selection
+            if trigger.event().button != PointerButton::Primary {
+                return;
+            }
+            let row = trigger.target();
+
+            let mode = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+                SelectMode::Range
+            } else if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+                SelectMode::Toggle
             } else {
-                selected_entity.0 = Some(entity);
+                SelectMode::Single
+            };
+
+            // Double clicking a row starts renaming its entity
+            let now = time.elapsed();
+            if mode == SelectMode::Single {
+                if let Some((last_entity, last_time)) = last_click.0.take() {
+                    if last_entity == entity && now - last_time <= DOUBLE_CLICK_TIME {
+                        commands.run_system_cached_with(start_rename, (row, entity));
+                        return;
+                    }
+                }
+                last_click.0 = Some((entity, now));
+            }
+
+            commands.run_system_cached_with(select_row, (row, mode));
+        };
+
+    // Shift-clicking the lock also locks all descendants
+    let toggle_lock_on_click = move |mut trigger: On<Pointer<Click>>,
+                                     keyboard: Res<ButtonInput<KeyCode>>,
+                                     mut commands: Commands| {
+        trigger.propagate(false);
+        let include_children = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        commands.run_system_cached_with(toggle_lock, (entity, include_children));
+    };
+    let lock_icon = match locked {
+        Some(_) => icons::LOCK,
+        None => icons::LOCK_OPEN,
+    };
+    let lock_color = match locked {
+        Some(locked) if locked.include_children => theme.text.high_priority,
+        Some(_) => theme.text.text_color,
+        None => theme.text.low_priority,
+    };
+
+    let toggle_visibility_on_click =
+        move |mut trigger: On<Pointer<Click>>, mut commands: Commands| {
+            trigger.propagate(false);
+            commands.run_system_cached_with(toggle_visibility, entity);
+        };
+    let visibility_toggle = match visibility {
+        VisibilityState::None => template! {},
+        _ => {
+            let (icon, color) = match visibility {
+                VisibilityState::Hidden => (icons::EYE_OFF, theme.text.text_color),
+                VisibilityState::InheritedHidden => (icons::EYE_OFF, theme.text.low_priority),
+                _ => (icons::EYE, theme.text.text_color),
+            };
+            template! {
+                (
+                    Text(icon.into()),
+                    TextFont {
+                        font: theme.icon.font.clone(),
+                        font_size: 11.0,
+                        ..Default::default()
+                    },
+                    TextColor(color),
+                ) => [
+                    on(toggle_visibility_on_click);
+                ];
+            }
+        }
+    };
+
+    // Dragging along the gutters selects the rows dragged over, updating live as the drag goes
+    let start_selection_on_drag_start =
+        move |mut trigger: On<Pointer<DragStart>>, mut commands: Commands| {
+            trigger.propagate(false);
+            if trigger.event().button == PointerButton::Primary {
+                commands.run_system_cached_with(start_gutter_selection, entity);
+            }
+        };
+    let extend_selection_on_drag_enter =
+        move |mut trigger: On<Pointer<DragEnter>>,
+              gutters: Query<(), With<SceneTreeGutter>>,
+              mut commands: Commands| {
+            if !gutters.contains(trigger.event().dragged) {
+                return;
             }
             trigger.propagate(false);
+            commands.run_system_cached_with(select_row, (trigger.target(), SelectMode::Range));
         };
 
+    let reparent_on_drop = move |mut trigger: On<Pointer<DragDrop>>,
+                                 rows: Query<&SceneTreeRow>,
+                                 mut commands: Commands| {
+        trigger.propagate(false);
+        if let Ok(row) = rows.get(trigger.event().dropped) {
+            commands.run_system_cached_with(reparent_entity, (row.0, Some(entity)));
+        }
+    };
+
     template! {
         {entity}: (
             Node {
                 padding: UiRect::all(Val::Px(4.0)),
                 align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceBetween,
                 ..Default::default()
             },
             BorderRadius::all(Val::Px(4.0)),
-            BackgroundColor(if selected_entity.0 == Some(entity) { tailwind::NEUTRAL_700.into() } else { Color::NONE }),
+            BackgroundColor(if selected_entities.contains(entity) { tailwind::NEUTRAL_700.into() } else { Color::NONE }),
+            SceneTreeRow(entity),
+            row_context_menu(entity),
         ) => [
             on(set_selected_entity_on_click);
+            on(reparent_on_drop);
+            on(extend_selection_on_drag_enter);
+            (
+                SceneTreeGutter,
+                // Out of the layout, so the rest of the row stays in place
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    bottom: Val::Px(0.0),
+                    width: Val::Px(6.0),
+                    ..Default::default()
+                },
+                BorderRadius::left(Val::Px(4.0)),
+                BackgroundColor(theme.text.low_priority.with_alpha(0.2)),
+            ) => [
+                on(start_selection_on_drag_start);
+            ];
             (
+                SceneTreeRowLabel,
                 Text(name.into()),
                 TextFont::from_font_size(11.0),
+                // Always part of the bundle so the label shows again after renaming
+                Node::default(),
                 Pickable::IGNORE,
             );
+            (
+                Node {
+                    column_gap: Val::Px(4.0),
+                    ..Default::default()
+                },
+                Pickable::IGNORE,
+            ) => [
+                @{ visibility_toggle };
+                (
+                    Text(lock_icon.into()),
+                    TextFont {
+                        font: theme.icon.font.clone(),
+                        font_size: 11.0,
+                        ..Default::default()
+                    },
+                    TextColor(lock_color),
+                ) => [
+                    on(toggle_lock_on_click);
+                ];
+            ];
         ];
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_undo::{ChangeChain, UndoRedo};
+
+    #[test]
+    fn filter_keeps_matches_and_their_ancestors() {
+        let mut world = World::new();
+        let level = world.spawn(Name::new("Level")).id();
+        let house = world.spawn((Name::new("House"), ChildOf(level))).id();
+        let door = world.spawn((Name::new("Front Door"), ChildOf(house))).id();
+        let window = world.spawn((Name::new("Window"), ChildOf(house))).id();
+        let tree = world.spawn((Name::new("Tree"), ChildOf(level))).id();
+        let light = world.spawn(Name::new("Sun Light")).id();
+
+        let mut query = world.query::<(Entity, &Name)>();
+        let parent_of = |entity: Entity| world.get::<ChildOf>(entity).map(ChildOf::parent);
+
+        let visible = filter_entities("door", query.iter(&world), parent_of).unwrap();
+        assert_eq!(visible, HashSet::from_iter([door, house, level]));
+        assert!(!visible.contains(&window));
+        assert!(!visible.contains(&tree));
+        assert!(!visible.contains(&light));
+
+        let visible = filter_entities("  LIGHT ", query.iter(&world), parent_of).unwrap();
+        assert_eq!(visible, HashSet::from_iter([light]));
+
+        assert!(filter_entities("", query.iter(&world), parent_of).is_none());
+        assert!(filter_entities("   ", query.iter(&world), parent_of).is_none());
+    }
+
+    #[test]
+    fn reparent_keeps_world_transform_and_can_be_undone() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, UndoPlugin));
+
+        let world = app.world_mut();
+        let a = world.spawn(Transform::default()).id();
+        let b = world.spawn(Transform::from_xyz(1., 0., 0.)).id();
+        let child = world.spawn((Transform::default(), ChildOf(a))).id();
+        app.update();
+
+        let world = app.world_mut();
+        world
+            .run_system_cached_with(reparent_entity, (child, Some(b)))
+            .unwrap();
+        assert_eq!(world.get::<ChildOf>(child).unwrap().parent(), b);
+        assert_eq!(
+            world.get::<Transform>(child).unwrap().translation,
+            Vec3::new(-1., 0., 0.)
+        );
+
+        // Moving an entity under its own descendant is rejected
+        world
+            .run_system_cached_with(reparent_entity, (b, Some(child)))
+            .unwrap();
+        assert!(world.get::<ChildOf>(b).is_none());
+
+        app.update();
+        app.update();
+
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.get::<ChildOf>(child).unwrap().parent(), a);
+        assert!(!world
+            .get::<Children>(b)
+            .is_some_and(|children| children.contains(&child)));
+    }
+
+    #[test]
+    fn shift_click_selects_displayed_range() {
+        let mut world = World::new();
+        world.init_resource::<SelectedEntity>();
+        world.init_resource::<SelectedEntities>();
+        world.init_resource::<SelectionAnchor>();
+
+        let entities: Vec<Entity> = (0..5).map(|_| world.spawn_empty().id()).collect();
+        let tree = world.spawn(SceneTreeRoot).id();
+        // The third entity is filtered out and has no row
+        let rows: Vec<Entity> = [0, 1, 3, 4]
+            .into_iter()
+            .map(|index| {
+                world
+                    .spawn((SceneTreeRow(entities[index]), ChildOf(tree)))
+                    .id()
+            })
+            .collect();
+
+        let click = |world: &mut World, row: Entity, mode: SelectMode| {
+            world
+                .run_system_cached_with(select_row, (row, mode))
+                .unwrap();
+        };
+
+        click(&mut world, rows[3], SelectMode::Single);
+        click(&mut world, rows[0], SelectMode::Range);
+        assert_eq!(
+            world.resource::<SelectedEntities>().0,
+            vec![entities[0], entities[1], entities[3], entities[4]]
+        );
+        assert_eq!(world.resource::<SelectedEntity>().0, Some(entities[0]));
+
+        // Extending the range again starts from the same anchor
+        click(&mut world, rows[2], SelectMode::Range);
+        assert_eq!(
+            world.resource::<SelectedEntities>().0,
+            vec![entities[3], entities[4]]
+        );
+
+        click(&mut world, rows[1], SelectMode::Toggle);
+        assert_eq!(
+            world.resource::<SelectedEntities>().0,
+            vec![entities[3], entities[4], entities[1]]
+        );
+        click(&mut world, rows[2], SelectMode::Toggle);
+        assert_eq!(
+            world.resource::<SelectedEntities>().0,
+            vec![entities[4], entities[1]]
+        );
+
+        click(&mut world, rows[0], SelectMode::Single);
+        assert_eq!(world.resource::<SelectedEntities>().0, vec![entities[0]]);
+        assert_eq!(world.resource::<SelectedEntity>().0, Some(entities[0]));
+    }
+
+    #[test]
+    fn gutter_drag_selects_rows_dragged_over() {
+        let mut world = World::new();
+        world.init_resource::<SelectedEntity>();
+        world.init_resource::<SelectedEntities>();
+        world.init_resource::<SelectionAnchor>();
+
+        let entities: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+        let tree = world.spawn(SceneTreeRoot).id();
+        let rows: Vec<Entity> = entities
+            .iter()
+            .map(|&entity| world.spawn((SceneTreeRow(entity), ChildOf(tree))).id())
+            .collect();
+        world.resource_mut::<SelectedEntities>().0 = vec![entities[0]];
+
+        // Pressing on the gutter of the second row and dragging down over two more rows
+        world
+            .run_system_cached_with(start_gutter_selection, entities[1])
+            .unwrap();
+        assert_eq!(world.resource::<SelectedEntities>().0, vec![entities[1]]);
+        for row in [rows[2], rows[3]] {
+            world
+                .run_system_cached_with(select_row, (row, SelectMode::Range))
+                .unwrap();
+        }
+        assert_eq!(
+            world.resource::<SelectedEntities>().0,
+            entities[1..=3].to_vec()
+        );
+
+        // Dragging back up shrinks the selection
+        world
+            .run_system_cached_with(select_row, (rows[2], SelectMode::Range))
+            .unwrap();
+        assert_eq!(
+            world.resource::<SelectedEntities>().0,
+            entities[1..=2].to_vec()
+        );
+    }
+
+    fn state(world: &mut World, entity: Entity) -> VisibilityState {
+        world
+            .run_system_cached_with(
+                |In(entity): In<Entity>,
+                 visibility_query: Query<&Visibility>,
+                 parent_query: Query<&ChildOf>| {
+                    visibility_state(entity, &visibility_query, &parent_query)
+                },
+                entity,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn hiding_parent_hides_children_and_can_be_undone() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin));
+
+        let world = app.world_mut();
+        let parent = world.spawn(Visibility::default()).id();
+        let child = world.spawn((Visibility::default(), ChildOf(parent))).id();
+        let no_visibility = world.spawn(ChildOf(parent)).id();
+        app.update();
+
+        let world = app.world_mut();
+        world
+            .run_system_cached_with(toggle_visibility, parent)
+            .unwrap();
+
+        assert_eq!(world.get::<Visibility>(parent), Some(&Visibility::Hidden));
+        assert_eq!(world.get::<Visibility>(child), Some(&Visibility::Inherited));
+        assert_eq!(state(world, parent), VisibilityState::Hidden);
+        assert_eq!(state(world, child), VisibilityState::InheritedHidden);
+        assert_eq!(state(world, no_visibility), VisibilityState::None);
+
+        app.update();
+        app.update();
+
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        assert_eq!(
+            world.get::<Visibility>(parent),
+            Some(&Visibility::Inherited)
+        );
+        assert_eq!(state(world, child), VisibilityState::Visible);
+    }
+
+    #[test]
+    fn rename_from_row_and_undo() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .init_resource::<InputFocus>();
+
+        let world = app.world_mut();
+        let entity = world.spawn(Name::new("Cube")).id();
+        let row = world
+            .spawn(SceneTreeRow(entity))
+            .with_child((SceneTreeRowLabel, Text::new("Cube"), Node::default()))
+            .id();
+        app.update();
+
+        let world = app.world_mut();
+        let start = |world: &mut World| {
+            world
+                .run_system_cached_with(start_rename, (row, entity))
+                .unwrap();
+            world
+                .query_filtered::<Entity, With<RenameField>>()
+                .single(world)
+                .unwrap()
+        };
+        let set_text = |world: &mut World, field: Entity, text: &str| {
+            world.get_mut::<EditableTextLine>(field).unwrap().text = text.into();
+        };
+
+        let field = start(world);
+        assert_eq!(world.resource::<InputFocus>().0, Some(field));
+        assert_eq!(world.get::<EditableTextLine>(field).unwrap().text, "Cube");
+        let label = world
+            .query_filtered::<Entity, With<SceneTreeRowLabel>>()
+            .single(world)
+            .unwrap();
+        assert_eq!(world.get::<Node>(label).unwrap().display, Display::None);
+
+        // Cancelling keeps the old name
+        set_text(world, field, "Sphere");
+        world
+            .run_system_cached_with(finish_rename, (field, false))
+            .unwrap();
+        assert!(world.get_entity(field).is_err());
+        assert_eq!(world.get::<Name>(entity).unwrap().as_str(), "Cube");
+
+        // Empty names are rejected
+        let field = start(world);
+        set_text(world, field, "   ");
+        world
+            .run_system_cached_with(finish_rename, (field, true))
+            .unwrap();
+        assert_eq!(world.get::<Name>(entity).unwrap().as_str(), "Cube");
+
+        let field = start(world);
+        set_text(world, field, " Crate ");
+        world
+            .run_system_cached_with(finish_rename, (field, true))
+            .unwrap();
+        assert_eq!(world.get::<Name>(entity).unwrap().as_str(), "Crate");
+
+        app.update();
+        app.update();
+
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().get::<Name>(entity).unwrap().as_str(), "Cube");
+    }
+
+    #[test]
+    fn create_empty_child_and_undo() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .init_resource::<SelectedEntity>()
+            .init_resource::<SelectedEntities>();
+
+        let parent = app.world_mut().spawn(Name::new("House")).id();
+        app.world_mut()
+            .run_system_cached_with(create_empty_child, parent)
+            .unwrap();
+
+        let world = app.world();
+        let children = world.get::<Children>(parent).unwrap();
+        assert_eq!(children.len(), 1);
+        let child = children[0];
+        assert_eq!(world.get::<Name>(child).unwrap().as_str(), "Empty");
+        assert_eq!(world.resource::<SelectedEntity>().0, Some(child));
+
+        app.update();
+        app.update();
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert!(world.get_entity(child).is_err());
+        assert!(world
+            .get::<Children>(parent)
+            .is_none_or(|children| children.is_empty()));
+    }
+
+    #[test]
+    fn delete_removes_subtree_and_undo_restores_it() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UndoPlugin))
+            .init_resource::<SelectedEntities>()
+            .register_type::<Name>()
+            .register_type::<ChildOf>();
+
+        let world = app.world_mut();
+        let level = world.spawn(Name::new("Level")).id();
+        let house = world.spawn((Name::new("House"), ChildOf(level))).id();
+        let door = world.spawn((Name::new("Door"), ChildOf(house))).id();
+        let locked = world.spawn((Name::new("Tree"), Locked::default())).id();
+        world.resource_mut::<SelectedEntities>().0 = vec![house, door, locked];
+
+        // Right-clicking a selected row deletes the whole selection, except locked entities
+        let entities = action_targets(world, door);
+        delete_entities(world, &entities);
+        assert!(world.get_entity(house).is_err());
+        assert!(world.get_entity(door).is_err());
+        assert!(world.get_entity(locked).is_ok());
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<ChangeChain>().changes.len(), 1);
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let mut names = world.query::<(Entity, &Name)>();
+        let mut find = |world: &World, name: &str| {
+            names
+                .iter(world)
+                .find(|(_, entity_name)| entity_name.as_str() == name)
+                .map(|(entity, _)| entity)
+        };
+        let restored_house = find(world, "House").unwrap();
+        let restored_door = find(world, "Door").unwrap();
+        assert_eq!(
+            world.get::<ChildOf>(restored_house).unwrap().parent(),
+            level
+        );
+        assert_eq!(
+            world.get::<ChildOf>(restored_door).unwrap().parent(),
+            restored_house
+        );
+    }
+}
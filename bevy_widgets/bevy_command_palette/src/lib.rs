@@ -3,20 +3,245 @@
 //! This lists a number of commands that can be executed by the user,
 //! allowing for quick access to a variety of functionality.
 //!
-//! Search and keyboard shortcuts will both be supported.
+//! Commands are registered in the [`CommandRegistry`], usually through
+//! [`CommandAppExt::register_command`], and searched by typing part of their name.
 
-/// an add function that adds two numbers
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
+pub mod registry;
+
+use bevy::{
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus},
+    prelude::*,
+};
+use bevy_editor_core::{actions, EditorKeybindings};
+use bevy_editor_styles::Theme;
+use bevy_i_cant_believe_its_not_bsn::{on, template, Template, TemplateEntityCommandsExt};
+use bevy_text_editing::{EditableTextLine, EditableTextLinePlugin, TextChanged};
+
+pub use registry::{fuzzy_match, run_command, CommandAppExt, CommandRegistry};
+
+/// The most commands listed at once, the search narrows down the rest.
+const MAX_RESULTS: usize = 12;
+
+/// The Command Palette Plugin.
+///
+/// The command palette action, `Ctrl + P` by default, opens the palette. Arrow keys move through
+/// the matching commands, `Enter` runs the highlighted one and `Escape` closes the palette.
+pub struct CommandPalettePlugin;
+
+impl Plugin for CommandPalettePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EditableTextLinePlugin>() {
+            app.add_plugins(EditableTextLinePlugin);
+        }
+
+        app.init_resource::<CommandRegistry>()
+            .init_resource::<InputFocus>()
+            .add_systems(
+                Update,
+                (toggle_command_palette, update_command_palette).chain(),
+            );
+    }
+}
+
+/// The root node of an open command palette.
+#[derive(Component)]
+struct CommandPalette {
+    /// The text typed in the search field.
+    query: String,
+    /// The index of the highlighted command among the matching ones.
+    selected: usize,
+    /// The node listing the matching commands.
+    list: Entity,
+}
+
+/// The command palette action opens the palette, or closes it when already open.
+fn toggle_command_palette(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    palettes: Query<Entity, With<CommandPalette>>,
+    mut input_focus: ResMut<InputFocus>,
+    theme: Res<Theme>,
+) {
+    if !keybindings.is_action_pressed(actions::COMMAND_PALETTE, &keyboard) {
+        return;
+    }
+    if !palettes.is_empty() {
+        for palette in &palettes {
+            commands.entity(palette).despawn();
+        }
+        return;
+    }
+
+    let list = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            ..default()
+        })
+        .id();
+    let palette = commands
+        .spawn((
+            CommandPalette {
+                query: String::new(),
+                selected: 0,
+                list,
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(15.),
+                left: Val::Percent(50.),
+                width: Val::Px(400.),
+                margin: UiRect::left(Val::Px(-200.)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(3.),
+                padding: UiRect::all(Val::Px(3.)),
+                ..default()
+            },
+            BoxShadow::from(ShadowStyle {
+                blur_radius: Val::Px(3.),
+                x_offset: Val::ZERO,
+                y_offset: Val::ZERO,
+                color: Color::BLACK.with_alpha(0.8),
+                ..Default::default()
+            }),
+            GlobalZIndex(1000),
+            theme.context_menu.background_color,
+            theme.general.border_radius,
+        ))
+        .id();
+
+    let field = commands
+        .spawn((
+            EditableTextLine::new(""),
+            Node {
+                height: Val::Px(24.),
+                padding: UiRect::horizontal(Val::Px(5.)),
+                ..default()
+            },
+            theme.context_menu.option_border_radius,
+            theme.general.background_color,
+            TextFont {
+                font: theme.text.font.clone(),
+                font_size: 12.,
+                ..default()
+            },
+            ChildOf(palette),
+        ))
+        .observe(on_query_changed)
+        .observe(on_palette_key_input)
+        .id();
+    commands.entity(list).insert(ChildOf(palette));
+
+    input_focus.0 = Some(field);
+}
+
+fn on_query_changed(
+    trigger: On<TextChanged>,
+    child_of_query: Query<&ChildOf>,
+    mut palettes: Query<&mut CommandPalette>,
+) {
+    let Ok(mut palette) = child_of_query
+        .get(trigger.target())
+        .and_then(|child_of| palettes.get_mut(child_of.parent()))
+    else {
+        return;
+    };
+    palette.query = trigger.event().new_text.clone();
+    palette.selected = 0;
+}
+
+/// Arrow keys move the highlight, Enter runs the highlighted command, Escape closes the palette.
+fn on_palette_key_input(
+    trigger: On<FocusedInput<KeyboardInput>>,
+    mut commands: Commands,
+    child_of_query: Query<&ChildOf>,
+    mut palettes: Query<(Entity, &mut CommandPalette)>,
+    registry: Res<CommandRegistry>,
+) {
+    let input = &trigger.event().input;
+    if !input.state.is_pressed() {
+        return;
+    }
+    let Ok((palette_entity, mut palette)) = child_of_query
+        .get(trigger.target())
+        .and_then(|child_of| palettes.get_mut(child_of.parent()))
+    else {
+        return;
+    };
+
+    let results = registry.search(&palette.query);
+    let count = results.len().min(MAX_RESULTS);
+    match input.key_code {
+        KeyCode::ArrowDown if count > 0 => palette.selected = (palette.selected + 1) % count,
+        KeyCode::ArrowUp if count > 0 => palette.selected = (palette.selected + count - 1) % count,
+        KeyCode::Enter | KeyCode::NumpadEnter => {
+            if let Some(name) = results.get(palette.selected) {
+                invoke_command(&mut commands, palette_entity, name.to_string());
+            }
+        }
+        KeyCode::Escape => commands.entity(palette_entity).despawn(),
+        _ => {}
+    }
+}
+
+/// Closes the palette and runs the command named `name`.
+fn invoke_command(commands: &mut Commands, palette: Entity, name: String) {
+    commands.entity(palette).despawn();
+    commands.queue(move |world: &mut World| {
+        run_command(world, &name);
+    });
+}
+
+/// Lists the commands matching the search of each palette, highlighting the selected one.
+fn update_command_palette(
+    mut commands: Commands,
+    palettes: Query<(Entity, &CommandPalette), Changed<CommandPalette>>,
+    registry: Res<CommandRegistry>,
+    theme: Res<Theme>,
+) {
+    for (palette_entity, palette) in &palettes {
+        let rows: Template = registry
+            .search(&palette.query)
+            .into_iter()
+            .take(MAX_RESULTS)
+            .enumerate()
+            .flat_map(|(index, name)| {
+                command_row(palette_entity, name, index == palette.selected, &theme)
+            })
+            .collect();
+
+        commands.entity(palette.list).build_children(rows);
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn command_row(palette: Entity, name: &str, selected: bool, theme: &Theme) -> Template {
+    let command = name.to_string();
+    let invoke_on_click = move |trigger: On<Pointer<Click>>, mut commands: Commands| {
+        if trigger.event().button == PointerButton::Primary {
+            invoke_command(&mut commands, palette, command.clone());
+        }
+    };
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    template! {
+        {name}: (
+            Node {
+                padding: UiRect::all(Val::Px(5.)),
+                ..default()
+            },
+            if selected { theme.context_menu.hover_color } else { BackgroundColor(Color::NONE) },
+            theme.context_menu.option_border_radius,
+        ) => [
+            on(invoke_on_click);
+            (
+                Text(name.into()),
+                TextFont {
+                    font: theme.text.font.clone(),
+                    font_size: 12.,
+                    ..default()
+                },
+                Pickable::IGNORE,
+            );
+        ];
     }
 }
@@ -0,0 +1,162 @@
+//! [`CommandRegistry`] module.
+
+use bevy::{
+    ecs::system::{BoxedSystem, SystemId},
+    prelude::*,
+};
+
+/// A registry of the commands listed in the command palette.
+#[derive(Resource, Default)]
+pub struct CommandRegistry {
+    commands: Vec<PaletteCommand>,
+}
+
+struct PaletteCommand {
+    name: String,
+    system: Option<BoxedSystem>,
+    id: Option<SystemId>,
+}
+
+impl CommandRegistry {
+    /// Register a new command, running `system` when invoked.
+    pub fn register<M>(&mut self, name: impl Into<String>, system: impl IntoSystem<(), (), M>) {
+        self.commands.push(PaletteCommand {
+            name: name.into(),
+            system: Some(Box::new(IntoSystem::into_system(system))),
+            id: None,
+        });
+    }
+
+    /// The names of the registered commands, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.commands.iter().map(|command| command.name.as_str())
+    }
+
+    /// The names of the commands matching `query`, best matches first.
+    /// See [`fuzzy_match`] for how names are matched.
+    pub fn search(&self, query: &str) -> Vec<&str> {
+        let mut matches: Vec<(u32, &str)> = self
+            .names()
+            .filter_map(|name| fuzzy_match(query, name).map(|score| (score, name)))
+            .collect();
+        // Stable, so equally good matches keep their registration order
+        matches.sort_by(|a, b| b.0.cmp(&a.0));
+        matches.into_iter().map(|(_, name)| name).collect()
+    }
+}
+
+/// Scores how well `candidate` matches `query`, or returns `None` if it doesn't match.
+///
+/// The characters of the query have to appear in the candidate in the same order, ignoring case
+/// and whitespace in the query. Consecutive characters and characters starting a word score
+/// higher, so `"svpf"` matches `"Save Prefab"` but `"save"` matches it better.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<u32> {
+    let mut score = 0;
+    let mut candidate_chars = candidate.chars().flat_map(char::to_lowercase).enumerate();
+    let mut previous_index: Option<usize> = None;
+    let mut last_char = None;
+
+    for query_char in query
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        loop {
+            let (index, character) = candidate_chars.next()?;
+            let word_start = last_char.is_none_or(|last: char| !last.is_alphanumeric());
+            last_char = Some(character);
+            if character != query_char {
+                continue;
+            }
+
+            score += 1;
+            if word_start {
+                score += 3;
+            }
+            if previous_index.is_some_and(|previous_index| previous_index + 1 == index) {
+                score += 2;
+            }
+            previous_index = Some(index);
+            break;
+        }
+    }
+
+    Some(score)
+}
+
+/// Runs the command named `name`, returning `false` if there is no such command.
+pub fn run_command(world: &mut World, name: &str) -> bool {
+    let id = world.resource_scope(|world, mut registry: Mut<CommandRegistry>| {
+        let command = registry
+            .commands
+            .iter_mut()
+            .find(|command| command.name == name)?;
+        let id = *command
+            .id
+            .get_or_insert_with(|| world.register_boxed_system(command.system.take().unwrap()));
+        Some(id)
+    });
+
+    let Some(id) = id else {
+        warn!("No command found in the registry with name: '{name}'");
+        return false;
+    };
+    if let Err(error) = world.run_system(id) {
+        error!("Unable to run command '{name}': {error}");
+    }
+    true
+}
+
+/// Extension trait for [`App`].
+pub trait CommandAppExt {
+    /// Register a new command, running `system` when invoked.
+    fn register_command<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M>,
+    ) -> &mut Self;
+}
+
+impl CommandAppExt for App {
+    fn register_command<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M>,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<CommandRegistry>()
+            .register(name, system);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Saves(u32);
+
+    #[test]
+    fn fuzzy_matched_command_runs_when_invoked() {
+        let mut app = App::new();
+        app.init_resource::<Saves>()
+            .register_command("Save Prefab", |mut saves: ResMut<Saves>| saves.0 += 1)
+            .register_command("Load glTF Scene", || {})
+            .register_command("Spawn Sphere", || {});
+
+        let registry = app.world().resource::<CommandRegistry>();
+        assert_eq!(registry.search("svpf"), vec!["Save Prefab"]);
+        assert_eq!(registry.search("s")[0], "Save Prefab");
+        assert!(registry.search("sphere save").is_empty());
+        assert_eq!(registry.search("").len(), 3);
+        assert!(fuzzy_match("save", "Save Prefab") > fuzzy_match("svpf", "Save Prefab"));
+
+        let name = registry.search("svpf")[0].to_owned();
+        assert!(run_command(app.world_mut(), &name));
+        assert!(run_command(app.world_mut(), &name));
+        assert_eq!(app.world().resource::<Saves>().0, 2);
+        assert!(!run_command(app.world_mut(), "Missing"));
+    }
+}
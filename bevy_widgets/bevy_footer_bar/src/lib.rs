@@ -11,6 +11,10 @@ use bevy_editor_styles::Theme;
 #[derive(Component)]
 pub struct FooterBarNode;
 
+/// The left part of the Footer bar, where status indicators are shown.
+#[derive(Component)]
+pub struct FooterBarStatusNode;
+
 /// The Bevy Footer Bar Plugin.
 pub struct FooterBarPlugin;
 
@@ -45,13 +49,16 @@ fn footer_setup(
             theme.general.background_color,
         ))
         .with_children(|parent| {
-            parent.spawn(Node {
-                width: Val::Percent(50.0),
-                height: Val::Percent(100.0),
-                justify_content: JustifyContent::FlexStart,
-                align_items: AlignItems::Center,
-                ..Default::default()
-            });
+            parent.spawn((
+                Node {
+                    width: Val::Percent(50.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::FlexStart,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                FooterBarStatusNode,
+            ));
             parent
                 .spawn(Node {
                     width: Val::Percent(50.0),
@@ -1,13 +1,349 @@
-use bevy::prelude::*;
+//! Small rendered previews of assets, to tell them apart at a glance in the editor UI.
 
-/// This crate is a work in progress and is not yet ready for use.
-/// The intention is to provide a way to load/render/unload assets in the background and provide previews of them in the Bevy Editor.
-/// For 2d assets this will be a simple sprite, for 3d assets this will require a quick render of the asset at a low resolution, just enough for a user to be able to tell quickly what it is.
+use std::collections::VecDeque;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    platform::collections::HashMap,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::RenderLayers,
+    },
+};
+use bevy_editor_core::EditorRenderLayers;
+
+/// The width and height of the preview images, in pixels.
+pub const PREVIEW_SIZE: u32 = 64;
+
+/// The number of frames a preview camera stays active, long enough for the render pipelines of
+/// a new material to be ready.
+const PREVIEW_RENDER_FRAMES: u32 = 10;
+
+/// Renders previews of mesh and material assets in the background, see [`AssetPreviews`].
+///
+/// For 3d assets this is a quick render of the asset at a low resolution, just enough for a user
+/// to be able to tell quickly what it is.
 /// This code may be reused for the Bevy Marketplace Viewer to provide previews of assets and plugins.
-/// So long as the assets are unchanged, the previews will be cached and will not need to be re-rendered.
-/// In theory this can be done passively in the background, and the previews will be ready when the user needs them.
+/// So long as the assets are unchanged, the previews are cached and not re-rendered.
 pub struct AssetPreviewPlugin;
 
 impl Plugin for AssetPreviewPlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssetPreviews>()
+            .init_resource::<EditorRenderLayers>()
+            .add_systems(
+                PostUpdate,
+                (invalidate_previews, spawn_previews, render_previews).chain(),
+            );
+    }
+}
+
+/// An asset a preview can be rendered for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PreviewSource {
+    /// A mesh, shown with a plain material.
+    Mesh(Handle<Mesh>),
+    /// A material, shown on a sphere.
+    Material(Handle<StandardMaterial>),
+}
+
+/// The cached asset previews.
+///
+/// [`request`](Self::request) a preview for each asset shown in the UI and display the image
+/// returned by [`get`](Self::get). Previews are rendered one at a time, the image stays
+/// transparent until its preview is rendered, and is rendered again when the asset changes.
+#[derive(Resource, Default)]
+pub struct AssetPreviews {
+    previews: HashMap<PreviewSource, Preview>,
+    /// Previews requested since the last frame, spawned in [`PostUpdate`].
+    requested: Vec<PreviewSource>,
+    /// Previews waiting to be rendered.
+    queue: VecDeque<PreviewSource>,
+    /// The preview being rendered, with the frames left before its camera is turned off.
+    rendering: Option<(PreviewSource, u32)>,
+    light: Option<Entity>,
+    sphere: Option<Handle<Mesh>>,
+    plain_material: Option<Handle<StandardMaterial>>,
+}
+
+/// The image of a preview, along with the camera and entity rendered to it.
+struct Preview {
+    image: Handle<Image>,
+    camera: Entity,
+    subject: Entity,
+}
+
+impl AssetPreviews {
+    /// Requests a preview of `source`, rendered in the background unless it is already cached.
+    pub fn request(&mut self, source: PreviewSource) {
+        if !self.previews.contains_key(&source) && !self.requested.contains(&source) {
+            self.requested.push(source);
+        }
+    }
+
+    /// The preview image of `source`, if it was requested.
+    pub fn get(&self, source: &PreviewSource) -> Option<Handle<Image>> {
+        self.previews
+            .get(source)
+            .map(|preview| preview.image.clone())
+    }
+
+    fn enqueue(&mut self, source: PreviewSource) {
+        if !self.queue.contains(&source) {
+            self.queue.push_back(source);
+        }
+    }
+}
+
+/// Renders the previews of the assets that changed again.
+fn invalidate_previews(
+    mut previews: ResMut<AssetPreviews>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut material_events: EventReader<AssetEvent<StandardMaterial>>,
+) {
+    let modified_meshes: Vec<AssetId<Mesh>> = mesh_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let modified_materials: Vec<AssetId<StandardMaterial>> = material_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    let changed: Vec<PreviewSource> = previews
+        .previews
+        .keys()
+        .filter(|source| match source {
+            PreviewSource::Mesh(mesh) => modified_meshes.contains(&mesh.id()),
+            PreviewSource::Material(material) => modified_materials.contains(&material.id()),
+        })
+        .cloned()
+        .collect();
+
+    for source in changed {
+        previews.enqueue(source);
+    }
+}
+
+/// Spawns the camera and entity of the requested previews, each rendering to its own image.
+fn spawn_previews(
+    mut commands: Commands,
+    mut previews: ResMut<AssetPreviews>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    render_layers: Res<EditorRenderLayers>,
+) {
+    if previews.requested.is_empty() {
+        return;
+    }
+    let previews = &mut *previews;
+    let layer = RenderLayers::layer(render_layers.asset_preview);
+
+    previews.light.get_or_insert_with(|| {
+        commands
+            .spawn((
+                DirectionalLight::default(),
+                Transform::from_xyz(1., 2., 3.).looking_at(Vec3::ZERO, Vec3::Y),
+                layer.clone(),
+            ))
+            .id()
+    });
+    let sphere = previews
+        .sphere
+        .get_or_insert_with(|| meshes.add(Sphere::new(0.5).mesh().uv(32, 18)))
+        .clone();
+    let plain_material = previews
+        .plain_material
+        .get_or_insert_with(|| materials.add(StandardMaterial::default()))
+        .clone();
+
+    for source in std::mem::take(&mut previews.requested) {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: PREVIEW_SIZE,
+                height: PREVIEW_SIZE,
+                ..default()
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::RENDER_ATTACHMENT;
+        let image = images.add(image);
+
+        let camera = commands
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    target: RenderTarget::Image(image.clone().into()),
+                    clear_color: ClearColorConfig::Custom(Color::NONE),
+                    is_active: false,
+                    ..default()
+                },
+                layer.clone(),
+            ))
+            .id();
+        let (mesh, material) = match &source {
+            PreviewSource::Mesh(mesh) => (mesh.clone(), plain_material.clone()),
+            PreviewSource::Material(material) => (sphere.clone(), material.clone()),
+        };
+        // All previews share the layer, so only the one being rendered is visible
+        let subject = commands
+            .spawn((
+                Mesh3d(mesh),
+                MeshMaterial3d(material),
+                Visibility::Hidden,
+                layer.clone(),
+            ))
+            .id();
+
+        previews.previews.insert(
+            source.clone(),
+            Preview {
+                image,
+                camera,
+                subject,
+            },
+        );
+        previews.enqueue(source);
+    }
+}
+
+/// Renders the queued previews one at a time, turning each camera off once its preview is
+/// rendered.
+fn render_previews(
+    mut previews: ResMut<AssetPreviews>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<StandardMaterial>>,
+    mut cameras: Query<(&mut Camera, &mut Transform)>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let previews = &mut *previews;
+    match &mut previews.rendering {
+        Some((_, frames)) if *frames > 0 => {
+            *frames -= 1;
+            return;
+        }
+        Some((source, _)) => {
+            if let Some(preview) = previews.previews.get(source) {
+                if let Ok((mut camera, _)) = cameras.get_mut(preview.camera) {
+                    camera.is_active = false;
+                }
+                if let Ok(mut visibility) = visibilities.get_mut(preview.subject) {
+                    *visibility = Visibility::Hidden;
+                }
+            }
+            previews.rendering = None;
+        }
+        None => {}
+    }
+
+    // Assets that are still loading wait in the queue
+    let Some(index) = previews.queue.iter().position(|source| match source {
+        PreviewSource::Mesh(mesh) => meshes.contains(mesh),
+        PreviewSource::Material(material) => materials.contains(material),
+    }) else {
+        return;
+    };
+    let source = previews.queue.remove(index).unwrap();
+    let Some(preview) = previews.previews.get(&source) else {
+        return;
+    };
+
+    // Frame the bounds of the mesh, so small and large meshes are shown alike
+    let (center, radius) = match &source {
+        PreviewSource::Mesh(mesh) => {
+            meshes
+                .get(mesh)
+                .and_then(Mesh::compute_aabb)
+                .map_or((Vec3::ZERO, 0.5), |aabb| {
+                    (
+                        Vec3::from(aabb.center),
+                        aabb.half_extents.length().max(0.01),
+                    )
+                })
+        }
+        PreviewSource::Material(_) => (Vec3::ZERO, 0.5),
+    };
+    if let Ok((mut camera, mut transform)) = cameras.get_mut(preview.camera) {
+        camera.is_active = true;
+        *transform = Transform::from_translation(center + Vec3::ONE.normalize() * radius * 3.)
+            .looking_at(center, Vec3::Y);
+    }
+    if let Ok(mut visibility) = visibilities.get_mut(preview.subject) {
+        *visibility = Visibility::Inherited;
+    }
+    previews.rendering = Some((source, PREVIEW_RENDER_FRAMES));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_cameras(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&Camera>()
+            .iter(app.world())
+            .filter(|camera| camera.is_active)
+            .count()
+    }
+
+    #[test]
+    fn previews_are_cached_and_rendered_once() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), AssetPreviewPlugin))
+            .init_asset::<Image>()
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>();
+
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let source = PreviewSource::Material(material.clone());
+        app.world_mut()
+            .resource_mut::<AssetPreviews>()
+            .request(source.clone());
+        app.update();
+
+        let image = app.world().resource::<AssetPreviews>().get(&source);
+        assert!(image.is_some());
+        assert_eq!(active_cameras(&mut app), 1);
+
+        // Requesting a cached preview doesn't render it again
+        app.world_mut()
+            .resource_mut::<AssetPreviews>()
+            .request(source.clone());
+        for _ in 0..=PREVIEW_RENDER_FRAMES {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<AssetPreviews>().get(&source), image);
+        assert_eq!(
+            app.world_mut().query::<&Camera>().iter(app.world()).count(),
+            1
+        );
+        assert_eq!(active_cameras(&mut app), 0);
+
+        // Changing the material renders it again
+        app.world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .get_mut(&material)
+            .unwrap()
+            .base_color = Color::BLACK;
+        app.update();
+        app.update();
+        assert_eq!(active_cameras(&mut app), 1);
+    }
 }
@@ -0,0 +1,136 @@
+//! Assigning the material of the selected entity to the other selected meshes.
+
+use bevy::prelude::*;
+use bevy_command_palette::CommandAppExt;
+use bevy_editor_core::{is_locked_in_world, SelectedEntities, SelectedEntity};
+use bevy_undo::{ComponentChange, NewChange, UndoPlugin};
+
+pub(crate) struct AssignMaterialPlugin;
+
+impl Plugin for AssignMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<UndoPlugin>() {
+            app.add_plugins(UndoPlugin);
+        }
+
+        app.register_command("Apply Material To Selection", |mut commands: Commands| {
+            commands.queue(apply_selected_material);
+        });
+    }
+}
+
+/// Assigns the [`StandardMaterial`] of the primary [`SelectedEntity`] to the other selected
+/// meshes.
+pub(crate) fn apply_selected_material(world: &mut World) {
+    let Some(source) = world.resource::<SelectedEntity>().0 else {
+        return;
+    };
+    let Some(material) = world
+        .get::<MeshMaterial3d<StandardMaterial>>(source)
+        .map(|material| material.0.clone())
+    else {
+        return;
+    };
+
+    let targets: Vec<Entity> = world
+        .resource::<SelectedEntities>()
+        .0
+        .iter()
+        .copied()
+        .filter(|&entity| entity != source)
+        .collect();
+    assign_material(world, &targets, material);
+}
+
+/// Assigns `material` to the meshes among `targets`, undone all at once.
+///
+/// Only meshes that already use a [`StandardMaterial`] are changed, locked entities are skipped.
+pub(crate) fn assign_material(
+    world: &mut World,
+    targets: &[Entity],
+    material: Handle<StandardMaterial>,
+) {
+    let new_material = MeshMaterial3d(material);
+    for &entity in targets {
+        if world.get::<Mesh3d>(entity).is_none() || is_locked_in_world(world, entity) {
+            continue;
+        }
+        let Some(mut current) = world.get_mut::<MeshMaterial3d<StandardMaterial>>(entity) else {
+            continue;
+        };
+        if *current == new_material {
+            continue;
+        }
+        let old_material = std::mem::replace(&mut *current, new_material.clone());
+
+        // Changes sent in the same frame are undone together
+        world.send_event(NewChange::new(ComponentChange::new(
+            entity,
+            old_material,
+            new_material.clone(),
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_editor_core::{EditorCorePlugin, Locked};
+    use bevy_undo::UndoRedo;
+
+    use super::*;
+
+    #[test]
+    fn selected_meshes_adopt_the_material_of_the_primary_selection() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), EditorCorePlugin))
+            .add_plugins(AssignMaterialPlugin)
+            .init_asset::<StandardMaterial>();
+
+        let mut materials = app.world_mut().resource_mut::<Assets<StandardMaterial>>();
+        let [red, green, blue] = [
+            Color::srgb(1., 0., 0.),
+            Color::srgb(0., 1., 0.),
+            Color::BLACK,
+        ]
+        .map(|color| materials.add(color));
+
+        let world = app.world_mut();
+        let mesh = |material: &Handle<StandardMaterial>| {
+            (Mesh3d::default(), MeshMaterial3d(material.clone()))
+        };
+        let source = world.spawn(mesh(&red)).id();
+        let first = world.spawn(mesh(&green)).id();
+        let second = world.spawn(mesh(&blue)).id();
+        let locked = world.spawn((mesh(&green), Locked::default())).id();
+        let not_a_mesh = world.spawn(Name::new("Empty")).id();
+        world.resource_mut::<SelectedEntities>().0 =
+            vec![first, second, locked, not_a_mesh, source];
+        world.resource_mut::<SelectedEntity>().0 = Some(source);
+        app.update();
+
+        apply_selected_material(app.world_mut());
+
+        let material = |app: &App, entity| {
+            app.world()
+                .get::<MeshMaterial3d<StandardMaterial>>(entity)
+                .unwrap()
+                .0
+                .clone()
+        };
+        assert_eq!(material(&app, first), red);
+        assert_eq!(material(&app, second), red);
+        assert_eq!(material(&app, locked), green);
+        assert_eq!(material(&app, source), red);
+
+        // A single undo restores every material
+        app.update();
+        app.update();
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        assert_eq!(material(&app, first), green);
+        assert_eq!(material(&app, second), blue);
+        assert_eq!(material(&app, source), red);
+    }
+}
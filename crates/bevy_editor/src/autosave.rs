@@ -0,0 +1,209 @@
+//! Periodically saving the scene to a rotating set of backup files.
+
+use std::{path::PathBuf, time::Duration};
+
+use bevy::prelude::*;
+use bevy_editor_settings::SettingsType;
+
+use crate::{
+    save_prefab::subtrees_scene,
+    unsaved_changes::{SceneSaved, UnsavedChanges},
+};
+
+pub(crate) struct AutosavePlugin;
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Autosave>()
+            .init_resource::<Autosave>()
+            .init_resource::<AutosaveTimer>()
+            .init_resource::<UnsavedChanges>()
+            .add_event::<AutosaveWritten>()
+            .add_event::<SceneSaved>()
+            .add_systems(Last, (tick_autosave, autosave.run_if(autosave_due)).chain());
+    }
+}
+
+/// Autosave settings.
+///
+/// Once enabled, the scene is written every [`interval_secs`](Self::interval_secs) while it has
+/// [`UnsavedChanges`], to the next of [`backups`](Self::backups) files in
+/// [`directory`](Self::directory), going back to the first one after the last. Saving the scene
+/// restarts the interval. Autosave is off by default and can be turned on in the settings pane.
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[reflect(Resource, @SettingsType::Workspace)]
+pub struct Autosave {
+    /// Whether the scene is saved automatically.
+    pub enabled: bool,
+    /// The time between two autosaves, in seconds.
+    pub interval_secs: f32,
+    /// The number of backup files kept.
+    pub backups: u32,
+    /// The directory the backup files are written to, relative to the working directory.
+    pub directory: String,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 300.,
+            backups: 3,
+            directory: "autosave".into(),
+        }
+    }
+}
+
+impl Autosave {
+    /// The time between two autosaves.
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs_f32(self.interval_secs.max(1.))
+    }
+
+    /// The path of the backup file at `index`.
+    pub fn backup_path(&self, index: u32) -> PathBuf {
+        PathBuf::from(&self.directory).join(format!("backup_{index}.scn.ron"))
+    }
+}
+
+/// Sent after the scene was autosaved.
+#[derive(Event, BufferedEvent, Clone, Debug, PartialEq)]
+pub struct AutosaveWritten {
+    /// The backup file the scene was written to.
+    pub path: PathBuf,
+}
+
+/// The time since the last save, along with the next backup file to write.
+#[derive(Resource, Default)]
+pub(crate) struct AutosaveTimer {
+    elapsed: Duration,
+    next_backup: u32,
+}
+
+impl AutosaveTimer {
+    /// Restarts the interval.
+    pub(crate) fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+fn tick_autosave(
+    settings: Res<Autosave>,
+    mut timer: ResMut<AutosaveTimer>,
+    mut saved: EventReader<SceneSaved>,
+    time: Res<Time<Real>>,
+) {
+    // The scene was just saved, there is nothing to back up yet
+    if settings.enabled && saved.read().count() == 0 {
+        timer.elapsed += time.delta();
+    } else {
+        timer.reset();
+    }
+}
+
+fn autosave_due(
+    settings: Res<Autosave>,
+    timer: Res<AutosaveTimer>,
+    unsaved_changes: Res<UnsavedChanges>,
+) -> bool {
+    settings.enabled && unsaved_changes.0 && timer.elapsed >= settings.interval()
+}
+
+/// Writes the named root entities and their descendants, the entities shown in the scene tree,
+/// to the next backup file.
+fn autosave(world: &mut World) {
+    let settings = world.resource::<Autosave>().clone();
+    let mut timer = world.resource_mut::<AutosaveTimer>();
+    timer.reset();
+    let index = timer.next_backup % settings.backups.max(1);
+    timer.next_backup = index + 1;
+    let path = settings.backup_path(index);
+
+    let roots: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Name>, Without<ChildOf>)>()
+        .iter(world)
+        .collect();
+    let scene = subtrees_scene(world, &roots);
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let serialized = match scene.serialize(&type_registry) {
+        Ok(serialized) => serialized,
+        Err(error) => {
+            error!("Unable to serialize the scene for autosave: {error}");
+            return;
+        }
+    };
+    drop(type_registry);
+
+    let written = std::fs::create_dir_all(&settings.directory)
+        .and_then(|()| std::fs::write(&path, serialized));
+    match written {
+        Ok(()) => {
+            debug!("Autosaved the scene to {}", path.display());
+            world.send_event(AutosaveWritten { path });
+        }
+        Err(error) => error!("Unable to autosave to {}: {error}", path.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn autosave_rotates_backups_after_interval() {
+        let directory = std::env::temp_dir().join("bevy_editor_autosave_test");
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AutosavePlugin))
+            .register_type::<Name>()
+            .insert_resource(Autosave {
+                enabled: true,
+                interval_secs: 10.,
+                backups: 2,
+                directory: directory.to_string_lossy().into_owned(),
+            })
+            .insert_resource(UnsavedChanges(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(4)));
+        app.world_mut().spawn(Name::new("Level"));
+
+        let mut written = Vec::new();
+        let mut update = |app: &mut App| {
+            app.update();
+            let mut events = app.world_mut().resource_mut::<Events<AutosaveWritten>>();
+            written.extend(events.drain().map(|event| event.path));
+        };
+        // The first update has no delta, so the interval passes every three updates after it
+        for _ in 0..10 {
+            update(&mut app);
+        }
+
+        // Saving the scene restarts the interval
+        update(&mut app);
+        update(&mut app);
+        app.world_mut().send_event(SceneSaved {
+            path: "level.scn.ron".into(),
+        });
+        update(&mut app);
+
+        // Nothing is written while there are no changes to back up
+        app.world_mut().resource_mut::<UnsavedChanges>().0 = false;
+        for _ in 0..6 {
+            update(&mut app);
+        }
+
+        let settings = app.world().resource::<Autosave>();
+        assert_eq!(
+            written,
+            vec![
+                settings.backup_path(0),
+                settings.backup_path(1),
+                settings.backup_path(0),
+            ]
+        );
+        let saved = std::fs::read_to_string(settings.backup_path(0)).unwrap();
+        assert!(saved.contains("Level"));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
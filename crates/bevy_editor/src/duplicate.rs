@@ -0,0 +1,191 @@
+//! Duplicating the selected entities along with their descendants.
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use bevy_command_palette::CommandAppExt;
+use bevy_editor_core::{actions, EditorKeybindings, SelectedEntities, SelectedEntity};
+use bevy_undo::{AddedEntity, NewChange, UndoPlugin};
+
+use crate::save_prefab::subtrees_scene;
+
+/// How far copies are moved from the entities they were duplicated from, so they don't overlap.
+const DUPLICATE_OFFSET: Vec3 = Vec3::new(0.5, 0., 0.5);
+
+pub(crate) struct DuplicatePlugin;
+
+impl Plugin for DuplicatePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<UndoPlugin>() {
+            app.add_plugins(UndoPlugin);
+        }
+
+        app.register_command("Duplicate Selection", |mut commands: Commands| {
+            commands.queue(duplicate_selection);
+        })
+        .add_systems(Update, duplicate_shortcut);
+    }
+}
+
+/// The duplicate action, `Ctrl + D` by default, duplicates the selected entities.
+fn duplicate_shortcut(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+    selected_entities: Res<SelectedEntities>,
+) {
+    if !selected_entities.0.is_empty()
+        && keybindings.is_action_pressed(actions::DUPLICATE, &keyboard)
+    {
+        commands.queue(duplicate_selection);
+    }
+}
+
+/// Duplicates the selected entities and their descendants through reflection, and selects the
+/// copies.
+///
+/// Copies are placed next to the originals under the same parent. Entity references between the
+/// duplicated entities are remapped to the copies, references to other entities are kept.
+/// Undoing removes all copies at once.
+pub(crate) fn duplicate_selection(world: &mut World) {
+    let selected = world.resource::<SelectedEntities>().0.clone();
+
+    // Descendants of selected entities are duplicated along with their selected ancestor
+    let roots: Vec<Entity> = selected
+        .iter()
+        .copied()
+        .filter(|&entity| world.get_entity(entity).is_ok())
+        .filter(|&entity| {
+            let mut parent = world.get::<ChildOf>(entity);
+            while let Some(child_of) = parent {
+                if selected.contains(&child_of.parent()) {
+                    return false;
+                }
+                parent = world.get::<ChildOf>(child_of.parent());
+            }
+            true
+        })
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+
+    let scene = subtrees_scene(world, &roots);
+    let duplicated: Vec<Entity> = scene.entities.iter().map(|entity| entity.entity).collect();
+
+    // Entities outside of the duplicated hierarchies map to themselves, so references to them are
+    // kept instead of pointing to new entities
+    let mut entity_map: EntityHashMap<Entity> = world
+        .query::<Entity>()
+        .iter(world)
+        .filter(|entity| !duplicated.contains(entity))
+        .map(|entity| (entity, entity))
+        .collect();
+    if let Err(error) = scene.write_to_world(world, &mut entity_map) {
+        error!("Unable to duplicate the selection: {error}");
+        return;
+    }
+
+    let copies: Vec<Entity> = roots.iter().map(|root| entity_map[root]).collect();
+    for (&root, &copy) in roots.iter().zip(&copies) {
+        let parent = world.get::<ChildOf>(root).map(ChildOf::parent);
+        let mut copy = world.entity_mut(copy);
+        if let Some(parent) = parent {
+            copy.insert(ChildOf(parent));
+        }
+        if let Some(mut transform) = copy.get_mut::<Transform>() {
+            transform.translation += DUPLICATE_OFFSET;
+        }
+    }
+
+    // Changes sent in the same frame are undone together, and despawning a copy also despawns
+    // its descendants
+    for &entity in &copies {
+        world.send_event(NewChange::new(AddedEntity { entity }));
+    }
+
+    world.resource_mut::<SelectedEntity>().0 = copies.last().copied();
+    world.resource_mut::<SelectedEntities>().0 = copies;
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_editor_core::EditorCorePlugin;
+    use bevy_undo::UndoRedo;
+
+    use super::*;
+
+    /// A component referencing another entity.
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Follow(#[entities] Entity);
+
+    #[test]
+    fn duplicate_parent_with_child() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, EditorCorePlugin, UndoPlugin))
+            .register_type::<Name>()
+            .register_type::<Transform>()
+            .register_type::<ChildOf>()
+            .register_type::<Follow>();
+
+        let world = app.world_mut();
+        let level = world.spawn(Name::new("Level")).id();
+        let tree = world.spawn(Name::new("Tree")).id();
+        let house = world
+            .spawn((
+                Name::new("House"),
+                Transform::from_xyz(1., 0., 0.),
+                ChildOf(level),
+            ))
+            .id();
+        let door = world
+            .spawn((Name::new("Door"), Follow(house), ChildOf(house)))
+            .id();
+        world.spawn((Name::new("Sign"), Follow(tree), ChildOf(house)));
+        world.resource_mut::<SelectedEntities>().0 = vec![house, door];
+        world.resource_mut::<SelectedEntity>().0 = Some(door);
+        app.update();
+
+        duplicate_selection(app.world_mut());
+
+        let world = app.world();
+        let copies = &world.resource::<SelectedEntities>().0;
+        assert_eq!(copies.len(), 1, "The door is duplicated with the house");
+        let copy = copies[0];
+        assert_ne!(copy, house);
+        assert_eq!(world.resource::<SelectedEntity>().0, Some(copy));
+
+        assert_eq!(world.get::<Name>(copy).unwrap().as_str(), "House");
+        assert_eq!(world.get::<ChildOf>(copy).unwrap().parent(), level);
+        assert_eq!(
+            world.get::<Transform>(copy).unwrap().translation,
+            Vec3::new(1., 0., 0.) + DUPLICATE_OFFSET
+        );
+
+        let copy_children: Vec<Entity> = world.get::<Children>(copy).unwrap().to_vec();
+        assert_eq!(copy_children.len(), 2);
+        for child in copy_children {
+            assert!(child != door);
+            let follow = world.get::<Follow>(child).unwrap().0;
+            match world.get::<Name>(child).unwrap().as_str() {
+                // References inside the duplicated hierarchy point to the copies
+                "Door" => assert_eq!(follow, copy),
+                // References outside of it are kept
+                "Sign" => assert_eq!(follow, tree),
+                name => panic!("Unexpected child {name}"),
+            }
+        }
+
+        assert_eq!(world.get::<Children>(level).unwrap().len(), 2);
+
+        // Undo removes the copies
+        app.update();
+        app.update();
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert!(world.get_entity(copy).is_err());
+        assert_eq!(world.get::<Children>(level).unwrap().len(), 1);
+    }
+}
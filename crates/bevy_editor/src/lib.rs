@@ -16,20 +16,37 @@ use bevy::prelude::*;
 // Re-export Bevy for project use
 pub use bevy;
 
+use bevy_command_palette::CommandPalettePlugin;
 use bevy_context_menu::ContextMenuPlugin;
 use bevy_editor_core::EditorCorePlugin;
+use bevy_editor_settings::EditorSettingsPlugin;
 use bevy_editor_styles::StylesPlugin;
+use bevy_transform_gizmos::TransformGizmoPlugin;
 
 // Panes
 use bevy_2d_viewport::Viewport2dPanePlugin;
 use bevy_3d_viewport::Viewport3dPanePlugin;
 use bevy_asset_browser::AssetBrowserPanePlugin;
 
-use crate::load_gltf::LoadGltfPlugin;
+use crate::{
+    assign_material::AssignMaterialPlugin, autosave::AutosavePlugin, duplicate::DuplicatePlugin,
+    load_gltf::LoadGltfPlugin, save_prefab::SavePrefabPlugin, session::SessionPlugin,
+    unsaved_changes::UnsavedChangesPlugin,
+};
 
+pub use autosave::{Autosave, AutosaveWritten};
+pub use session::EditorSession;
+pub use unsaved_changes::{SceneSaved, UnsavedChanges};
+
+mod assign_material;
+mod autosave;
+mod duplicate;
 mod load_gltf;
 pub mod project;
+mod save_prefab;
+mod session;
 mod ui;
+mod unsaved_changes;
 
 /// The plugin that handle the bare minimum to run the application
 pub struct RuntimePlugin;
@@ -52,13 +69,23 @@ impl Plugin for EditorPlugin {
             .add_plugins((
                 EditorCorePlugin,
                 ContextMenuPlugin,
+                CommandPalettePlugin,
                 StylesPlugin,
+                TransformGizmoPlugin,
                 Viewport2dPanePlugin,
                 Viewport3dPanePlugin,
                 ui::EditorUIPlugin,
                 AssetBrowserPanePlugin,
                 LoadGltfPlugin,
+                SavePrefabPlugin,
+                DuplicatePlugin,
+                SessionPlugin,
+                AutosavePlugin,
+                UnsavedChangesPlugin,
             ))
+            .add_plugins(AssignMaterialPlugin)
+            // Loads the settings of the plugins above and saves the workspace settings on change
+            .add_plugins(EditorSettingsPlugin)
             .add_systems(Startup, dummy_setup);
     }
 }
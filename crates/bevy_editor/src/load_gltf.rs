@@ -4,6 +4,8 @@ use bevy::{
     prelude::*,
     tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
 };
+use bevy_command_palette::CommandAppExt;
+use bevy_editor_core::{actions, EditorKeybindings};
 use rfd::{AsyncFileDialog, FileHandle};
 
 pub(crate) struct LoadGltfPlugin;
@@ -11,6 +13,7 @@ pub(crate) struct LoadGltfPlugin;
 impl Plugin for LoadGltfPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GltfFilepickerTask>()
+            .register_command("Load glTF Scene", open_gltf_picker)
             .add_systems(Update, (pick_gltf, poll_pick_gltf, file_dropped));
     }
 }
@@ -32,23 +35,29 @@ fn file_dropped(
 pub(crate) struct GltfFilepickerTask(Option<Task<Option<FileHandle>>>);
 
 pub(crate) fn pick_gltf(
-    mut file_picker_task: ResMut<GltfFilepickerTask>,
+    mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
 ) {
+    if keybindings.is_action_pressed(actions::LOAD_GLTF, &keyboard_input) {
+        commands.run_system_cached(open_gltf_picker);
+    }
+}
+
+/// Opens the file picker to load a glTF scene, unless it is already open.
+fn open_gltf_picker(mut file_picker_task: ResMut<GltfFilepickerTask>) {
     if file_picker_task.0.is_some() {
         return;
     }
 
-    if keyboard_input.pressed(KeyCode::ControlLeft) && keyboard_input.just_pressed(KeyCode::KeyL) {
-        file_picker_task.0 = Some(
-            AsyncComputeTaskPool::get().spawn(
-                AsyncFileDialog::new()
-                    .set_title("Load GLTF file")
-                    .add_filter("gltf/glb", &["gltf", "glb"])
-                    .pick_file(),
-            ),
-        );
-    }
+    file_picker_task.0 = Some(
+        AsyncComputeTaskPool::get().spawn(
+            AsyncFileDialog::new()
+                .set_title("Load GLTF file")
+                .add_filter("gltf/glb", &["gltf", "glb"])
+                .pick_file(),
+        ),
+    );
 }
 
 fn poll_pick_gltf(
@@ -0,0 +1,215 @@
+//! Saving the selected entity and its descendants as a standalone prefab scene.
+
+use std::path::Path;
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use bevy_command_palette::CommandAppExt;
+use bevy_editor_core::{actions, EditorKeybindings, SelectedEntity};
+use rfd::{AsyncFileDialog, FileHandle};
+
+pub(crate) struct SavePrefabPlugin;
+
+impl Plugin for SavePrefabPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PrefabFilepickerTask>()
+            .register_command("Save Selection as Prefab", open_prefab_picker)
+            .add_systems(Update, (pick_prefab_path, poll_pick_prefab_path));
+    }
+}
+
+/// The entity being saved, along with the task picking the file to save it to.
+#[derive(Resource, Default)]
+pub(crate) struct PrefabFilepickerTask(Option<(Entity, Task<Option<FileHandle>>)>);
+
+/// The save prefab action, `Ctrl + Shift + S` by default, saves the selected entity and its descendants as a prefab.
+pub(crate) fn pick_prefab_path(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    keybindings: Res<EditorKeybindings>,
+) {
+    if keybindings.is_action_pressed(actions::SAVE_PREFAB, &keyboard_input) {
+        commands.run_system_cached(open_prefab_picker);
+    }
+}
+
+/// Opens the file picker to save the selected entity as a prefab, unless it is already open.
+fn open_prefab_picker(
+    mut file_picker_task: ResMut<PrefabFilepickerTask>,
+    selected_entity: Res<SelectedEntity>,
+) {
+    if file_picker_task.0.is_some() {
+        return;
+    }
+    let Some(entity) = selected_entity.0 else {
+        return;
+    };
+
+    file_picker_task.0 = Some((
+        entity,
+        AsyncComputeTaskPool::get().spawn(
+            AsyncFileDialog::new()
+                .set_title("Save selection as prefab")
+                .set_file_name("prefab.scn.ron")
+                .add_filter("scene", &["ron"])
+                .save_file(),
+        ),
+    ));
+}
+
+fn poll_pick_prefab_path(
+    mut file_picker_task: ResMut<PrefabFilepickerTask>,
+    mut commands: Commands,
+) {
+    let Some((_, task)) = &mut file_picker_task.0 else {
+        return;
+    };
+
+    let Some(result) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    let (entity, _) = file_picker_task.0.take().unwrap();
+
+    if let Some(file) = result {
+        let path = file.path().to_owned();
+        commands.queue(move |world: &mut World| save_prefab(world, entity, &path));
+    }
+}
+
+fn save_prefab(world: &mut World, root: Entity, path: &Path) {
+    if world.get_entity(root).is_err() {
+        warn!("Entity {root} no longer exists, the prefab was not saved");
+        return;
+    }
+
+    let scene = subtree_scene(world, root);
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let serialized = match scene.serialize(&type_registry) {
+        Ok(serialized) => serialized,
+        Err(error) => {
+            error!("Unable to serialize prefab: {error}");
+            return;
+        }
+    };
+
+    match std::fs::write(path, serialized) {
+        Ok(()) => info!("Saved prefab to {}", path.display()),
+        Err(error) => error!("Unable to write prefab to {}: {error}", path.display()),
+    }
+}
+
+/// Builds a scene containing `root` and all of its descendants.
+///
+/// The root is detached from its parent so the scene can be spawned on its own, while the
+/// hierarchy inside the subtree is kept and remapped when the scene is spawned.
+pub(crate) fn subtree_scene(world: &World, root: Entity) -> DynamicScene {
+    subtrees_scene(world, &[root])
+}
+
+/// Builds a scene containing each of `roots` and all of their descendants, with the roots
+/// detached from their parents like in [`subtree_scene`].
+pub(crate) fn subtrees_scene(world: &World, roots: &[Entity]) -> DynamicScene {
+    let mut entities = roots.to_vec();
+    let mut index = 0;
+    while let Some(&entity) = entities.get(index) {
+        if let Some(children) = world.get::<Children>(entity) {
+            entities.extend(children.iter());
+        }
+        index += 1;
+    }
+
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        // Children is rebuilt from ChildOf when the scene is spawned
+        .deny_component::<Children>()
+        .extract_entities(entities.into_iter())
+        .build();
+
+    for root in scene
+        .entities
+        .iter_mut()
+        .filter(|entity| roots.contains(&entity.entity))
+    {
+        root.components
+            .retain(|component| !component.represents::<ChildOf>());
+    }
+
+    scene
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::entity::EntityHashMap, scene::serde::SceneDeserializer};
+    use serde::de::DeserializeSeed;
+
+    use super::*;
+
+    #[test]
+    fn subtree_scene_reloads_standalone() {
+        let mut app = App::new();
+        app.register_type::<Name>()
+            .register_type::<Transform>()
+            .register_type::<ChildOf>();
+
+        let world = app.world_mut();
+        let level = world.spawn(Name::new("Level")).id();
+        let house = world
+            .spawn((
+                Name::new("House"),
+                Transform::from_xyz(1., 2., 3.),
+                ChildOf(level),
+            ))
+            .id();
+        world.spawn((Name::new("Door"), ChildOf(house)));
+        world.spawn((Name::new("Window"), ChildOf(house)));
+        world.spawn((Name::new("Tree"), ChildOf(level)));
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let serialized = subtree_scene(world, house)
+            .serialize(&type_registry.read())
+            .unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &type_registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        let mut prefab_world = World::new();
+        prefab_world.insert_resource(type_registry);
+        scene
+            .write_to_world(&mut prefab_world, &mut EntityHashMap::default())
+            .unwrap();
+
+        let mut names = prefab_world.query::<(Entity, &Name, Option<&ChildOf>)>();
+        let mut loaded: Vec<_> = names
+            .iter(&prefab_world)
+            .map(|(entity, name, child_of)| {
+                (
+                    entity,
+                    name.as_str().to_owned(),
+                    child_of.map(ChildOf::parent),
+                )
+            })
+            .collect();
+        loaded.sort_by(|a, b| a.1.cmp(&b.1));
+
+        let loaded_names: Vec<&str> = loaded.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(loaded_names, vec!["Door", "House", "Window"]);
+
+        let (loaded_house, _, house_parent) = &loaded[1];
+        assert!(house_parent.is_none());
+        assert_eq!(
+            prefab_world
+                .get::<Transform>(*loaded_house)
+                .unwrap()
+                .translation,
+            Vec3::new(1., 2., 3.)
+        );
+        for (_, _, parent) in [&loaded[0], &loaded[2]] {
+            assert_eq!(*parent, Some(*loaded_house));
+        }
+    }
+}
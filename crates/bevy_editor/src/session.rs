@@ -0,0 +1,226 @@
+//! Keeping the 3D viewport camera and the pane layout across editor sessions.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_editor_cam::prelude::EditorCam;
+use bevy_pane_layout::{
+    layout::{load_layout, save_layout},
+    PaneLayoutSet, RootPaneLayoutNode,
+};
+use serde::{Deserialize, Serialize};
+
+/// The version of the session file format, files written by other versions are ignored.
+const SESSION_VERSION: u32 = 1;
+
+pub(crate) struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorSession>()
+            .init_resource::<PendingCameraPose>()
+            .add_systems(Startup, restore_session.after(PaneLayoutSet))
+            .add_systems(Update, restore_camera_pose)
+            .add_systems(Last, save_session.run_if(on_event::<AppExit>));
+    }
+}
+
+/// Where the editor state is written on exit and read back on the next launch.
+#[derive(Resource, Clone, Debug)]
+pub struct EditorSession {
+    /// The path of the session file, relative to the working directory.
+    pub path: PathBuf,
+}
+
+impl Default for EditorSession {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("editor_session.ron"),
+        }
+    }
+}
+
+/// The editor state saved in the session file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct SessionState {
+    version: u32,
+    /// The pose of the first 3D viewport camera.
+    camera: Option<CameraPose>,
+    /// The pane layout, as produced by [`save_layout`].
+    layout: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+struct CameraPose {
+    translation: [f32; 3],
+    rotation: [f32; 4],
+}
+
+impl From<&Transform> for CameraPose {
+    fn from(transform: &Transform) -> Self {
+        Self {
+            translation: transform.translation.to_array(),
+            rotation: transform.rotation.to_array(),
+        }
+    }
+}
+
+impl CameraPose {
+    fn apply(&self, transform: &mut Transform) {
+        transform.translation = Vec3::from_array(self.translation);
+        transform.rotation = Quat::from_array(self.rotation).normalize();
+    }
+}
+
+/// The camera pose restored on the first 3D viewport camera once it is spawned.
+#[derive(Resource, Default)]
+struct PendingCameraPose(Option<CameraPose>);
+
+impl SessionState {
+    /// Reads the session file at `path`.
+    /// Returns `None`, with a warning if the file exists, when it can't be used.
+    fn read(path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        match ron::from_str::<SessionState>(&contents) {
+            Ok(state) if state.version == SESSION_VERSION => Some(state),
+            Ok(state) => {
+                warn!(
+                    "Ignoring session file {} written by version {}, expected version {SESSION_VERSION}",
+                    path.display(),
+                    state.version
+                );
+                None
+            }
+            Err(error) => {
+                warn!(
+                    "Ignoring malformed session file {}: {error}",
+                    path.display()
+                );
+                None
+            }
+        }
+    }
+}
+
+fn restore_session(
+    mut commands: Commands,
+    session: Res<EditorSession>,
+    mut pending_pose: ResMut<PendingCameraPose>,
+) {
+    let Some(state) = SessionState::read(&session.path) else {
+        return;
+    };
+
+    pending_pose.0 = state.camera;
+    if let Some(layout) = state.layout {
+        if let Err(error) = load_layout(&mut commands, &layout) {
+            warn!("Ignoring the saved pane layout: {error}");
+        }
+    }
+}
+
+fn restore_camera_pose(
+    mut pending_pose: ResMut<PendingCameraPose>,
+    mut cameras: Query<&mut Transform, (Added<EditorCam>, With<Camera3d>)>,
+) {
+    if pending_pose.0.is_none() {
+        return;
+    }
+    if let Some(mut transform) = cameras.iter_mut().next() {
+        pending_pose.0.take().unwrap().apply(&mut transform);
+    }
+}
+
+fn save_session(world: &mut World) {
+    let camera = world
+        .query_filtered::<&Transform, (With<EditorCam>, With<Camera3d>)>()
+        .iter(world)
+        .next()
+        .map(CameraPose::from);
+    let has_layout = world
+        .query_filtered::<(), With<RootPaneLayoutNode>>()
+        .single(world)
+        .is_ok();
+    let state = SessionState {
+        version: SESSION_VERSION,
+        camera,
+        layout: has_layout.then(|| save_layout(world)),
+    };
+
+    let path = &world.resource::<EditorSession>().path;
+    let serialized = ron::ser::to_string_pretty(&state, ron::ser::PrettyConfig::default())
+        .expect("Session state should always be serializable");
+    if let Err(error) = std::fs::write(path, serialized) {
+        error!("Unable to write session to {}: {error}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_app(path: PathBuf) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SessionPlugin))
+            .insert_resource(EditorSession { path });
+        app
+    }
+
+    fn spawn_camera(app: &mut App) -> Entity {
+        app.world_mut()
+            .spawn((
+                Camera3d::default(),
+                EditorCam::default(),
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn camera_pose_is_restored_on_relaunch() {
+        let path = std::env::temp_dir().join("bevy_editor_session_restore.ron");
+        let pose = Transform::from_xyz(1., 2., 3.).looking_at(Vec3::ZERO, Vec3::Y);
+
+        let mut app = session_app(path.clone());
+        app.update();
+        let camera = spawn_camera(&mut app);
+        *app.world_mut().get_mut::<Transform>(camera).unwrap() = pose;
+        app.world_mut().send_event(AppExit::Success);
+        app.update();
+
+        let mut app = session_app(path.clone());
+        let camera = spawn_camera(&mut app);
+        app.update();
+        let restored = *app.world().get::<Transform>(camera).unwrap();
+        assert!(restored.translation.abs_diff_eq(pose.translation, 1e-5));
+        assert!(restored.rotation.abs_diff_eq(pose.rotation, 1e-5));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn corrupt_or_outdated_session_is_ignored() {
+        let path = std::env::temp_dir().join("bevy_editor_session_corrupt.ron");
+
+        std::fs::write(&path, "not a session").unwrap();
+        assert_eq!(SessionState::read(&path), None);
+
+        let outdated = SessionState {
+            version: SESSION_VERSION + 1,
+            camera: Some(CameraPose::from(&Transform::from_xyz(1., 2., 3.))),
+            layout: None,
+        };
+        std::fs::write(&path, ron::to_string(&outdated).unwrap()).unwrap();
+        assert_eq!(SessionState::read(&path), None);
+
+        let mut app = session_app(path.clone());
+        let camera = spawn_camera(&mut app);
+        app.update();
+        assert_eq!(
+            *app.world().get::<Transform>(camera).unwrap(),
+            Transform::default()
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
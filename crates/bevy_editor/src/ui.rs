@@ -2,8 +2,10 @@ use bevy::prelude::*;
 
 use bevy_editor_styles::Theme;
 use bevy_footer_bar::{FooterBarNode, FooterBarPlugin, FooterBarSet};
+use bevy_history_pane::HistoryPanePlugin;
 use bevy_menu_bar::{MenuBarNode, MenuBarPlugin, MenuBarSet};
 use bevy_pane_layout::{PaneLayoutPlugin, PaneLayoutSet, RootPaneLayoutNode};
+use bevy_preferences::PreferencesPanePlugin;
 use bevy_properties_pane::PropertiesPanePlugin;
 use bevy_scene_tree::SceneTreePlugin;
 
@@ -23,6 +25,8 @@ impl Plugin for EditorUIPlugin {
                 FooterBarPlugin,
                 SceneTreePlugin,
                 PropertiesPanePlugin,
+                HistoryPanePlugin,
+                PreferencesPanePlugin,
             ));
     }
 }
@@ -0,0 +1,159 @@
+//! Showing whether the scene has changes that weren't saved yet.
+
+use std::path::PathBuf;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_editor_styles::Theme;
+use bevy_footer_bar::{FooterBarSet, FooterBarStatusNode};
+use bevy_undo::{NewChange, UndoRedo, UndoSet};
+
+/// Appended to the window title while there are unsaved changes.
+const TITLE_SUFFIX: &str = " *";
+
+pub(crate) struct UnsavedChangesPlugin;
+
+impl Plugin for UnsavedChangesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnsavedChanges>()
+            .add_event::<SceneSaved>()
+            .add_systems(Startup, spawn_unsaved_changes_indicator.after(FooterBarSet))
+            .add_systems(
+                PostUpdate,
+                (
+                    // The undo systems clear the change events
+                    track_unsaved_changes.before(UndoSet::Global),
+                    (update_unsaved_changes_indicator, update_window_title)
+                        .run_if(resource_changed::<UnsavedChanges>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether the scene changed since it was last saved.
+///
+/// Set by every change recorded for undo, and by undoing and redoing, and cleared by
+/// [`SceneSaved`]. Shown in the footer bar and the window title.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsavedChanges(pub bool);
+
+/// Sent after the whole scene was saved by the user, clearing [`UnsavedChanges`].
+/// Exporting a selection as a prefab does not count as saving the scene.
+#[derive(Event, BufferedEvent, Clone, Debug, PartialEq)]
+pub struct SceneSaved {
+    /// The file the scene was saved to.
+    pub path: PathBuf,
+}
+
+/// The footer bar text shown while there are unsaved changes.
+#[derive(Component)]
+struct UnsavedChangesIndicator;
+
+fn spawn_unsaved_changes_indicator(
+    mut commands: Commands,
+    status: Query<Entity, With<FooterBarStatusNode>>,
+    theme: Res<Theme>,
+) {
+    let Ok(status) = status.single() else {
+        return;
+    };
+    commands.spawn((
+        UnsavedChangesIndicator,
+        Text::default(),
+        TextFont {
+            font: theme.text.font.clone(),
+            font_size: 10.,
+            ..default()
+        },
+        TextColor(theme.text.text_color),
+        ChildOf(status),
+    ));
+}
+
+fn track_unsaved_changes(
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+    mut new_changes: EventReader<NewChange>,
+    mut undo_redo: EventReader<UndoRedo>,
+    mut saved: EventReader<SceneSaved>,
+) {
+    let changed = new_changes.read().count() + undo_redo.read().count() > 0;
+    // A save in the same frame comes after the changes that were saved
+    let saved = saved.read().count() > 0;
+    if changed || saved {
+        unsaved_changes.set_if_neq(UnsavedChanges(!saved));
+    }
+}
+
+fn update_unsaved_changes_indicator(
+    unsaved_changes: Res<UnsavedChanges>,
+    mut indicators: Query<&mut Text, With<UnsavedChangesIndicator>>,
+) {
+    for mut text in &mut indicators {
+        text.0 = if unsaved_changes.0 {
+            "Unsaved changes".into()
+        } else {
+            String::new()
+        };
+    }
+}
+
+fn update_window_title(
+    unsaved_changes: Res<UnsavedChanges>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    for mut window in &mut windows {
+        let title = window
+            .title
+            .strip_suffix(TITLE_SUFFIX)
+            .unwrap_or(&window.title)
+            .to_string();
+        window.title = if unsaved_changes.0 {
+            title + TITLE_SUFFIX
+        } else {
+            title
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_undo::{RenamedEntity, UndoPlugin};
+
+    use super::*;
+
+    #[test]
+    fn changes_are_unsaved_until_saved() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .add_plugins((UndoPlugin, UnsavedChangesPlugin));
+        app.world_mut().spawn((
+            Window {
+                title: "Bevy Editor".into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        app.update();
+        assert!(!app.world().resource::<UnsavedChanges>().0);
+
+        let entity = app.world_mut().spawn(Name::new("Door")).id();
+        app.world_mut().send_event(NewChange::new(RenamedEntity {
+            entity,
+            old_name: None,
+            new_name: Some(Name::new("Door")),
+        }));
+        app.update();
+        assert!(app.world().resource::<UnsavedChanges>().0);
+        let mut windows = app.world_mut().query::<&Window>();
+        assert_eq!(windows.single(app.world()).unwrap().title, "Bevy Editor *");
+
+        app.world_mut().send_event(SceneSaved {
+            path: "level.scn.ron".into(),
+        });
+        app.update();
+        assert!(!app.world().resource::<UnsavedChanges>().0);
+        assert_eq!(windows.single(app.world()).unwrap().title, "Bevy Editor");
+    }
+}
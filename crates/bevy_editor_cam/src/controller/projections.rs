@@ -30,6 +30,10 @@ pub struct PerspectiveSettings {
     /// clipped. Some parts of the object may protrude toward the camera, which is what necessitates
     /// this.
     pub near_clip_multiplier: f32,
+    /// The distance of the far clipping plane. Anything farther away from the camera is not
+    /// rendered, so this should be increased when working on very large scenes. Bevy defaults to
+    /// `1000.0`.
+    pub far_clip: f32,
 }
 
 impl Default for PerspectiveSettings {
@@ -37,6 +41,7 @@ impl Default for PerspectiveSettings {
         Self {
             near_clip_limits: 1e-9..0.1,
             near_clip_multiplier: 0.05,
+            far_clip: 1000.0,
         }
     }
 }
@@ -51,6 +56,7 @@ pub fn update_perspective(mut cameras: Query<(&EditorCam, &mut Projection)>) {
         let multiplier = editor_cam.perspective.near_clip_multiplier;
         perspective.near = (editor_cam.last_anchor_depth.abs() as f32 * multiplier)
             .clamp(limits.start, limits.end);
+        perspective.far = editor_cam.perspective.far_clip;
     }
 }
 
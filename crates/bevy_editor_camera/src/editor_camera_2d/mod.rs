@@ -8,11 +8,13 @@
 use std::ops::RangeInclusive;
 
 use bevy::{
+    ecs::entity::EntityHashSet,
     input::mouse::AccumulatedMouseScroll,
     math::bounding::{Aabb2d, BoundingVolume},
     prelude::*,
     render::camera::CameraProjection,
-    window::PrimaryWindow,
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::cursor::CursorIcon,
 };
 
 /// Plugin which adds necessary components and systems for 2d editor cameras to work.
@@ -39,6 +41,10 @@ pub struct EditorCamera2d {
     ///
     /// When one of these mouse buttons is pressed the camera will be panned.
     pub pan_mouse_buttons: Vec<MouseButton>,
+    /// Keys used for panning with the left mouse button, like a hand tool.
+    ///
+    /// When one of these keys is held, dragging with the left mouse button pans the camera.
+    pub pan_keys: Vec<KeyCode>,
     /// The bound which the camera will be clamped to when panning
     /// and zooming. Use infinity values to disable any clamping.
     pub bound: Rect,
@@ -48,6 +54,8 @@ pub struct EditorCamera2d {
     pub scale_range: RangeInclusive<f32>,
     /// The sensitivity of the mouse wheel input when zooming.
     pub zoom_sensitivity: f32,
+    /// When true, scrolling up zooms out instead of in.
+    pub invert_zoom: bool,
     /// When true, zooming the camera will center on the mouse cursor
     ///
     /// When false, the camera will stay in place, zooming towards the
@@ -55,29 +63,82 @@ pub struct EditorCamera2d {
     pub zoom_to_cursor: bool,
     /// Overrides the viewport. Useful to map the controls correctly
     /// when the camera is rendering to an image.
+    ///
+    /// In logical UI coordinates, see [`viewport_override`](Self::viewport_override()).
     pub viewport_override: Option<Rect>,
 }
 
 impl EditorCamera2d {
+    /// The rect the camera is shown in when it renders to an image, like the content node of a
+    /// viewport pane, in logical UI coordinates with y pointing down.
+    ///
+    /// Viewports keep it up to date as their pane moves and resizes, so overlays drawn over the
+    /// viewport can use this rather than deriving the rect from the UI nodes themselves.
+    pub fn viewport_override(&self) -> Option<Rect> {
+        self.viewport_override
+    }
+
     fn aabb(&self) -> Aabb2d {
         Aabb2d {
             min: self.bound.min,
             max: self.bound.max,
         }
     }
+
+    /// Converts a position in the [`viewport_override`](Self::viewport_override) space, with y
+    /// pointing down, to the world position shown there by the camera at `transform`.
+    ///
+    /// Returns `None` if there is no viewport override or the viewport is empty.
+    pub fn viewport_to_world(
+        &self,
+        transform: &Transform,
+        projection: &OrthographicProjection,
+        viewport: Vec2,
+    ) -> Option<Vec2> {
+        let rect = self.viewport_override?;
+        if rect.size().cmple(Vec2::ZERO).any() {
+            return None;
+        }
+
+        let uv = (viewport - rect.min) / rect.size();
+        let uv = Vec2::new(uv.x, 1. - uv.y);
+        Some(transform.translation.truncate() + projection.area.min + uv * projection.area.size())
+    }
+
+    /// Converts a world position to where the camera at `transform` shows it in the
+    /// [`viewport_override`](Self::viewport_override) space, with y pointing down.
+    ///
+    /// Returns `None` if there is no viewport override or the projection area is empty.
+    pub fn world_to_viewport(
+        &self,
+        transform: &Transform,
+        projection: &OrthographicProjection,
+        world: Vec2,
+    ) -> Option<Vec2> {
+        let rect = self.viewport_override?;
+        if projection.area.size().cmple(Vec2::ZERO).any() {
+            return None;
+        }
+
+        let uv = (world - transform.translation.truncate() - projection.area.min)
+            / projection.area.size();
+        Some(rect.min + Vec2::new(uv.x, 1. - uv.y) * rect.size())
+    }
 }
 
 impl Default for EditorCamera2d {
     fn default() -> Self {
         Self {
             enabled: true,
-            pan_mouse_buttons: vec![MouseButton::Right],
+            pan_mouse_buttons: vec![MouseButton::Right, MouseButton::Middle],
+            pan_keys: vec![KeyCode::Space],
             bound: Rect {
                 min: Vec2::ONE * f32::NEG_INFINITY,
                 max: Vec2::ONE * f32::INFINITY,
             },
             scale_range: f32::NEG_INFINITY..=f32::INFINITY,
             zoom_sensitivity: 0.1,
+            invert_zoom: false,
             zoom_to_cursor: true,
             viewport_override: None,
         }
@@ -175,8 +236,13 @@ fn camera_zoom(
             .viewport_override
             .unwrap_or(Rect::from_corners(Vec2::ZERO, viewport_size));
 
+        let scroll = if e_camera.invert_zoom {
+            -mouse_wheel.delta.y
+        } else {
+            mouse_wheel.delta.y
+        };
         let old_scale = projection.scale;
-        projection.scale *= 1. - mouse_wheel.delta.y * e_camera.zoom_sensitivity;
+        projection.scale *= 1. - scroll * e_camera.zoom_sensitivity;
 
         constrain_proj_scale(
             projection,
@@ -223,16 +289,29 @@ fn camera_zoom(
     }
 }
 
+/// Pans the enabled cameras while a pan button, or a pan key and the left mouse button, is held.
+///
+/// A pan keeps going when the camera gets disabled, e.g. when the pointer leaves its viewport,
+/// until the buttons are released. The cursor shows a grab icon while panning.
 fn camera_pan(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
+    mut commands: Commands,
+    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
-    mut query: Query<(&EditorCamera2d, &Camera, &Projection, &mut Transform)>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut query: Query<(
+        Entity,
+        &EditorCamera2d,
+        &Camera,
+        &Projection,
+        &mut Transform,
+    )>,
     mut prev_mouse_pos: Local<Option<Vec2>>,
+    mut panning: Local<EntityHashSet>,
 ) {
     // See https://github.com/johanhelsing/bevy_pancam/blob/main/src/lib.rs#L279
     // for why we are using the mouse position instead of the mouse delta (from
     // the MouseMotion event).
-    let Ok(window) = primary_window.single() else {
+    let Ok((window_entity, window)) = primary_window.single() else {
         // Log an error message once here?
         return;
     };
@@ -244,30 +323,33 @@ fn camera_pan(
     };
     // This is the pixels the mouse moved since the last frame in "window space".
     let mouse_delta_pixels = mouse_pos - prev_mouse_pos.unwrap_or(mouse_pos);
-
-    for (e_camera, camera, projection, mut transform) in query.iter_mut() {
-        if !e_camera.enabled {
+    let was_panning = !panning.is_empty();
+
+    let dragging =
+        |btn: MouseButton| mouse_buttons.pressed(btn) && !mouse_buttons.just_pressed(btn);
+    for (entity, e_camera, camera, projection, mut transform) in query.iter_mut() {
+        let pan_held = e_camera.pan_mouse_buttons.iter().any(|btn| dragging(*btn))
+            || (keyboard.any_pressed(e_camera.pan_keys.iter().copied())
+                && dragging(MouseButton::Left));
+        if !pan_held {
+            panning.remove(&entity);
             continue;
         }
+        if !e_camera.enabled && !panning.contains(&entity) {
+            continue;
+        }
+        panning.insert(entity);
 
         let Projection::Orthographic(projection) = projection else {
             panic!("EditorCamera2d requires an Orthographic projection");
         };
 
+        // Because the `mouse_delta_pixels` is in "window space" we need to convert
+        // it to world space by multiplying it with the ratio of the projection area
+        // and the viewport size.
         let projection_area_size = projection.area.size();
-        let mouse_delta = if !e_camera
-            .pan_mouse_buttons
-            .iter()
-            .any(|btn| mouse_buttons.pressed(*btn) && !mouse_buttons.just_pressed(*btn))
-        {
-            Vec2::ZERO
-        } else {
-            // Because the `mouse_delta_pixels` is in "window space" we need to convert
-            // it to world space by multiplying it with the ratio of the projection area
-            // and the viewport size.
-            let viewport_size = camera.logical_viewport_size().unwrap_or(window_size);
-            mouse_delta_pixels * projection_area_size / viewport_size
-        };
+        let viewport_size = camera.logical_viewport_size().unwrap_or(window_size);
+        let mouse_delta = mouse_delta_pixels * projection_area_size / viewport_size;
 
         if mouse_delta == Vec2::ZERO {
             continue;
@@ -279,5 +361,196 @@ fn camera_pan(
                 .extend(transform.translation.z);
     }
 
+    if panning.is_empty() == was_panning {
+        let icon = if panning.is_empty() {
+            SystemCursorIcon::Default
+        } else {
+            SystemCursorIcon::Grabbing
+        };
+        commands
+            .entity(window_entity)
+            .insert(CursorIcon::System(icon));
+    }
+
     *prev_mouse_pos = Some(mouse_pos);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projection(scale: f32) -> OrthographicProjection {
+        let mut projection = OrthographicProjection {
+            scale,
+            ..OrthographicProjection::default_2d()
+        };
+        projection.update(200., 100.);
+        projection
+    }
+
+    #[test]
+    fn viewport_and_world_positions_round_trip() {
+        let camera = EditorCamera2d {
+            viewport_override: Some(Rect::from_corners(
+                Vec2::new(50., 20.),
+                Vec2::new(250., 120.),
+            )),
+            ..default()
+        };
+        let transform = Transform::from_xyz(10., 5., 0.);
+
+        let projection = projection(1.);
+        // The top left corner of the viewport
+        assert_eq!(
+            camera.viewport_to_world(&transform, &projection, Vec2::new(50., 20.)),
+            Some(Vec2::new(-90., 55.))
+        );
+        // The center of the viewport shows the camera position
+        assert_eq!(
+            camera.world_to_viewport(&transform, &projection, Vec2::new(10., 5.)),
+            Some(Vec2::new(150., 70.))
+        );
+
+        let zoomed_out = projection(2.5);
+        for point in [Vec2::ZERO, Vec2::new(-300., 42.), Vec2::new(12.5, -80.)] {
+            for projection in [&projection, &zoomed_out] {
+                let viewport = camera
+                    .world_to_viewport(&transform, projection, point)
+                    .unwrap();
+                let world = camera
+                    .viewport_to_world(&transform, projection, viewport)
+                    .unwrap();
+                assert!(world.abs_diff_eq(point, 1e-3));
+            }
+        }
+    }
+
+    #[test]
+    fn no_conversions_without_viewport_override() {
+        let camera = EditorCamera2d::default();
+        let transform = Transform::default();
+        let projection = projection(1.);
+
+        assert_eq!(
+            camera.viewport_to_world(&transform, &projection, Vec2::ZERO),
+            None
+        );
+        assert_eq!(
+            camera.world_to_viewport(&transform, &projection, Vec2::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn space_drag_pans_by_world_delta() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_systems(Update, camera_pan);
+
+        let mut window = Window::default();
+        window.resolution.set(200., 100.);
+        window.set_cursor_position(Some(Vec2::new(100., 50.)));
+        let window = app.world_mut().spawn((window, PrimaryWindow)).id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                EditorCamera2d::default(),
+                Camera::default(),
+                Projection::Orthographic(projection(2.)),
+                Transform::default(),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+
+        let mut drag_to = |app: &mut App, position: Vec2| {
+            app.world_mut()
+                .resource_mut::<ButtonInput<MouseButton>>()
+                .clear();
+            app.world_mut()
+                .get_mut::<Window>(window)
+                .unwrap()
+                .set_cursor_position(Some(position));
+            app.update();
+        };
+        drag_to(&mut app, Vec2::new(110., 55.));
+
+        // One pixel is two world units at this zoom, the camera moves against the drag
+        let translation = app.world().get::<Transform>(camera).unwrap().translation;
+        assert_eq!(translation.truncate(), Vec2::new(-20., 10.));
+        assert!(matches!(
+            app.world().get::<CursorIcon>(window),
+            Some(CursorIcon::System(SystemCursorIcon::Grabbing))
+        ));
+
+        // Leaving the viewport doesn't interrupt the pan
+        app.world_mut()
+            .get_mut::<EditorCamera2d>(camera)
+            .unwrap()
+            .enabled = false;
+        drag_to(&mut app, Vec2::new(115., 55.));
+        let translation = app.world().get::<Transform>(camera).unwrap().translation;
+        assert_eq!(translation.truncate(), Vec2::new(-30., 10.));
+
+        // Releasing stops it
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        drag_to(&mut app, Vec2::new(150., 55.));
+        let translation = app.world().get::<Transform>(camera).unwrap().translation;
+        assert_eq!(translation.truncate(), Vec2::new(-30., 10.));
+        assert!(matches!(
+            app.world().get::<CursorIcon>(window),
+            Some(CursorIcon::System(SystemCursorIcon::Default))
+        ));
+    }
+
+    #[test]
+    fn invert_zoom_flips_scroll_direction() {
+        let zoom_with = |invert_zoom: bool| {
+            let mut app = App::new();
+            app.add_plugins(MinimalPlugins)
+                .init_resource::<AccumulatedMouseScroll>()
+                .add_systems(Update, camera_zoom);
+
+            let mut window = Window::default();
+            window.resolution.set(200., 100.);
+            app.world_mut().spawn((window, PrimaryWindow));
+            let camera = app
+                .world_mut()
+                .spawn((
+                    EditorCamera2d {
+                        invert_zoom,
+                        ..default()
+                    },
+                    Camera::default(),
+                    Projection::Orthographic(projection(1.)),
+                    Transform::default(),
+                ))
+                .id();
+
+            app.world_mut()
+                .resource_mut::<AccumulatedMouseScroll>()
+                .delta = Vec2::new(0., 1.);
+            app.update();
+
+            let Some(Projection::Orthographic(projection)) = app.world().get::<Projection>(camera)
+            else {
+                panic!("EditorCamera2d requires an Orthographic projection");
+            };
+            projection.scale
+        };
+
+        // Scrolling up zooms in by default, and out when inverted
+        assert!(zoom_with(false) < 1.);
+        assert!(zoom_with(true) > 1.);
+    }
+}
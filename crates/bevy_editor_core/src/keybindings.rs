@@ -0,0 +1,202 @@
+//! Remappable keyboard shortcuts for editor actions.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_editor_settings::SettingsType;
+
+/// The names of the editor actions that have a default keybinding.
+pub mod actions {
+    /// Opens the file picker to load a glTF scene.
+    pub const LOAD_GLTF: &str = "load_gltf";
+    /// Saves the selected entity and its descendants as a prefab.
+    pub const SAVE_PREFAB: &str = "save_prefab";
+    /// Drops the selected entity onto the surface below it.
+    pub const SNAP_TO_GROUND: &str = "snap_to_ground";
+    /// Drops the selected entity onto the surface below it and aligns it to the surface.
+    pub const SNAP_TO_GROUND_ALIGNED: &str = "snap_to_ground_aligned";
+    /// Moves the selected entity to the surface under the pointer and aligns it to the surface.
+    pub const PLACE_ON_SURFACE: &str = "place_on_surface";
+    /// Moves the selected entity to the surface under the pointer, keeping its rotation.
+    pub const PLACE_ON_SURFACE_KEEP_ROTATION: &str = "place_on_surface_keep_rotation";
+    /// Turns measuring distances in the viewports on and off.
+    pub const MEASURE: &str = "measure";
+    /// Frames the selected entities in the hovered viewport.
+    pub const FOCUS_SELECTION: &str = "focus_selection";
+    /// Duplicates the selected entities.
+    pub const DUPLICATE: &str = "duplicate";
+    /// Makes the hovered viewport look through the selected camera, or back through its own.
+    pub const LOOK_THROUGH: &str = "look_through";
+    /// Opens the command palette, or closes it when already open.
+    pub const COMMAND_PALETTE: &str = "command_palette";
+    /// Moves the camera of the hovered 2D viewport back to the origin, without zoom.
+    pub const RESET_VIEW: &str = "reset_view";
+}
+
+/// A key along with the modifiers that have to be held with it.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Keybinding {
+    /// The key that triggers the action when pressed.
+    pub key: KeyCode,
+    /// Whether either control key has to be held.
+    pub ctrl: bool,
+    /// Whether either shift key has to be held.
+    pub shift: bool,
+    /// Whether either alt key has to be held.
+    pub alt: bool,
+}
+
+impl Keybinding {
+    /// A binding to `key` without modifiers.
+    pub const fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Requires control to be held as well.
+    pub const fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Requires shift to be held as well.
+    pub const fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Requires alt to be held as well.
+    pub const fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Returns `true` on the frame the key is pressed while exactly the binding's modifiers are
+    /// held.
+    pub fn just_pressed(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.just_pressed(self.key)
+            && self.ctrl == keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+            && self.shift == keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+            && self.alt == keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+    }
+}
+
+/// The keybindings of the editor actions, keyed by action name. See [`actions`] for the actions
+/// the editor binds by default.
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[reflect(Resource, @SettingsType::Workspace)]
+pub struct EditorKeybindings {
+    /// The binding of each action.
+    pub bindings: HashMap<String, Keybinding>,
+}
+
+impl Default for EditorKeybindings {
+    fn default() -> Self {
+        let mut keybindings = Self {
+            bindings: HashMap::default(),
+        };
+        keybindings.set(actions::LOAD_GLTF, Keybinding::new(KeyCode::KeyL).ctrl());
+        keybindings.set(
+            actions::SAVE_PREFAB,
+            Keybinding::new(KeyCode::KeyS).ctrl().shift(),
+        );
+        keybindings.set(actions::SNAP_TO_GROUND, Keybinding::new(KeyCode::End));
+        keybindings.set(
+            actions::SNAP_TO_GROUND_ALIGNED,
+            Keybinding::new(KeyCode::End).shift(),
+        );
+        keybindings.set(actions::PLACE_ON_SURFACE, Keybinding::new(KeyCode::KeyP));
+        keybindings.set(
+            actions::PLACE_ON_SURFACE_KEEP_ROTATION,
+            Keybinding::new(KeyCode::KeyP).alt(),
+        );
+        keybindings.set(actions::MEASURE, Keybinding::new(KeyCode::KeyM));
+        keybindings.set(actions::FOCUS_SELECTION, Keybinding::new(KeyCode::KeyF));
+        keybindings.set(actions::DUPLICATE, Keybinding::new(KeyCode::KeyD).ctrl());
+        keybindings.set(actions::LOOK_THROUGH, Keybinding::new(KeyCode::Numpad0));
+        keybindings.set(
+            actions::COMMAND_PALETTE,
+            Keybinding::new(KeyCode::KeyP).ctrl(),
+        );
+        keybindings.set(actions::RESET_VIEW, Keybinding::new(KeyCode::Home));
+        keybindings
+    }
+}
+
+impl EditorKeybindings {
+    /// The binding of `action`, if it is bound.
+    pub fn get(&self, action: &str) -> Option<&Keybinding> {
+        self.bindings.get(action)
+    }
+
+    /// Binds `action` to `binding`, replacing its previous binding.
+    pub fn set(&mut self, action: impl Into<String>, binding: Keybinding) {
+        self.bindings.insert(action.into(), binding);
+    }
+
+    /// Returns `true` on the frame the binding of `action` is pressed.
+    pub fn is_action_pressed(&self, action: &str, keyboard: &ButtonInput<KeyCode>) -> bool {
+        self.get(action)
+            .is_some_and(|binding| binding.just_pressed(keyboard))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_editor_settings::{EditorSettingsPlugin, GlobalSettingsPath};
+
+    use super::*;
+
+    #[test]
+    fn modifiers_have_to_match_exactly() {
+        let keybindings = EditorKeybindings::default();
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+
+        keyboard.press(KeyCode::End);
+        assert!(keybindings.is_action_pressed(actions::SNAP_TO_GROUND, &keyboard));
+        assert!(!keybindings.is_action_pressed(actions::SNAP_TO_GROUND_ALIGNED, &keyboard));
+
+        keyboard.clear();
+        keyboard.release(KeyCode::End);
+        keyboard.press(KeyCode::ShiftRight);
+        keyboard.press(KeyCode::End);
+        assert!(!keybindings.is_action_pressed(actions::SNAP_TO_GROUND, &keyboard));
+        assert!(keybindings.is_action_pressed(actions::SNAP_TO_GROUND_ALIGNED, &keyboard));
+
+        assert!(!keybindings.is_action_pressed("unbound", &keyboard));
+    }
+
+    #[test]
+    fn bindings_load_from_workspace_settings() {
+        let path = std::env::temp_dir().join("bevy_editor_core_keybindings_test");
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("workspace.toml"),
+            "[editor_keybindings.bindings.measure]\nkey = \"KeyG\"\nshift = true\n",
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.insert_resource(GlobalSettingsPath(path.clone()))
+            .register_type::<EditorKeybindings>()
+            .init_resource::<EditorKeybindings>()
+            .add_plugins(EditorSettingsPlugin);
+        app.finish();
+
+        let keybindings = app.world().resource::<EditorKeybindings>();
+        assert_eq!(
+            keybindings.get(actions::MEASURE),
+            Some(&Keybinding::new(KeyCode::KeyG).shift())
+        );
+        // Actions missing from the file keep their default binding
+        assert_eq!(
+            keybindings.get(actions::DUPLICATE),
+            Some(&Keybinding::new(KeyCode::KeyD).ctrl())
+        );
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -1,6 +1,12 @@
 //! This crate provides core functionality for the Bevy Engine Editor.
 
-use bevy::{ecs::entity::Entities, prelude::*};
+use bevy::{asset::AssetPath, ecs::entity::Entities, prelude::*};
+
+mod keybindings;
+mod render_layers;
+
+pub use keybindings::{actions, EditorKeybindings, Keybinding};
+pub use render_layers::EditorRenderLayers;
 
 /// Plugin for the editor scene tree pane.
 pub struct EditorCorePlugin;
@@ -8,8 +14,26 @@ pub struct EditorCorePlugin;
 impl Plugin for EditorCorePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SelectedEntity>()
+            .init_resource::<SelectedEntities>()
+            .init_resource::<EditorKeybindings>()
+            .init_resource::<EditorRenderLayers>()
             .register_type::<SelectedEntity>()
-            .add_systems(PostUpdate, reset_selected_entity_if_entity_despawned);
+            .register_type::<SelectedEntities>()
+            .register_type::<Locked>()
+            .register_type::<EditorKeybindings>()
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        reset_selected_entity_if_entity_despawned,
+                        sync_selected_entities.run_if(resource_changed::<SelectedEntity>),
+                    )
+                        .chain(),
+                    update_locked_picking.run_if(locked_picking_changed),
+                    render_layers::warn_render_layer_collisions
+                        .run_if(resource_changed::<EditorRenderLayers>),
+                ),
+            );
     }
 }
 
@@ -18,14 +42,199 @@ impl Plugin for EditorCorePlugin {
 #[reflect(Resource, Default)]
 pub struct SelectedEntity(pub Option<Entity>);
 
-/// System to reset [`SelectedEntity`] when the entity is despawned.
+/// All selected entities in the scene, in the order they were selected.
+///
+/// [`SelectedEntity`] is the primary selection and is always part of this set when it is set.
+/// Setting [`SelectedEntity`] to an entity outside of the set replaces the set with that entity.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SelectedEntities(pub Vec<Entity>);
+
+impl SelectedEntities {
+    /// Returns `true` if `entity` is selected.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
+
+/// An entity that can be dragged onto the scene to spawn the asset at its path, like the files
+/// listed in the asset browser.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct DraggableAsset(pub AssetPath<'static>);
+
+/// System to reset [`SelectedEntity`] when the entity is despawned, and to drop despawned
+/// entities from [`SelectedEntities`].
 pub fn reset_selected_entity_if_entity_despawned(
     mut selected_entity: ResMut<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
     entities: &Entities,
 ) {
+    if selected_entities.0.iter().any(|e| !entities.contains(*e)) {
+        selected_entities.0.retain(|e| entities.contains(*e));
+    }
+
     if let Some(e) = selected_entity.0 {
         if !entities.contains(e) {
-            selected_entity.0 = None;
+            selected_entity.0 = selected_entities.0.last().copied();
+        }
+    }
+}
+
+/// System to keep [`SelectedEntities`] in line with a [`SelectedEntity`] set on its own.
+pub fn sync_selected_entities(
+    selected_entity: Res<SelectedEntity>,
+    mut selected_entities: ResMut<SelectedEntities>,
+) {
+    match selected_entity.0 {
+        Some(e) if selected_entities.contains(e) => {}
+        Some(e) => selected_entities.0 = vec![e],
+        None if !selected_entities.0.is_empty() => selected_entities.0.clear(),
+        None => {}
+    }
+}
+
+/// Marks an entity as locked, so it can't be picked in the viewports, moved by gizmo tools or
+/// edited in the inspector.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct Locked {
+    /// Also lock all descendants of the entity.
+    pub include_children: bool,
+}
+
+/// Returns `true` if `entity` is locked, either directly or by an ancestor that locks its children.
+pub fn is_locked(
+    entity: Entity,
+    locked_query: &Query<&Locked>,
+    parent_query: &Query<&ChildOf>,
+) -> bool {
+    locked_query.contains(entity)
+        || parent_query
+            .iter_ancestors(entity)
+            .any(|ancestor| locked_query.get(ancestor).is_ok_and(|l| l.include_children))
+}
+
+/// Same as [`is_locked`], for code with direct access to the [`World`].
+pub fn is_locked_in_world(world: &World, entity: Entity) -> bool {
+    if world.get::<Locked>(entity).is_some() {
+        return true;
+    }
+
+    let mut current = world.get::<ChildOf>(entity).map(ChildOf::parent);
+    while let Some(ancestor) = current {
+        if world
+            .get::<Locked>(ancestor)
+            .is_some_and(|locked| locked.include_children)
+        {
+            return true;
         }
+        current = world.get::<ChildOf>(ancestor).map(ChildOf::parent);
+    }
+    false
+}
+
+/// Marks entities made unpickable by [`update_locked_picking`], holding the [`Pickable`] they had
+/// before being locked so unlocking restores it.
+#[derive(Component)]
+pub struct LockedPickingIgnore(pub Option<Pickable>);
+
+/// Run condition for [`update_locked_picking`], `true` when an entity was locked, unlocked,
+/// reparented or became pickable since the last run.
+pub fn locked_picking_changed(
+    changed: Query<(), Or<(Changed<Locked>, Changed<ChildOf>)>>,
+    added: Query<(), Or<(Added<Mesh3d>, Added<Mesh2d>, Added<Sprite>)>>,
+    mut removed_locks: RemovedComponents<Locked>,
+    mut removed_parents: RemovedComponents<ChildOf>,
+) -> bool {
+    let removed = removed_locks.read().count() + removed_parents.read().count();
+    removed > 0 || !changed.is_empty() || !added.is_empty()
+}
+
+/// System to exclude locked meshes and sprites from picking.
+pub fn update_locked_picking(
+    mut commands: Commands,
+    pickables: Query<
+        (Entity, Option<&Pickable>, Option<&LockedPickingIgnore>),
+        Or<(With<Mesh3d>, With<Mesh2d>, With<Sprite>)>,
+    >,
+    locked_query: Query<&Locked>,
+    parent_query: Query<&ChildOf>,
+) {
+    for (entity, pickable, ignored) in &pickables {
+        let locked = is_locked(entity, &locked_query, &parent_query);
+        match (locked, ignored) {
+            (true, None) => {
+                commands
+                    .entity(entity)
+                    .insert((Pickable::IGNORE, LockedPickingIgnore(pickable.cloned())));
+            }
+            (false, Some(LockedPickingIgnore(Some(previous)))) => {
+                commands
+                    .entity(entity)
+                    .insert(previous.clone())
+                    .remove::<LockedPickingIgnore>();
+            }
+            (false, Some(LockedPickingIgnore(None))) => {
+                commands
+                    .entity(entity)
+                    .remove::<(Pickable, LockedPickingIgnore)>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locked_entities_are_not_pickable() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, EditorCorePlugin));
+
+        let world = app.world_mut();
+        let parent = world.spawn((Mesh3d::default(), Locked::default())).id();
+        let child = world.spawn((Mesh3d::default(), ChildOf(parent))).id();
+        let not_blocking = Pickable {
+            should_block_lower: false,
+            is_hoverable: true,
+        };
+        let custom = world.spawn((Mesh3d::default(), not_blocking.clone())).id();
+        app.update();
+
+        let pickable = |app: &App, entity: Entity| {
+            app.world()
+                .get::<Pickable>(entity)
+                .is_none_or(|pickable| pickable.is_hoverable)
+        };
+        assert!(!pickable(&app, parent));
+        assert!(pickable(&app, child));
+        assert!(!is_locked_in_world(app.world(), child));
+
+        // Cascade the lock to the children
+        app.world_mut().entity_mut(parent).insert(Locked {
+            include_children: true,
+        });
+        app.update();
+        assert!(!pickable(&app, child));
+        assert!(is_locked_in_world(app.world(), child));
+
+        app.world_mut().entity_mut(parent).remove::<Locked>();
+        app.update();
+        assert!(pickable(&app, parent));
+        assert!(pickable(&app, child));
+        assert!(app.world().get::<Pickable>(parent).is_none());
+
+        // Unlocking restores the picking behavior set before locking
+        app.world_mut().entity_mut(custom).insert(Locked::default());
+        app.update();
+        assert!(!pickable(&app, custom));
+        app.world_mut().entity_mut(custom).remove::<Locked>();
+        app.update();
+        let restored = app.world().get::<Pickable>(custom).unwrap();
+        assert!(restored.is_hoverable);
+        assert!(!restored.should_block_lower);
+        assert!(app.world().get::<LockedPickingIgnore>(custom).is_none());
     }
 }
@@ -0,0 +1,94 @@
+//! The render layers the editor draws its own content on.
+
+use bevy::{prelude::*, render::view::RenderLayers};
+
+/// The render layers used by the editor for its grids, view gizmo and asset previews, kept in one
+/// place so they can be moved out of the way of the layers a project uses.
+///
+/// The viewport cameras render [`scene`](Self::scene) along with the grid of their viewport.
+/// Changes to the grid layers apply to open viewports, the view gizmo layer is read when the
+/// view gizmo is spawned and the asset preview layer when a preview is spawned.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct EditorRenderLayers {
+    /// The layers of the scene being edited, shown in all viewports.
+    pub scene: RenderLayers,
+    /// The layer of the grid of the 3D viewports.
+    pub grid_3d: usize,
+    /// The layer of the grid of the 2D viewports.
+    pub grid_2d: usize,
+    /// The layer of the view gizmo of the 3D viewports.
+    pub view_gizmo: usize,
+    /// The layer asset previews are rendered on.
+    pub asset_preview: usize,
+}
+
+impl Default for EditorRenderLayers {
+    fn default() -> Self {
+        Self {
+            scene: RenderLayers::layer(0),
+            grid_3d: 1,
+            grid_2d: 2,
+            view_gizmo: 22,
+            asset_preview: 23,
+        }
+    }
+}
+
+impl EditorRenderLayers {
+    /// The layers rendered by the cameras of the 3D viewports.
+    pub fn viewport_3d_camera(&self) -> RenderLayers {
+        self.scene.clone().with(self.grid_3d)
+    }
+
+    /// The layers rendered by the cameras of the 2D viewports.
+    pub fn viewport_2d_camera(&self) -> RenderLayers {
+        self.scene.clone().with(self.grid_2d)
+    }
+
+    /// The editor layers that are also part of the scene layers, and would show scene content
+    /// in editor-only passes or editor content in scene cameras.
+    pub fn collisions(&self) -> Vec<usize> {
+        [
+            self.grid_3d,
+            self.grid_2d,
+            self.view_gizmo,
+            self.asset_preview,
+        ]
+        .into_iter()
+        .filter(|&layer| self.scene.intersects(&RenderLayers::layer(layer)))
+        .collect()
+    }
+}
+
+/// Warns about editor layers that collide with the scene layers.
+pub(crate) fn warn_render_layer_collisions(layers: Res<EditorRenderLayers>) {
+    let collisions = layers.collisions();
+    if !collisions.is_empty() {
+        warn!("Editor render layers {collisions:?} are also used by the scene");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collisions_with_scene_layers_are_reported() {
+        let mut layers = EditorRenderLayers::default();
+        assert!(layers.collisions().is_empty());
+        assert_eq!(
+            layers.viewport_3d_camera(),
+            RenderLayers::from_layers(&[0, 1])
+        );
+
+        layers.scene = RenderLayers::from_layers(&[0, 1]);
+        assert_eq!(layers.collisions(), vec![1]);
+
+        layers.grid_3d = 30;
+        assert!(layers.collisions().is_empty());
+        assert_eq!(
+            layers.viewport_3d_camera(),
+            RenderLayers::from_layers(&[0, 1, 30])
+        );
+    }
+}
@@ -23,8 +23,9 @@ impl LoadStruct<'_> {
 
             let field_mut = strct.field_at_mut(i).unwrap();
             let field_attrs = field.custom_attributes();
+            // Dynamic fields, like those of default map and list items, don't know their type
             LoadStructure {
-                type_info: field_mut.get_represented_type_info().unwrap(),
+                type_info: field.type_info().unwrap(),
                 table: toml_value,
                 structure: field_mut,
                 custom_attributes: Some(field_attrs),
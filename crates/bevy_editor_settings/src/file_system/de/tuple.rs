@@ -16,13 +16,13 @@ impl LoadTuple<'_> {
             };
 
             let field_mut = self.tuple.field_mut(i).unwrap();
-            let field_attrs = self.tuple_info.field_at(i).unwrap().custom_attributes();
+            let field_info = self.tuple_info.field_at(i).unwrap();
 
             LoadStructure {
-                type_info: field_mut.get_represented_type_info().unwrap(),
+                type_info: field_info.type_info().unwrap(),
                 table: toml_value,
                 structure: field_mut,
-                custom_attributes: Some(field_attrs),
+                custom_attributes: Some(field_info.custom_attributes()),
             }
             .load();
         }
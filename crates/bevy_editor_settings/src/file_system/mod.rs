@@ -1,13 +1,23 @@
 use std::path::PathBuf;
 
-use bevy::log::{error, warn};
+use bevy::{
+    log::{error, warn},
+    prelude::*,
+};
 use de::{load_preferences, load_toml_file};
+use ser::save_preferences;
 
 pub mod de;
+pub mod ser;
 
 use crate::{GlobalSettingsPath, SettingsType};
 
 const SETTINGS_BASE_DIR: &str = "bevy_editor";
+const WORKSPACE_SETTINGS_FILE: &str = "workspace.toml";
+
+/// The workspace settings as last loaded or saved, so they are only written when they change.
+#[derive(Resource)]
+pub struct SavedWorkspaceSettings(toml::Table);
 
 pub fn global_settings_path() -> Option<PathBuf> {
     let path = directories::BaseDirs::new()?;
@@ -23,14 +33,18 @@ pub fn global_settings_path() -> Option<PathBuf> {
     Some(path)
 }
 
-pub fn load_settings(app: &mut bevy::app::App) {
+pub fn load_settings(app: &mut App) {
     if app.world().get_resource::<GlobalSettingsPath>().is_some() {
         load_global_settings(app.world_mut());
+        load_workspace_settings(app.world_mut());
     }
     load_project_settings(app.world_mut());
+
+    let saved = save_preferences(app.world(), SettingsType::Workspace);
+    app.insert_resource(SavedWorkspaceSettings(saved));
 }
 
-pub fn load_project_settings(world: &mut bevy::prelude::World) {
+pub fn load_project_settings(world: &mut World) {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let Ok(file) = load_toml_file(path.join("Bevy.toml")) else {
         warn!("Failed to load project settings");
@@ -40,7 +54,7 @@ pub fn load_project_settings(world: &mut bevy::prelude::World) {
     load_preferences(world, file, SettingsType::Project);
 }
 
-pub fn load_global_settings(world: &mut bevy::prelude::World) {
+pub fn load_global_settings(world: &mut World) {
     let path = &world.get_resource::<GlobalSettingsPath>().unwrap().0;
     let Ok(file) = load_toml_file(path.join("global.toml")) else {
         warn!("Failed to load global settings");
@@ -49,3 +63,49 @@ pub fn load_global_settings(world: &mut bevy::prelude::World) {
 
     load_preferences(world, file, SettingsType::Global);
 }
+
+pub fn load_workspace_settings(world: &mut World) {
+    let path = &world.get_resource::<GlobalSettingsPath>().unwrap().0;
+    // The file only exists once workspace settings were changed in the editor
+    let Ok(file) = load_toml_file(path.join(WORKSPACE_SETTINGS_FILE)) else {
+        return;
+    };
+
+    load_preferences(world, file, SettingsType::Workspace);
+}
+
+/// Writes the workspace settings to the global settings directory when any of them changed.
+/// Keys of the file that don't belong to a resource of this app are kept.
+pub fn save_workspace_settings(world: &mut World) {
+    let (last_run, this_run) = (world.last_change_tick(), world.change_tick());
+    let changed = world.iter_resources().any(|(info, _)| {
+        world
+            .get_resource_change_ticks_by_id(info.id())
+            .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+    });
+    if !changed {
+        return;
+    }
+
+    let settings = save_preferences(world, SettingsType::Workspace);
+    let Some(mut saved) = world.get_resource_mut::<SavedWorkspaceSettings>() else {
+        return;
+    };
+    if saved.0 == settings {
+        return;
+    }
+    saved.0 = settings.clone();
+
+    let Some(path) = world.get_resource::<GlobalSettingsPath>() else {
+        return;
+    };
+    let path = path.0.join(WORKSPACE_SETTINGS_FILE);
+    let mut file = load_toml_file(&path).unwrap_or_default();
+    file.extend(settings);
+    let result = toml::to_string(&file)
+        .map_err(|error| error.to_string())
+        .and_then(|contents| std::fs::write(&path, contents).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        error!("Failed to save workspace settings: {}", error);
+    }
+}
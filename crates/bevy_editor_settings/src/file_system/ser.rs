@@ -0,0 +1,229 @@
+use bevy::{
+    prelude::*,
+    reflect::{ReflectFromPtr, ReflectRef, TypeInfo, VariantType},
+};
+use heck::ToSnakeCase;
+
+use crate::{SettingKey, SettingsType};
+
+/// Builds the table of all resources of `settings_type`, laid out the way [`super::de`] loads them.
+pub fn save_preferences(world: &World, settings_type: SettingsType) -> toml::Table {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut table = toml::Table::new();
+
+    for (info, ptr) in world.iter_resources() {
+        let Some(type_reg) = info.type_id().and_then(|type_id| registry.get(type_id)) else {
+            continue;
+        };
+        let custom_attributes = match type_reg.type_info() {
+            TypeInfo::Struct(info) => info.custom_attributes(),
+            TypeInfo::Enum(info) => info.custom_attributes(),
+            TypeInfo::TupleStruct(info) => info.custom_attributes(),
+            _ => continue,
+        };
+        if custom_attributes.get::<SettingsType>() != Some(&settings_type) {
+            continue;
+        }
+        let Some(reflect_from_ptr) = type_reg.data::<ReflectFromPtr>() else {
+            continue;
+        };
+        #[allow(unsafe_code)]
+        // SAFETY: `ptr` points to the resource of the type the `ReflectFromPtr` was created for
+        let resource = unsafe { reflect_from_ptr.as_reflect(ptr) };
+
+        let name = custom_attributes
+            .get::<SettingKey>()
+            .map(|key| key.0.to_string())
+            .unwrap_or_else(|| resource.reflect_type_ident().unwrap().to_snake_case());
+
+        let value = match resource.reflect_ref() {
+            ReflectRef::Struct(_) => save_value(resource.as_partial_reflect()),
+            ReflectRef::Enum(_) => save_value(resource.as_partial_reflect()).map(|variant| {
+                toml::Value::Table([("variant".to_string(), variant)].into_iter().collect())
+            }),
+            ReflectRef::TupleStruct(_) => save_value(resource.as_partial_reflect()).map(|fields| {
+                toml::Value::Table([("fields".to_string(), fields)].into_iter().collect())
+            }),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                table.insert(name, value);
+            }
+            None => warn!(
+                "Preferences: Unable to save {}",
+                resource.reflect_type_path()
+            ),
+        }
+    }
+
+    table
+}
+
+/// Converts `value` to TOML, or `None` when it holds a type that can't be stored.
+fn save_value(value: &dyn PartialReflect) -> Option<toml::Value> {
+    let value = match value.reflect_ref() {
+        ReflectRef::Struct(strct) => toml::Value::Table(
+            (0..strct.field_len())
+                .map(|i| {
+                    Some((
+                        strct.name_at(i)?.to_string(),
+                        save_value(strct.field_at(i)?)?,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        ),
+        ReflectRef::TupleStruct(tuple_struct) => toml::Value::Array(
+            tuple_struct
+                .iter_fields()
+                .map(save_value)
+                .collect::<Option<_>>()?,
+        ),
+        ReflectRef::Tuple(tuple) => {
+            toml::Value::Array(tuple.iter_fields().map(save_value).collect::<Option<_>>()?)
+        }
+        ReflectRef::List(list) => {
+            toml::Value::Array(list.iter().map(save_value).collect::<Option<_>>()?)
+        }
+        ReflectRef::Array(array) => {
+            toml::Value::Array(array.iter().map(save_value).collect::<Option<_>>()?)
+        }
+        ReflectRef::Set(set) => {
+            toml::Value::Array(set.iter().map(save_value).collect::<Option<_>>()?)
+        }
+        ReflectRef::Map(map) => toml::Value::Table(
+            map.iter()
+                .map(|(key, value)| {
+                    Some((
+                        key.try_downcast_ref::<String>()?.clone(),
+                        save_value(value)?,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        ),
+        ReflectRef::Enum(enm) => {
+            let variant = enm.variant_name().to_string();
+            match enm.variant_type() {
+                VariantType::Unit => toml::Value::String(variant),
+                VariantType::Tuple => {
+                    let fields = (0..enm.field_len())
+                        .map(|i| save_value(enm.field_at(i)?))
+                        .collect::<Option<_>>()?;
+                    toml::Value::Table(
+                        [(variant, toml::Value::Array(fields))]
+                            .into_iter()
+                            .collect(),
+                    )
+                }
+                VariantType::Struct => {
+                    let fields = (0..enm.field_len())
+                        .map(|i| Some((enm.name_at(i)?.to_string(), save_value(enm.field_at(i)?)?)))
+                        .collect::<Option<_>>()?;
+                    toml::Value::Table(
+                        [(variant, toml::Value::Table(fields))]
+                            .into_iter()
+                            .collect(),
+                    )
+                }
+            }
+        }
+        ReflectRef::Opaque(value) => save_opaque(value)?,
+        #[allow(unreachable_patterns)]
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Converts the strings, numbers and booleans [`super::de`] can load.
+fn save_opaque(value: &dyn PartialReflect) -> Option<toml::Value> {
+    if let Some(value) = value.try_downcast_ref::<String>() {
+        Some(toml::Value::String(value.clone()))
+    } else if let Some(value) = value.try_downcast_ref::<bool>() {
+        Some(toml::Value::Boolean(*value))
+    } else if let Some(value) = value.try_downcast_ref::<f64>() {
+        Some(toml::Value::Float(*value))
+    } else if let Some(value) = value.try_downcast_ref::<f32>() {
+        Some(toml::Value::Float(f64::from(*value)))
+    } else if let Some(value) = value.try_downcast_ref::<i64>() {
+        Some(toml::Value::Integer(*value))
+    } else if let Some(value) = value.try_downcast_ref::<i32>() {
+        Some(toml::Value::Integer(i64::from(*value)))
+    } else if let Some(value) = value.try_downcast_ref::<i16>() {
+        Some(toml::Value::Integer(i64::from(*value)))
+    } else if let Some(value) = value.try_downcast_ref::<i8>() {
+        Some(toml::Value::Integer(i64::from(*value)))
+    } else if let Some(value) = value.try_downcast_ref::<u64>() {
+        i64::try_from(*value).ok().map(toml::Value::Integer)
+    } else if let Some(value) = value.try_downcast_ref::<u32>() {
+        Some(toml::Value::Integer(i64::from(*value)))
+    } else if let Some(value) = value.try_downcast_ref::<u16>() {
+        Some(toml::Value::Integer(i64::from(*value)))
+    } else if let Some(value) = value.try_downcast_ref::<u8>() {
+        Some(toml::Value::Integer(i64::from(*value)))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::DynamicTyped as _;
+
+    use super::*;
+    use crate::file_system::de::LoadStructure;
+
+    #[derive(Debug, Clone, PartialEq, Reflect, Default)]
+    enum Shape {
+        #[default]
+        Point,
+        Circle(f32),
+        Rect {
+            width: f32,
+            height: f32,
+        },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Reflect, Default)]
+    struct Values {
+        name: String,
+        enabled: bool,
+        size: u8,
+        offset: Vec3,
+        shapes: Vec<Shape>,
+        tags: bevy::platform::collections::HashMap<String, i32>,
+    }
+
+    #[tracing_test::traced_test]
+    #[test]
+    fn saved_values_load_back() {
+        let values = Values {
+            name: "grid".to_string(),
+            enabled: true,
+            size: 8,
+            offset: Vec3::new(0.5, -1., 2.),
+            shapes: vec![
+                Shape::Point,
+                Shape::Circle(1.5),
+                Shape::Rect {
+                    width: 2.,
+                    height: 3.,
+                },
+            ],
+            tags: [("a".to_string(), 1), ("b".to_string(), 2)]
+                .into_iter()
+                .collect(),
+        };
+
+        let toml_value = save_value(&values).unwrap();
+        let mut loaded = Values::default();
+        LoadStructure {
+            type_info: loaded.reflect_type_info(),
+            table: &toml_value,
+            structure: &mut loaded,
+            custom_attributes: None,
+        }
+        .load();
+
+        assert_eq!(loaded, values);
+    }
+}
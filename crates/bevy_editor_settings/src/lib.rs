@@ -48,20 +48,28 @@ pub struct SettingKey(pub &'static str);
 pub struct GlobalSettingsPath(pub std::path::PathBuf);
 
 /// A Bevy plugin for editor settings.
-/// This plugin loads the workspace settings, user settings, and project settings.
+/// This plugin loads the workspace settings, user settings, and project settings,
+/// and saves the workspace settings to `workspace.toml` in the global settings directory whenever
+/// they change.
+///
+/// A [`GlobalSettingsPath`] inserted before the plugin is added is used instead of the user's
+/// configuration directory.
 pub struct EditorSettingsPlugin;
 
 impl Plugin for EditorSettingsPlugin {
     fn build(&self, app: &mut App) {
-        match file_system::global_settings_path() {
-            Some(path) => {
-                debug!("Global settings path: {:?}", path);
-                app.insert_resource(GlobalSettingsPath(path));
-            }
-            None => {
-                warn!("Failed to load global settings");
-            }
-        };
+        if !app.world().contains_resource::<GlobalSettingsPath>() {
+            match file_system::global_settings_path() {
+                Some(path) => {
+                    debug!("Global settings path: {:?}", path);
+                    app.insert_resource(GlobalSettingsPath(path));
+                }
+                None => {
+                    warn!("Failed to load global settings");
+                }
+            };
+        }
+        app.add_systems(Last, file_system::save_workspace_settings);
     }
 
     fn finish(&self, app: &mut App) {
@@ -253,4 +261,58 @@ mod tests {
             }
         );
     }
+
+    #[derive(Debug, Clone, PartialEq, Reflect, Resource)]
+    #[reflect(Resource, @SettingsType::Workspace)]
+    struct WorkspaceLayout {
+        pub zoom: f32,
+        pub panes: Vec<String>,
+    }
+
+    impl Default for WorkspaceLayout {
+        fn default() -> Self {
+            Self {
+                zoom: 1.0,
+                panes: vec!["viewport".to_string()],
+            }
+        }
+    }
+
+    fn workspace_app(path: &std::path::Path) -> App {
+        let mut app = App::new();
+        app.insert_resource(GlobalSettingsPath(path.to_owned()))
+            .register_type::<WorkspaceLayout>()
+            .init_resource::<WorkspaceLayout>()
+            .add_plugins(EditorSettingsPlugin);
+        app.finish();
+        app.update();
+        app
+    }
+
+    #[traced_test]
+    #[test]
+    fn workspace_settings_are_saved_on_change_and_loaded_on_start() {
+        let path = std::env::temp_dir().join("bevy_editor_settings_workspace_test");
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        let file = path.join("workspace.toml");
+
+        // Unchanged settings are not written
+        let mut app = workspace_app(&path);
+        app.update();
+        assert!(!file.exists());
+
+        let changed = WorkspaceLayout {
+            zoom: 2.5,
+            panes: vec!["viewport".to_string(), "inspector".to_string()],
+        };
+        *app.world_mut().resource_mut::<WorkspaceLayout>() = changed.clone();
+        app.update();
+        assert!(file.exists());
+
+        let app = workspace_app(&path);
+        assert_eq!(*app.world().resource::<WorkspaceLayout>(), changed);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
 }
@@ -1,9 +1,45 @@
 //! Icons
 
+/// A window, used as the default icon of panes.
+pub const APP_WINDOW: &str = "\u{e42b}";
+
+/// A box, used for 3D content.
+pub const BOX: &str = "\u{e065}";
+
 /// A downward-facing chevron, often used to indicate
 /// collapsible sections or dropdowns.
 pub const CHEVRON_DOWN: &str = "\u{e071}";
 
+/// An open eye, used to show that something is visible.
+pub const EYE: &str = "\u{e0be}";
+
+/// A crossed out eye, used to show that something is hidden.
+pub const EYE_OFF: &str = "\u{e0bf}";
+
+/// A folder, used for directories and assets.
+pub const FOLDER: &str = "\u{e0db}";
+
+/// A frame, used for 2D content.
+pub const FRAME: &str = "\u{e291}";
+
 /// A vertical grip icon, typically used for drag handles
 /// or reordering list items.
 pub const GRIP_VERTICAL: &str = "\u{e0ef}";
+
+/// A clock with a counterclockwise arrow, used for undo history.
+pub const HISTORY: &str = "\u{e1f5}";
+
+/// A tree of list items, used for hierarchies.
+pub const LIST_TREE: &str = "\u{e40d}";
+
+/// A closed padlock, used to show that something is locked.
+pub const LOCK: &str = "\u{e10f}";
+
+/// An open padlock, used to show that something can be locked.
+pub const LOCK_OPEN: &str = "\u{e110}";
+
+/// Horizontal sliders, used for properties and settings.
+pub const SLIDERS_HORIZONTAL: &str = "\u{e29a}";
+
+/// A cross icon, typically used for close or remove buttons.
+pub const X: &str = "\u{e1b2}";
@@ -2,6 +2,9 @@
 use bevy::{asset::embedded_asset, prelude::*};
 
 pub mod icons;
+mod loader;
+
+pub use loader::{ThemeLoader, ThemeOverrides};
 
 /// The Pallet Plugin.
 pub struct StylesPlugin;
@@ -10,7 +13,15 @@ impl Plugin for StylesPlugin {
     fn build(&self, app: &mut App) {
         embedded_asset!(app, "assets/fonts/Inter-Regular.ttf");
         embedded_asset!(app, "assets/icons/Lucide.ttf");
-        app.init_resource::<Theme>();
+        app.init_resource::<ThemeLoader>()
+            .init_resource::<Theme>()
+            .init_resource::<ThemeVariant>()
+            .add_systems(
+                Update,
+                apply_theme_variant.run_if(
+                    resource_changed::<ThemeVariant>.and(not(resource_added::<ThemeVariant>)),
+                ),
+            );
     }
 }
 
@@ -39,6 +50,16 @@ pub struct Theme {
     pub scroll_box: ScrollBoxStyles,
 }
 
+/// The built-in [`Theme`] presets. Changing this resource replaces the current [`Theme`] with the preset.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ThemeVariant {
+    /// The default dark theme, including any overrides from the [`ThemeLoader`] file.
+    #[default]
+    Dark,
+    /// A high-contrast theme for low-vision users.
+    HighContrast,
+}
+
 /// The general styles for the editor.
 pub struct GeneralStyles {
     /// The common border radius for elements in the editor.
@@ -83,6 +104,8 @@ pub struct PaneStyles {
     pub area_background_color: BackgroundColor,
     /// The border radius of the header of the pane.
     pub header_border_radius: BorderRadius,
+    /// The color of the icon in the header of panes without an accent color.
+    pub tab_icon_color: Color,
 }
 
 /// The styles for menus in the editor.
@@ -129,7 +152,19 @@ pub struct ScrollBoxStyles {
 
 impl FromWorld for Theme {
     fn from_world(world: &mut World) -> Self {
-        let asset_server = world.resource::<AssetServer>();
+        let mut theme = Theme::dark(world.resource::<AssetServer>());
+
+        if let Some(loader) = world.get_resource::<ThemeLoader>() {
+            loader.apply(&mut theme);
+        }
+
+        theme
+    }
+}
+
+impl Theme {
+    /// The default dark theme.
+    pub fn dark(asset_server: &AssetServer) -> Self {
         Theme {
             general: GeneralStyles {
                 border_radius: BorderRadius::all(Val::Px(8.)),
@@ -154,6 +189,7 @@ impl FromWorld for Theme {
                 header_background_color: BackgroundColor(Color::oklch(0.3407, 0.0, 0.0)),
                 area_background_color: BackgroundColor(Color::oklch(0.3677, 0.0, 0.0)),
                 header_border_radius: BorderRadius::top(Val::Px(8.)),
+                tab_icon_color: Color::oklch(0.75, 0.0, 0.0),
             },
             menu: MenuStyles {
                 background_color: Color::oklch(0.209, 0.0, 0.0),
@@ -178,4 +214,111 @@ impl FromWorld for Theme {
             },
         }
     }
+
+    /// A high-contrast theme for low-vision users.
+    ///
+    /// Text and grid lines stand out strongly against near-black backgrounds, and the axis colors
+    /// are taken from the Okabe-Ito palette so they stay distinguishable with common types of
+    /// color blindness.
+    pub fn high_contrast(asset_server: &AssetServer) -> Self {
+        let vermillion = Color::oklch(0.621, 0.17, 47.5);
+        let yellow = Color::oklch(0.902, 0.172, 105.0);
+        let sky_blue = Color::oklch(0.735, 0.117, 236.2);
+
+        let mut theme = Theme::dark(asset_server);
+        theme.general.background_color = BackgroundColor(Color::oklch(0.0, 0.0, 0.0));
+        theme.button.background_color = BackgroundColor(Color::oklch(0.25, 0.0, 0.0));
+        theme.button.hover_color = sky_blue;
+        theme.text.low_priority = Color::oklch(0.75, 0.0, 0.0);
+        theme.text.text_color = Color::oklch(1.0, 0.0, 0.0);
+        theme.text.high_priority = yellow;
+        theme.pane.header_background_color = BackgroundColor(Color::oklch(0.2, 0.0, 0.0));
+        theme.pane.area_background_color = BackgroundColor(Color::oklch(0.1, 0.0, 0.0));
+        theme.menu.background_color = Color::oklch(0.0, 0.0, 0.0);
+        theme.context_menu.background_color = BackgroundColor(Color::oklch(0.1, 0.0, 0.0));
+        theme.context_menu.hover_color = BackgroundColor(Color::oklch(0.3, 0.0, 0.0));
+        theme.viewport = ViewportStyles {
+            background_color: Color::oklch(0.1, 0.0, 0.0),
+            x_axis_color: vermillion,
+            y_axis_color: yellow,
+            z_axis_color: sky_blue,
+            grid_major_line_color: Color::oklch(0.85, 0.0, 0.0),
+            grid_minor_line_color: Color::oklch(0.6, 0.0, 0.0),
+        };
+        theme.scroll_box.background_color = BackgroundColor(Color::oklch(0.15, 0.0, 0.0));
+        theme.scroll_box.handle_color = Color::oklch(0.7, 0.0, 0.0);
+        theme
+    }
+}
+
+fn apply_theme_variant(
+    variant: Res<ThemeVariant>,
+    mut theme: ResMut<Theme>,
+    asset_server: Res<AssetServer>,
+    loader: Option<Res<ThemeLoader>>,
+) {
+    *theme = match *variant {
+        ThemeVariant::Dark => {
+            let mut dark = Theme::dark(&asset_server);
+            if let Some(loader) = loader {
+                loader.apply(&mut dark);
+            }
+            dark
+        }
+        ThemeVariant::HighContrast => Theme::high_contrast(&asset_server),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The WCAG contrast ratio between two colors.
+    fn contrast_ratio(a: Color, b: Color) -> f32 {
+        let luminance = |color: Color| {
+            let color = LinearRgba::from(color);
+            0.2126 * color.red + 0.7152 * color.green + 0.0722 * color.blue
+        };
+        let (a, b) = (luminance(a), luminance(b));
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    #[test]
+    fn high_contrast_preset_contrast_ratios() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>();
+        let theme = Theme::high_contrast(app.world().resource::<AssetServer>());
+
+        // WCAG AA for normal text
+        const THRESHOLD: f32 = 4.5;
+
+        let pairs = [
+            (theme.text.text_color, theme.pane.area_background_color.0),
+            (theme.text.text_color, theme.pane.header_background_color.0),
+            (
+                theme.text.low_priority,
+                theme.pane.header_background_color.0,
+            ),
+            (theme.text.text_color, theme.general.background_color.0),
+            (
+                theme.viewport.grid_major_line_color,
+                theme.viewport.background_color,
+            ),
+            (
+                theme.viewport.grid_minor_line_color,
+                theme.viewport.background_color,
+            ),
+            (theme.viewport.x_axis_color, theme.viewport.background_color),
+            (theme.viewport.y_axis_color, theme.viewport.background_color),
+            (theme.viewport.z_axis_color, theme.viewport.background_color),
+        ];
+        for (foreground, background) in pairs {
+            let ratio = contrast_ratio(foreground, background);
+            assert!(
+                ratio >= THRESHOLD,
+                "{foreground:?} on {background:?} has a contrast ratio of {ratio}"
+            );
+        }
+    }
 }
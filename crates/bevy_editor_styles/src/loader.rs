@@ -0,0 +1,226 @@
+//! Loading [`Theme`] colors from a RON file.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::Theme;
+
+/// Reads a theme file and applies it on top of the built-in [`Theme`] when it is created.
+///
+/// The file is a RON map from theme section to color field, with colors given as OKLCH
+/// `(lightness, chroma, hue)` tuples:
+///
+/// ```ron
+/// {
+///     "viewport": {
+///         "background_color": (0.2, 0.0, 0.0),
+///         "grid_major_line_color": (0.6, 0.0, 0.0),
+///     },
+/// }
+/// ```
+///
+/// A missing or malformed file leaves the built-in theme untouched.
+#[derive(Resource, Clone, Debug)]
+pub struct ThemeLoader {
+    /// The path of the theme file, relative to the working directory.
+    pub path: PathBuf,
+}
+
+impl Default for ThemeLoader {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("assets/theme.ron"),
+        }
+    }
+}
+
+impl ThemeLoader {
+    /// Apply the theme file to `theme`, if it exists and can be parsed.
+    pub fn apply(&self, theme: &mut Theme) {
+        let Ok(contents) = std::fs::read_to_string(&self.path) else {
+            debug!(
+                "No theme file found at {}, using the default theme",
+                self.path.display()
+            );
+            return;
+        };
+
+        match ThemeOverrides::from_ron(&contents) {
+            Ok(overrides) => {
+                overrides.apply(theme);
+            }
+            Err(error) => {
+                warn!(
+                    "Failed to parse theme file {}, using the default theme: {error}",
+                    self.path.display()
+                );
+            }
+        }
+    }
+}
+
+/// Color overrides for a [`Theme`], as read from a theme file. See [`ThemeLoader`] for the format.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(transparent)]
+pub struct ThemeOverrides(BTreeMap<String, BTreeMap<String, (f32, f32, f32)>>);
+
+impl ThemeOverrides {
+    /// Parse overrides from a RON string.
+    pub fn from_ron(source: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(source)
+    }
+
+    /// Apply the overrides to `theme`.
+    ///
+    /// Returns the `section.field` names of the colors that weren't specified and kept their
+    /// default value.
+    pub fn apply(&self, theme: &mut Theme) -> Vec<String> {
+        let mut fallbacks = Vec::new();
+        let mut known = 0;
+
+        for (section, field, color) in theme_colors(theme) {
+            match self.0.get(section).and_then(|fields| fields.get(field)) {
+                Some(&(lightness, chroma, hue)) => {
+                    *color = Color::oklch(lightness, chroma, hue);
+                    known += 1;
+                }
+                None => fallbacks.push(format!("{section}.{field}")),
+            }
+        }
+
+        let specified: usize = self.0.values().map(BTreeMap::len).sum();
+        if specified != known {
+            warn!("The theme file contains unknown sections or fields, they were ignored");
+        }
+        if !fallbacks.is_empty() {
+            info!(
+                "Theme colors not set in the theme file, using defaults: {}",
+                fallbacks.join(", ")
+            );
+        }
+
+        fallbacks
+    }
+}
+
+/// All colors of a [`Theme`] along with the section and field name they go by in a theme file.
+fn theme_colors(theme: &mut Theme) -> [(&'static str, &'static str, &mut Color); 19] {
+    [
+        (
+            "general",
+            "background_color",
+            &mut theme.general.background_color.0,
+        ),
+        (
+            "button",
+            "background_color",
+            &mut theme.button.background_color.0,
+        ),
+        ("button", "hover_color", &mut theme.button.hover_color),
+        ("text", "low_priority", &mut theme.text.low_priority),
+        ("text", "text_color", &mut theme.text.text_color),
+        ("text", "high_priority", &mut theme.text.high_priority),
+        (
+            "pane",
+            "header_background_color",
+            &mut theme.pane.header_background_color.0,
+        ),
+        (
+            "pane",
+            "area_background_color",
+            &mut theme.pane.area_background_color.0,
+        ),
+        ("menu", "background_color", &mut theme.menu.background_color),
+        (
+            "context_menu",
+            "background_color",
+            &mut theme.context_menu.background_color.0,
+        ),
+        (
+            "context_menu",
+            "hover_color",
+            &mut theme.context_menu.hover_color.0,
+        ),
+        (
+            "viewport",
+            "background_color",
+            &mut theme.viewport.background_color,
+        ),
+        ("viewport", "x_axis_color", &mut theme.viewport.x_axis_color),
+        ("viewport", "y_axis_color", &mut theme.viewport.y_axis_color),
+        ("viewport", "z_axis_color", &mut theme.viewport.z_axis_color),
+        (
+            "viewport",
+            "grid_major_line_color",
+            &mut theme.viewport.grid_major_line_color,
+        ),
+        (
+            "viewport",
+            "grid_minor_line_color",
+            &mut theme.viewport.grid_minor_line_color,
+        ),
+        (
+            "scroll_box",
+            "background_color",
+            &mut theme.scroll_box.background_color.0,
+        ),
+        (
+            "scroll_box",
+            "handle_color",
+            &mut theme.scroll_box.handle_color,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_theme() -> Theme {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>();
+        Theme::from_world(app.world_mut())
+    }
+
+    #[test]
+    fn partial_theme_overrides_specified_fields() {
+        let defaults = default_theme();
+        let mut theme = default_theme();
+
+        let overrides = ThemeOverrides::from_ron(
+            r#"{
+                "viewport": {
+                    "background_color": (0.1, 0.0, 0.0),
+                    "x_axis_color": (0.7, 0.2, 30.0),
+                },
+                "text": {
+                    "text_color": (1.0, 0.0, 0.0),
+                },
+            }"#,
+        )
+        .unwrap();
+        let fallbacks = overrides.apply(&mut theme);
+
+        assert_eq!(theme.viewport.background_color, Color::oklch(0.1, 0.0, 0.0));
+        assert_eq!(theme.viewport.x_axis_color, Color::oklch(0.7, 0.2, 30.0));
+        assert_eq!(theme.text.text_color, Color::oklch(1.0, 0.0, 0.0));
+
+        assert_eq!(theme.viewport.z_axis_color, defaults.viewport.z_axis_color);
+        assert_eq!(
+            theme.general.background_color.0,
+            defaults.general.background_color.0
+        );
+
+        assert_eq!(fallbacks.len(), 16);
+        assert!(fallbacks.contains(&"viewport.z_axis_color".to_string()));
+        assert!(!fallbacks.contains(&"viewport.background_color".to_string()));
+    }
+
+    #[test]
+    fn malformed_theme_is_rejected() {
+        assert!(ThemeOverrides::from_ron("{ \"viewport\": 3 }").is_err());
+    }
+}
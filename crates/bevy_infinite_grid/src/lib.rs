@@ -39,6 +39,17 @@ pub struct InfiniteGridSettings {
     pub fadeout_distance: f32,
     pub dot_fadeout_strength: f32,
     pub scale: f32,
+    pub style: GridStyle,
+}
+
+/// How the grid cells are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GridStyle {
+    /// Continuous lines along both axes.
+    #[default]
+    Lines,
+    /// Dots where the lines would cross.
+    Dots,
 }
 
 impl Default for InfiniteGridSettings {
@@ -51,6 +62,7 @@ impl Default for InfiniteGridSettings {
             fadeout_distance: 100.,
             dot_fadeout_strength: 0.25,
             scale: 1.,
+            style: GridStyle::Lines,
         }
     }
 }
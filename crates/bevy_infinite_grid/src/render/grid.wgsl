@@ -10,6 +10,8 @@ struct InfiniteGridSettings {
     // 1 / fadeout_distance
     dist_fadeout_const: f32,
     dot_fadeout_const: f32,
+    // 1 when the grid is drawn as dots, 0 for lines
+    dots: f32,
     x_axis_col: vec3<f32>,
     z_axis_col: vec3<f32>,
     minor_line_col: vec4<f32>,
@@ -153,14 +155,15 @@ fn fragment(in: VertexOutput) -> FragmentOutput {
     let coord = plane_coords / scale; // use the scale variable to set the distance between the lines
     let derivative = fwidth(coord);
     let grid = abs(fract(coord - 0.5) - 0.5) / derivative;
-    let lne = min(grid.x, grid.y);
+    // Dots only show where both lines are close, and are drawn twice as wide to stay visible
+    let lne = mix(min(grid.x, grid.y), max(grid.x, grid.y) * 0.5, grid_settings.dots);
 
     let minimumz = min(derivative.y, 1.) * scale;
     let minimumx = min(derivative.x, 1.) * scale;
 
     let derivative2 = fwidth(coord * 0.1);
     let grid2 = abs(fract((coord * 0.1) - 0.5) - 0.5) / derivative2;
-    let mg_line = min(grid2.x, grid2.y);
+    let mg_line = mix(min(grid2.x, grid2.y), max(grid2.x, grid2.y), grid_settings.dots);
 
     let grid_alpha = 1.0 - min(lne, 1.0);
     let base_grid_color = mix(grid_settings.major_line_col, grid_settings.minor_line_col, step(1., mg_line));
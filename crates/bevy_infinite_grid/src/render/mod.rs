@@ -35,7 +35,7 @@ use bevy::{
     },
 };
 
-use crate::InfiniteGridSettings;
+use crate::{GridStyle, InfiniteGridSettings};
 
 const GRID_SHADER_HANDLE: Handle<Shader> = weak_handle!("7cd38dd1-d707-481e-b38c-0eccb706e629");
 
@@ -92,6 +92,8 @@ pub struct GridDisplaySettingsUniform {
     // 1 / fadeout_distance
     dist_fadeout_const: f32,
     dot_fadeout_const: f32,
+    // 1 when the grid is drawn as dots, 0 for lines
+    dots: f32,
     x_axis_color: Vec3,
     z_axis_color: Vec3,
     minor_line_color: Vec4,
@@ -104,6 +106,7 @@ impl GridDisplaySettingsUniform {
             scale: settings.scale,
             dist_fadeout_const: 1. / settings.fadeout_distance,
             dot_fadeout_const: 1. / settings.dot_fadeout_strength,
+            dots: f32::from(u8::from(settings.style == GridStyle::Dots)),
             x_axis_color: settings.x_axis_color.to_linear().to_vec3(),
             z_axis_color: settings.z_axis_color.to_linear().to_vec3(),
             minor_line_color: settings.minor_line_color.to_linear().to_vec4(),
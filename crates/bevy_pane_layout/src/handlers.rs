@@ -3,16 +3,20 @@ use bevy_editor_styles::Theme;
 
 use crate::{
     ui::{spawn_divider, spawn_pane, spawn_resize_handle},
-    Divider, PaneRootNode, RootPaneLayoutNode, Size,
+    Divider, MaximizedPane, PaneClosed, RootPaneLayoutNode, Size,
 };
 
+/// Closes the pane, giving its space to its neighbors. A maximized layout is restored first so the
+/// other panes don't stay hidden.
 pub(crate) fn remove_pane(
     target: In<Entity>,
     mut commands: Commands,
+    mut maximized: ResMut<MaximizedPane>,
     parent_query: Query<&ChildOf>,
     children_query: Query<&Children>,
     root_query: Query<(), With<RootPaneLayoutNode>>,
     mut size_query: Query<&mut Size>,
+    mut node_query: Query<&mut Node>,
 ) {
     // Grab the id of the pane root
     let target = parent_query.iter_ancestors(*target).nth(1).unwrap();
@@ -23,6 +27,7 @@ pub(crate) fn remove_pane(
     if root_query.contains(parent) {
         return;
     }
+    maximized.restore(&mut size_query, &mut node_query);
 
     // Find the index of this pane among its siblings
     let siblings = children_query.get(parent).unwrap();
@@ -51,21 +56,72 @@ pub(crate) fn remove_pane(
     commands.entity(siblings[resize_handle_index]).despawn();
     // Despawn this pane
     commands.entity(target).despawn();
+    commands.send_event(PaneClosed { pane: target });
 }
 
-/// Right clicking dividers the pane horizontally
-/// Holding left shift and right clicking dividers the pane vertically
-#[expect(clippy::too_many_arguments)]
+/// Expands the pane to fill the whole layout, or restores the layout if it is already maximized.
+/// Only one pane can be maximized at a time, maximizing another pane restores the previous one first.
+pub(crate) fn toggle_maximize_pane(
+    target: In<Entity>,
+    mut maximized: ResMut<MaximizedPane>,
+    parent_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    root_query: Query<(), With<RootPaneLayoutNode>>,
+    mut size_query: Query<&mut Size>,
+    mut node_query: Query<&mut Node>,
+) {
+    // Grab the id of the pane root
+    let target = parent_query.iter_ancestors(*target).nth(1).unwrap();
+
+    // Restore the previous layout
+    let previous = maximized.restore(&mut size_query, &mut node_query);
+
+    if previous == Some(target) {
+        return;
+    }
+
+    // Expand the pane and each of its ancestors, hiding everything next to them
+    let mut entity = target;
+    loop {
+        let mut size = size_query.get_mut(entity).unwrap();
+        maximized.sizes.push((entity, size.0));
+        size.0 = 1.;
+
+        let parent = parent_query.get(entity).unwrap().parent();
+        for sibling in children_query.get(parent).unwrap().iter() {
+            if sibling == entity {
+                continue;
+            }
+            if let Ok(mut node) = node_query.get_mut(sibling) {
+                maximized.hidden.push((sibling, node.display));
+                node.display = Display::None;
+            }
+        }
+
+        if root_query.contains(parent) {
+            break;
+        }
+        entity = parent;
+    }
+    maximized.pane = Some(target);
+}
+
+/// Splits the pane in two, horizontally or `vertical`ly, with a new pane of the `pane_type`
+/// registered in the [`PaneRegistry`](crate::registry::PaneRegistry) taking up the second half.
+/// A maximized layout is restored first, so the split starts from the stored sizes.
 pub(crate) fn split_pane(
-    In((target, vertical)): In<(Entity, bool)>,
+    In((target, vertical, pane_type)): In<(Entity, bool, String)>,
     mut commands: Commands,
     theme: Res<Theme>,
+    mut maximized: ResMut<MaximizedPane>,
     divider_query: Query<&Divider>,
-    pane_root_query: Query<&PaneRootNode>,
     mut size_query: Query<&mut Size>,
+    mut node_query: Query<&mut Node>,
     children_query: Query<&Children>,
     parent_query: Query<&ChildOf>,
 ) {
+    maximized.restore(&mut size_query, &mut node_query);
+
     let divider = if vertical {
         Divider::Vertical
     } else {
@@ -75,8 +131,6 @@ pub(crate) fn split_pane(
     // Grab the id of the pane root
     let target = parent_query.iter_ancestors(target).nth(1).unwrap();
 
-    let pane = pane_root_query.get(target).unwrap();
-
     let parent = parent_query.get(target).unwrap().parent();
 
     // Find the index of this pane among its siblings
@@ -92,8 +146,8 @@ pub(crate) fn split_pane(
     let mut size = size_query.get_mut(target).unwrap();
     let new_size = if matching_direction { size.0 / 2. } else { 0.5 };
 
-    // TODO The new pane should inherit the state of the existing pane
-    let new_pane = spawn_pane(&mut commands, &theme, new_size, &pane.name).id();
+    // The content of the new pane is created by the registry once it is spawned
+    let new_pane = spawn_pane(&mut commands, &theme, new_size, pane_type).id();
 
     let resize_handle = spawn_resize_handle(&mut commands, divider).id();
 
@@ -109,3 +163,232 @@ pub(crate) fn split_pane(
     }
     size.0 = new_size;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        registry::{PaneAppExt, PaneStructure, PaneTab},
+        PaneLayoutPlugin, PaneRootNode, PaneTabIcon,
+    };
+    use bevy_editor_styles::icons;
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .add_plugins(PaneLayoutPlugin);
+        app.world_mut().spawn(RootPaneLayoutNode);
+        app.update();
+        app
+    }
+
+    fn find_pane(world: &mut World, name: &str) -> PaneStructure {
+        *world
+            .query::<(&PaneRootNode, &PaneStructure)>()
+            .iter(world)
+            .find(|(pane, _)| pane.name == name)
+            .unwrap()
+            .1
+    }
+
+    #[derive(Component)]
+    struct InspectorContent;
+
+    #[test]
+    fn split_pane_adds_pane_of_chosen_type() {
+        let mut app = setup_app();
+        app.register_pane(
+            "Inspector",
+            |pane: In<PaneStructure>, mut commands: Commands| {
+                commands.entity(pane.content).insert(InspectorContent);
+            },
+        );
+        let structure = find_pane(app.world_mut(), "Viewport 3D");
+
+        app.world_mut()
+            .run_system_cached_with(
+                split_pane,
+                (structure.header, false, "Inspector".to_string()),
+            )
+            .unwrap();
+        app.update();
+
+        // The viewport sits in a vertical divider, so it is wrapped in a new horizontal one
+        let world = app.world_mut();
+        let divider = world.get::<ChildOf>(structure.root).unwrap().parent();
+        assert_eq!(world.get::<Divider>(divider), Some(&Divider::Horizontal));
+        let leaves: Vec<(String, f32)> = world
+            .get::<Children>(divider)
+            .unwrap()
+            .iter()
+            .filter_map(|child| {
+                let name = world.get::<PaneRootNode>(child)?.name.clone();
+                Some((name, world.get::<Size>(child)?.0))
+            })
+            .collect();
+        assert_eq!(
+            leaves,
+            vec![
+                ("Viewport 3D".to_string(), 0.5),
+                ("Inspector".to_string(), 0.5)
+            ]
+        );
+
+        let inspector = find_pane(world, "Inspector");
+        assert!(world.get::<InspectorContent>(inspector.content).is_some());
+    }
+
+    #[test]
+    fn split_pane_shows_tab_of_pane_type() {
+        let mut app = setup_app();
+        let accent = Color::srgb(0.9, 0.5, 0.1);
+        app.register_pane_with_tab(
+            "Inspector",
+            PaneTab::new(icons::SLIDERS_HORIZONTAL).with_accent_color(accent),
+            |_: In<PaneStructure>| {},
+        );
+        let structure = find_pane(app.world_mut(), "Viewport 3D");
+
+        app.world_mut()
+            .run_system_cached_with(
+                split_pane,
+                (structure.header, true, "Inspector".to_string()),
+            )
+            .unwrap();
+        app.update();
+
+        let world = app.world_mut();
+        let tab_icon = |world: &mut World, name: &str| {
+            let header = find_pane(world, name).header;
+            let (text, color) = world
+                .query_filtered::<(Entity, &Text, &TextColor), With<PaneTabIcon>>()
+                .iter(world)
+                .find(|(entity, ..)| {
+                    world
+                        .get::<ChildOf>(*entity)
+                        .and_then(|child_of| world.get::<ChildOf>(child_of.parent()))
+                        .is_some_and(|child_of| child_of.parent() == header)
+                })
+                .map(|(_, text, color)| (text.0.clone(), color.0))
+                .unwrap();
+            (text, color)
+        };
+        assert_eq!(
+            tab_icon(world, "Inspector"),
+            (icons::SLIDERS_HORIZONTAL.to_string(), accent)
+        );
+        // Pane types without a registered tab get the neutral one
+        let theme_color = world.resource::<Theme>().pane.tab_icon_color;
+        assert_eq!(
+            tab_icon(world, "Viewport 3D"),
+            (PaneTab::default().icon.to_string(), theme_color)
+        );
+    }
+
+    #[test]
+    fn close_pane_despawns_pane_and_reports_it() {
+        let mut app = setup_app();
+        let world = app.world_mut();
+        let structure = find_pane(world, "Viewport 3D");
+
+        world
+            .run_system_cached_with(remove_pane, structure.header)
+            .unwrap();
+
+        assert!(world.get_entity(structure.root).is_err());
+        assert!(world.get_entity(structure.content).is_err());
+
+        let closed: Vec<Entity> = world
+            .resource::<Events<PaneClosed>>()
+            .iter_current_update_events()
+            .map(|event| event.pane)
+            .collect();
+        assert_eq!(closed, vec![structure.root]);
+    }
+
+    #[test]
+    fn maximize_and_restore_pane() {
+        let mut app = setup_app();
+        let structure = find_pane(app.world_mut(), "Viewport 3D");
+
+        let world = app.world_mut();
+        let initial_sizes: Vec<(Entity, f32)> = world
+            .query::<(Entity, &Size)>()
+            .iter(world)
+            .map(|(entity, size)| (entity, size.0))
+            .collect();
+
+        world
+            .run_system_cached_with(toggle_maximize_pane, structure.header)
+            .unwrap();
+        app.update();
+
+        // The pane and all of its ancestors fill their parents and everything else is hidden
+        let world = app.world_mut();
+        let mut entity = structure.root;
+        while world.get::<RootPaneLayoutNode>(entity).is_none() {
+            let node = world.get::<Node>(entity).unwrap();
+            assert_eq!(node.width, Val::Percent(100.));
+            assert_eq!(node.height, Val::Percent(100.));
+
+            let parent = world.get::<ChildOf>(entity).unwrap().parent();
+            for sibling in world.get::<Children>(parent).unwrap().iter() {
+                if sibling != entity {
+                    assert_eq!(world.get::<Node>(sibling).unwrap().display, Display::None);
+                }
+            }
+            entity = parent;
+        }
+
+        world
+            .run_system_cached_with(toggle_maximize_pane, structure.header)
+            .unwrap();
+        app.update();
+
+        let world = app.world_mut();
+        for (entity, size) in initial_sizes {
+            assert_eq!(world.get::<Size>(entity).unwrap().0, size);
+        }
+        let hidden = world
+            .query::<&Node>()
+            .iter(world)
+            .filter(|node| node.display == Display::None)
+            .count();
+        assert_eq!(hidden, 0);
+    }
+
+    #[test]
+    fn closing_maximized_pane_shows_other_panes() {
+        let mut app = setup_app();
+        let structure = find_pane(app.world_mut(), "Viewport 3D");
+
+        let world = app.world_mut();
+        world
+            .run_system_cached_with(toggle_maximize_pane, structure.header)
+            .unwrap();
+        world
+            .run_system_cached_with(remove_pane, structure.header)
+            .unwrap();
+        app.update();
+
+        let world = app.world_mut();
+        assert!(world.get_entity(structure.root).is_err());
+        assert!(world.resource::<MaximizedPane>().pane.is_none());
+        let hidden = world
+            .query::<&Node>()
+            .iter(world)
+            .filter(|node| node.display == Display::None)
+            .count();
+        assert_eq!(hidden, 0);
+        // The space of the closed pane goes to its neighbors, using the sizes from before it was
+        // maximized
+        let sizes: Vec<f32> = world
+            .query_filtered::<&Size, With<PaneRootNode>>()
+            .iter(world)
+            .map(|size| size.0)
+            .collect();
+        assert!(sizes.iter().all(|&size| size < 1.));
+    }
+}
@@ -0,0 +1,225 @@
+//! Saving and restoring the pane layout.
+//!
+//! The layout is stored as a tree of dividers and panes in RON. Panes are referenced by the name
+//! they were registered with in the [`PaneRegistry`](crate::registry::PaneRegistry), so a layout
+//! that references a pane type that is no longer registered still loads: the pane keeps its slot
+//! and header, but its content is left empty.
+
+use bevy::prelude::*;
+use bevy_editor_styles::Theme;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ui::{spawn_divider, spawn_pane, spawn_resize_handle},
+    Divider, MaximizedPane, PaneRootNode, RootPaneLayoutNode, Size,
+};
+
+/// A node of the serialized layout tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum LayoutNode {
+    /// A divider splitting its space between its children.
+    Divider {
+        direction: Divider,
+        size: f32,
+        children: Vec<LayoutNode>,
+    },
+    /// A pane, referenced by its registered name.
+    Pane { name: String, size: f32 },
+}
+
+/// Serialize the current pane layout to a RON string.
+///
+/// # Panics
+///
+/// Panics if there isn't exactly one [`RootPaneLayoutNode`] in the world.
+pub fn save_layout(world: &mut World) -> String {
+    let root = world
+        .query_filtered::<Entity, With<RootPaneLayoutNode>>()
+        .single(world)
+        .expect("There should be exactly one RootPaneLayoutNode");
+
+    let nodes: Vec<LayoutNode> = world
+        .get::<Children>(root)
+        .into_iter()
+        .flat_map(|children| children.iter())
+        .filter_map(|child| layout_node(world, child))
+        .collect();
+
+    ron::ser::to_string_pretty(&nodes, ron::ser::PrettyConfig::default())
+        .expect("Layout nodes should always be serializable")
+}
+
+/// Replace the current pane layout with one previously produced by [`save_layout`].
+///
+/// The layout is parsed immediately and applied when the commands are flushed.
+pub fn load_layout(commands: &mut Commands, layout: &str) -> Result<(), ron::error::SpannedError> {
+    let nodes: Vec<LayoutNode> = ron::from_str(layout)?;
+    commands.run_system_cached_with(apply_layout, nodes);
+    Ok(())
+}
+
+fn layout_node(world: &World, entity: Entity) -> Option<LayoutNode> {
+    let size = world.get::<Size>(entity).map_or(1., |size| size.0);
+
+    if let Some(pane) = world.get::<PaneRootNode>(entity) {
+        return Some(LayoutNode::Pane {
+            name: pane.name.clone(),
+            size,
+        });
+    }
+
+    // Anything that is neither a pane nor a divider is a resize handle, which is recreated on load.
+    let direction = *world.get::<Divider>(entity)?;
+    let children = world
+        .get::<Children>(entity)
+        .into_iter()
+        .flat_map(|children| children.iter())
+        .filter_map(|child| layout_node(world, child))
+        .collect();
+
+    Some(LayoutNode::Divider {
+        direction,
+        size,
+        children,
+    })
+}
+
+fn apply_layout(
+    In(nodes): In<Vec<LayoutNode>>,
+    mut commands: Commands,
+    theme: Res<Theme>,
+    mut maximized: ResMut<MaximizedPane>,
+    mut size_query: Query<&mut Size>,
+    mut node_query: Query<&mut Node>,
+    root: Single<(Entity, Option<&Children>), With<RootPaneLayoutNode>>,
+) {
+    // The maximized pane is replaced along with the rest of the layout
+    maximized.restore(&mut size_query, &mut node_query);
+    let (root, children) = *root;
+
+    for child in children.into_iter().flat_map(|children| children.iter()) {
+        commands.entity(child).despawn();
+    }
+
+    for node in &nodes {
+        spawn_layout_node(&mut commands, &theme, node, root);
+    }
+}
+
+fn spawn_layout_node(commands: &mut Commands, theme: &Theme, node: &LayoutNode, parent: Entity) {
+    match node {
+        LayoutNode::Pane { name, size } => {
+            spawn_pane(commands, theme, *size, name).insert(ChildOf(parent));
+        }
+        LayoutNode::Divider {
+            direction,
+            size,
+            children,
+        } => {
+            let divider = spawn_divider(commands, *direction, *size)
+                .insert(ChildOf(parent))
+                .id();
+
+            for (index, child) in children.iter().enumerate() {
+                if index != 0 {
+                    spawn_resize_handle(commands, *direction).insert(ChildOf(divider));
+                }
+                spawn_layout_node(commands, theme, child, divider);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Font>()
+            .init_resource::<Theme>()
+            .init_resource::<MaximizedPane>();
+        app
+    }
+
+    fn pane_names(world: &mut World) -> Vec<String> {
+        let mut names: Vec<String> = world
+            .query::<&PaneRootNode>()
+            .iter(world)
+            .map(|pane| pane.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn layout_round_trip() {
+        let mut app = setup_app();
+        let world = app.world_mut();
+        let root = world.spawn(RootPaneLayoutNode).id();
+
+        // Horizontal split with a vertical split on its right side.
+        let nodes = vec![LayoutNode::Divider {
+            direction: Divider::Horizontal,
+            size: 1.,
+            children: vec![
+                LayoutNode::Pane {
+                    name: "Scene Tree".into(),
+                    size: 0.25,
+                },
+                LayoutNode::Divider {
+                    direction: Divider::Vertical,
+                    size: 0.75,
+                    children: vec![
+                        LayoutNode::Pane {
+                            name: "Viewport 3D".into(),
+                            size: 0.5,
+                        },
+                        LayoutNode::Pane {
+                            name: "Asset Browser".into(),
+                            size: 0.5,
+                        },
+                    ],
+                },
+            ],
+        }];
+        let mut commands = world.commands();
+        load_layout(
+            &mut commands,
+            &ron::ser::to_string(&nodes).expect("Layout nodes should be serializable"),
+        )
+        .unwrap();
+        world.flush();
+
+        let saved = save_layout(world);
+        assert_eq!(ron::from_str::<Vec<LayoutNode>>(&saved).unwrap(), nodes);
+
+        // Clear the layout
+        let children = world.get::<Children>(root).unwrap().to_vec();
+        for child in children {
+            world.despawn(child);
+        }
+        assert!(pane_names(world).is_empty());
+
+        let mut commands = world.commands();
+        load_layout(&mut commands, &saved).unwrap();
+        world.flush();
+
+        assert_eq!(save_layout(world), saved);
+        assert_eq!(
+            pane_names(world),
+            vec!["Asset Browser", "Scene Tree", "Viewport 3D"]
+        );
+    }
+
+    #[test]
+    fn invalid_layout_is_rejected() {
+        let mut app = setup_app();
+        let world = app.world_mut();
+        world.spawn(RootPaneLayoutNode);
+
+        let mut commands = world.commands();
+        assert!(load_layout(&mut commands, "not a layout").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! Resizable, divider-able panes for Bevy.
 
 mod handlers;
+pub mod layout;
 mod pane_drop_area;
 pub mod registry;
 mod ui;
@@ -19,18 +20,22 @@ mod ui;
 ///   - If a pane can not be sensibly resized, it can overflow under the other panes.
 /// - Panes must not interfere with each other, only temporary/absolute positioned elements are allowed to overlap panes.
 use bevy::prelude::*;
-use bevy_editor_styles::Theme;
+use bevy_editor_styles::{StylesPlugin, Theme};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     registry::PaneRegistryPlugin,
-    ui::{spawn_divider, spawn_pane, spawn_resize_handle},
+    ui::{
+        add_header_context_menus, spawn_divider, spawn_pane, spawn_resize_handle,
+        update_pane_tab_icons,
+    },
 };
 
 /// Crate prelude.
 pub mod prelude {
     pub use crate::{
-        registry::{PaneAppExt, PaneStructure},
-        PaneAreaNode, PaneContentNode, PaneHeaderNode,
+        registry::{PaneAppExt, PaneStructure, PaneTab},
+        PaneAreaNode, PaneClosed, PaneContentNode, PaneHeaderNode, PaneTabIcon,
     };
 }
 
@@ -39,12 +44,24 @@ pub struct PaneLayoutPlugin;
 
 impl Plugin for PaneLayoutPlugin {
     fn build(&self, app: &mut App) {
+        // Panes are styled from the theme, fall back to the default one
+        if !app.is_plugin_added::<StylesPlugin>() {
+            app.add_plugins(StylesPlugin);
+        }
+
         app.add_plugins(PaneRegistryPlugin)
             .init_resource::<DragState>()
+            .init_resource::<MaximizedPane>()
+            .add_event::<PaneClosed>()
             .add_systems(Startup, setup.in_set(PaneLayoutSet))
             .add_systems(
                 Update,
-                (cleanup_divider_single_child, apply_size)
+                (
+                    add_header_context_menus,
+                    update_pane_tab_icons,
+                    cleanup_divider_single_child,
+                    apply_size,
+                )
                     .chain()
                     .in_set(PaneLayoutSet),
             );
@@ -86,12 +103,44 @@ struct DragState {
     parent_node_size: f32,
 }
 
+/// The pane that currently fills the whole layout, along with what is needed to restore the layout.
+#[derive(Resource, Default)]
+struct MaximizedPane {
+    pane: Option<Entity>,
+    /// Sizes of the pane and its ancestors before they were expanded.
+    sizes: Vec<(Entity, f32)>,
+    /// Nodes along the way to the root that were hidden, with their previous display.
+    hidden: Vec<(Entity, Display)>,
+}
+
+impl MaximizedPane {
+    /// Restores the sizes and displays changed by maximizing a pane, returning the pane that was
+    /// maximized.
+    fn restore(
+        &mut self,
+        size_query: &mut Query<&mut Size>,
+        node_query: &mut Query<&mut Node>,
+    ) -> Option<Entity> {
+        for (entity, size) in self.sizes.drain(..) {
+            if let Ok(mut current) = size_query.get_mut(entity) {
+                current.0 = size;
+            }
+        }
+        for (entity, display) in self.hidden.drain(..) {
+            if let Ok(mut node) = node_query.get_mut(entity) {
+                node.display = display;
+            }
+        }
+        self.pane.take()
+    }
+}
+
 /// System Set to set up the Pane Layout.
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PaneLayoutSet;
 
-// TODO There is no way to save or load layouts at this moment.
-// The setup system currently just creates a default layout at startup.
+// The setup system creates a default layout at startup.
+// A saved layout can replace it afterwards through `layout::load_layout`.
 fn setup(
     mut commands: Commands,
     theme: Res<Theme>,
@@ -163,7 +212,7 @@ fn cleanup_divider_single_child(
 }
 
 /// A node that divides an area into multiple areas along an axis.
-#[derive(Component, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 enum Divider {
     Horizontal,
     Vertical,
@@ -176,6 +225,13 @@ struct ResizeHandle;
 #[derive(Component)]
 struct Size(f32);
 
+/// Sent after a pane has been closed and its root entity despawned.
+#[derive(Event, BufferedEvent, Clone, Copy, Debug)]
+pub struct PaneClosed {
+    /// The root entity of the closed pane.
+    pub pane: Entity,
+}
+
 /// Root node to capture all editor UI elements, nothing but the layout system should modify this.
 #[derive(Component)]
 pub struct RootPaneLayoutNode;
@@ -197,3 +253,26 @@ pub struct PaneHeaderNode;
 /// Node to denote the content space of the Pane.
 #[derive(Component)]
 pub struct PaneContentNode;
+
+/// Icon in the header of a Pane, showing the [`PaneTab`](registry::PaneTab) of its pane type.
+#[derive(Component)]
+pub struct PaneTabIcon;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_layout_without_styles_plugin() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), PaneLayoutPlugin))
+            .init_asset::<Font>();
+        app.world_mut().spawn(RootPaneLayoutNode);
+        app.update();
+
+        assert!(app.world().contains_resource::<Theme>());
+        let world = app.world_mut();
+        let panes = world.query::<&PaneRootNode>().iter(world).count();
+        assert_eq!(panes, 4);
+    }
+}
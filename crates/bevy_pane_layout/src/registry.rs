@@ -5,6 +5,7 @@ use bevy::{
     platform::collections::HashMap,
     prelude::*,
 };
+use bevy_editor_styles::icons;
 
 use crate::{PaneLayoutSet, PaneRootNode};
 
@@ -36,22 +37,79 @@ pub struct PaneStructure {
     pub content: Entity,
 }
 
+/// How the header of a pane type is shown, so panes can be told apart at a glance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaneTab {
+    /// The icon shown before the name of the pane, one of [`icons`].
+    pub icon: &'static str,
+    /// The color of the icon, the tab icon color of the
+    /// [`Theme`](bevy_editor_styles::Theme) when `None`.
+    pub accent_color: Option<Color>,
+}
+
+impl PaneTab {
+    /// A tab showing `icon` in the neutral color of the theme.
+    pub fn new(icon: &'static str) -> Self {
+        Self {
+            icon,
+            accent_color: None,
+        }
+    }
+
+    /// Colors the icon with `color` instead of the neutral color of the theme.
+    pub fn with_accent_color(mut self, color: Color) -> Self {
+        self.accent_color = Some(color);
+        self
+    }
+}
+
+impl Default for PaneTab {
+    fn default() -> Self {
+        Self::new(icons::APP_WINDOW)
+    }
+}
+
 impl PaneRegistry {
-    /// Register a new pane type.
+    /// Register a new pane type, with the default [`PaneTab`].
     pub fn register<M>(
         &mut self,
         name: impl Into<String>,
         system: impl IntoSystem<In<PaneStructure>, (), M>,
+    ) {
+        self.register_with_tab(name, PaneTab::default(), system);
+    }
+
+    /// Register a new pane type, showing `tab` in the header of its panes.
+    pub fn register_with_tab<M>(
+        &mut self,
+        name: impl Into<String>,
+        tab: PaneTab,
+        system: impl IntoSystem<In<PaneStructure>, (), M>,
     ) {
         self.panes.push(Pane {
             name: name.into(),
+            tab,
             creation_callback: Some(Box::new(IntoSystem::into_system(system))),
         });
     }
+
+    /// The names of the registered pane types, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.panes.iter().map(|pane| pane.name.as_str())
+    }
+
+    /// The tab of the pane type named `name`, if it is registered.
+    pub fn tab(&self, name: &str) -> Option<&PaneTab> {
+        self.panes
+            .iter()
+            .find(|pane| pane.name == name)
+            .map(|pane| &pane.tab)
+    }
 }
 
 struct Pane {
     name: String,
+    tab: PaneTab,
     creation_callback: Option<BoxedSystem<In<PaneStructure>>>,
 }
 
@@ -94,6 +152,14 @@ pub trait PaneAppExt {
         name: impl Into<String>,
         system: impl IntoSystem<In<PaneStructure>, (), M>,
     ) -> &mut Self;
+
+    /// Register a new pane type, showing `tab` in the header of its panes.
+    fn register_pane_with_tab<M>(
+        &mut self,
+        name: impl Into<String>,
+        tab: PaneTab,
+        system: impl IntoSystem<In<PaneStructure>, (), M>,
+    ) -> &mut Self;
 }
 
 impl PaneAppExt for App {
@@ -108,4 +174,17 @@ impl PaneAppExt for App {
 
         self
     }
+
+    fn register_pane_with_tab<M>(
+        &mut self,
+        name: impl Into<String>,
+        tab: PaneTab,
+        system: impl IntoSystem<In<PaneStructure>, (), M>,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<PaneRegistry>()
+            .register_with_tab(name, tab, system);
+
+        self
+    }
 }
@@ -1,12 +1,19 @@
+use std::time::Duration;
+
 use bevy::{prelude::*, window::SystemCursorIcon, winit::cursor::CursorIcon};
 use bevy_context_menu::{ContextMenu, ContextMenuOption};
 use bevy_editor_styles::{icons, Theme};
 
 use crate::{
-    handlers::*, registry::PaneStructure, Divider, DragState, PaneAreaNode, PaneContentNode,
-    PaneHeaderNode, PaneRootNode, ResizeHandle, Size,
+    handlers::*,
+    registry::{PaneRegistry, PaneStructure},
+    Divider, DragState, PaneAreaNode, PaneContentNode, PaneHeaderNode, PaneRootNode, PaneTabIcon,
+    ResizeHandle, Size,
 };
 
+/// The maximum time between two clicks on a pane header for them to count as a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
 pub(crate) fn spawn_pane<'a>(
     commands: &'a mut Commands,
     theme: &Theme,
@@ -57,20 +64,28 @@ pub(crate) fn spawn_pane<'a>(
             },
             theme.pane.header_background_color,
             theme.pane.header_border_radius,
-            ContextMenu::new([
-                ContextMenuOption::new("Close", |mut commands, entity| {
-                    commands.run_system_cached_with(remove_pane, entity);
-                }),
-                ContextMenuOption::new("Split - Horizontal", |mut commands, entity| {
-                    commands.run_system_cached_with(split_pane, (entity, false));
-                }),
-                ContextMenuOption::new("Split - Vertical", |mut commands, entity| {
-                    commands.run_system_cached_with(split_pane, (entity, true));
-                }),
-            ]),
             PaneHeaderNode,
             ChildOf(area),
         ))
+        .observe(
+            |trigger: On<Pointer<Click>>,
+             time: Res<Time<Real>>,
+             mut last_click: Local<Option<Duration>>,
+             mut commands: Commands| {
+                if trigger.event().button != PointerButton::Primary {
+                    return;
+                }
+
+                let now = time.elapsed();
+                match *last_click {
+                    Some(last) if now - last <= DOUBLE_CLICK_TIME => {
+                        *last_click = None;
+                        commands.run_system_cached_with(toggle_maximize_pane, trigger.target());
+                    }
+                    _ => *last_click = Some(now),
+                }
+            },
+        )
         .observe(
             move |_trigger: On<Pointer<Move>>,
                   window_query: Query<Entity, With<Window>>,
@@ -109,6 +124,20 @@ pub(crate) fn spawn_pane<'a>(
                             ..default()
                         },
                     ));
+                    // Filled in from the registry by `update_pane_tab_icons`
+                    parent.spawn((
+                        Text::default(),
+                        TextFont {
+                            font: theme.icon.font.clone(),
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        Node {
+                            margin: UiRect::left(Val::Px(4.)),
+                            ..default()
+                        },
+                        PaneTabIcon,
+                    ));
                     parent.spawn((
                         Text::new(format!(" {name}")),
                         TextFont {
@@ -119,14 +148,50 @@ pub(crate) fn spawn_pane<'a>(
                     ));
                 });
 
-            parent.spawn((
-                Text::new(icons::GRIP_VERTICAL),
-                TextFont {
-                    font: theme.icon.font.clone(),
-                    font_size: 16.0,
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(4.),
+                    flex_shrink: 0.0,
                     ..default()
-                },
-            ));
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(icons::GRIP_VERTICAL),
+                        TextFont {
+                            font: theme.icon.font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                    ));
+                    // Close button, closes the pane the same way as the context menu option.
+                    parent
+                        .spawn((
+                            Text::new(icons::X),
+                            TextFont {
+                                font: theme.icon.font.clone(),
+                                font_size: 16.0,
+                                ..default()
+                            },
+                        ))
+                        .observe(
+                            |mut trigger: On<Pointer<Click>>,
+                             parent_query: Query<&ChildOf>,
+                             mut commands: Commands| {
+                                if trigger.event().button != PointerButton::Primary {
+                                    return;
+                                }
+                                trigger.propagate(false);
+
+                                // Walk up from the button to the header
+                                let header = parent_query
+                                    .iter_ancestors(trigger.target())
+                                    .nth(1)
+                                    .unwrap();
+                                commands.run_system_cached_with(remove_pane, header);
+                            },
+                        );
+                });
         })
         .id();
 
@@ -152,6 +217,62 @@ pub(crate) fn spawn_pane<'a>(
     commands.entity(root)
 }
 
+/// Gives new pane headers their context menu, with options to split the pane for each registered
+/// pane type.
+pub(crate) fn add_header_context_menus(
+    mut commands: Commands,
+    headers: Query<Entity, Added<PaneHeaderNode>>,
+    pane_registry: Res<PaneRegistry>,
+) {
+    for header in &headers {
+        let mut options = vec![
+            ContextMenuOption::new("Close", |mut commands, entity| {
+                commands.run_system_cached_with(remove_pane, entity);
+            }),
+            ContextMenuOption::new("Maximize / Restore", |mut commands, entity| {
+                commands.run_system_cached_with(toggle_maximize_pane, entity);
+            }),
+        ];
+        for (label, vertical) in [("Split Horizontally", false), ("Split Vertically", true)] {
+            options.extend(pane_registry.names().map(|name| {
+                let pane_type = name.to_string();
+                ContextMenuOption::new(format!("{label}: {name}"), move |mut commands, entity| {
+                    commands
+                        .run_system_cached_with(split_pane, (entity, vertical, pane_type.clone()));
+                })
+            }));
+        }
+
+        commands.entity(header).insert(ContextMenu::new(options));
+    }
+}
+
+/// Shows the icon and accent color of their pane type in new pane headers, or the default
+/// [`PaneTab`](crate::registry::PaneTab) for unregistered pane types.
+pub(crate) fn update_pane_tab_icons(
+    mut icons_query: Query<(Entity, &mut Text, &mut TextColor), Added<PaneTabIcon>>,
+    parent_query: Query<&ChildOf>,
+    pane_root_query: Query<&PaneRootNode>,
+    pane_registry: Res<PaneRegistry>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut text, mut color) in &mut icons_query {
+        let Some(pane_root) = parent_query
+            .iter_ancestors(entity)
+            .find_map(|ancestor| pane_root_query.get(ancestor).ok())
+        else {
+            continue;
+        };
+
+        let tab = pane_registry
+            .tab(&pane_root.name)
+            .cloned()
+            .unwrap_or_default();
+        text.0 = tab.icon.to_string();
+        color.0 = tab.accent_color.unwrap_or(theme.pane.tab_icon_color);
+    }
+}
+
 pub(crate) fn spawn_divider<'a>(
     commands: &'a mut Commands,
     divider: Divider,
@@ -1 +1,208 @@
 //! Gizmos used for the user interface to manipulate transforms.
+
+use bevy::prelude::*;
+use bevy_editor_settings::{SettingKey, SettingsType};
+
+/// Plugin for the transform gizmos.
+pub struct TransformGizmoPlugin;
+
+impl Plugin for TransformGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GizmoSnapSettings>()
+            .register_type::<GridSnapConfig>()
+            .init_resource::<GizmoSnapSettings>()
+            .init_resource::<GridSnapConfig>();
+    }
+}
+
+/// The increment translations snap to, in world units.
+///
+/// This is independent of the spacing of the viewport grids, so a fine grid can be shown while
+/// snapping to coarser steps. It is a workspace setting, changed from the settings pane.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource, @SettingsType::Workspace, @SettingKey("grid_snap"))]
+pub struct GridSnapConfig {
+    /// The translation increment, in world units.
+    pub increment: f32,
+}
+
+impl Default for GridSnapConfig {
+    fn default() -> Self {
+        Self { increment: 0.5 }
+    }
+}
+
+impl GridSnapConfig {
+    /// Snaps a translation to the increment.
+    pub fn snap(&self, translation: Vec3) -> Vec3 {
+        snap(translation, self.increment)
+    }
+}
+
+/// Increments that gizmo rotations and scales snap to. Translations snap to the
+/// [`GridSnapConfig`] increment.
+///
+/// Snapping is applied to the transform committed at the end of a drag, so the drag itself stays
+/// smooth.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource, @SettingsType::Workspace, @SettingKey("gizmo_snap"))]
+pub struct GizmoSnapSettings {
+    /// Snap without holding the snap modifier. Holding it inverts this.
+    pub enabled: bool,
+    /// The angle increment rotations snap to, in degrees.
+    pub rotation: f32,
+    /// The increment scales snap to.
+    pub scale: f32,
+}
+
+impl Default for GizmoSnapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rotation: 15.,
+            scale: 0.1,
+        }
+    }
+}
+
+impl GizmoSnapSettings {
+    /// The keys that toggle snapping while they are held.
+    pub const MODIFIERS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+
+    /// Whether manipulations snap, taking the held snap modifier into account.
+    pub fn is_active(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        self.enabled != keyboard.any_pressed(Self::MODIFIERS)
+    }
+
+    /// Snaps a rotation made from `start` so that the angle rotated by is a multiple of the angle
+    /// increment.
+    pub fn snap_rotation(&self, start: Quat, rotation: Quat) -> Quat {
+        let increment = self.rotation.to_radians();
+        if increment <= 0. {
+            return rotation;
+        }
+
+        let (axis, angle) = (rotation * start.inverse()).to_axis_angle();
+        let angle = (angle / increment).round() * increment;
+        (Quat::from_axis_angle(axis, angle) * start).normalize()
+    }
+
+    /// Snaps a scale to the scale increment, never snapping an axis down to zero.
+    pub fn snap_scale(&self, scale: Vec3) -> Vec3 {
+        if self.scale <= 0. {
+            return scale;
+        }
+
+        let snapped = snap(scale, self.scale);
+        // A zero scale can't be scaled back up, keep it at the smallest increment instead
+        Vec3::select(
+            snapped.cmpeq(Vec3::ZERO),
+            scale.signum() * self.scale,
+            snapped,
+        )
+    }
+
+    /// Snaps the transform committed by a manipulation that started at `start`, with translations
+    /// snapping to `grid`.
+    pub fn snap_transform(
+        &self,
+        grid: &GridSnapConfig,
+        start: &Transform,
+        transform: &Transform,
+    ) -> Transform {
+        Transform {
+            translation: if transform.translation == start.translation {
+                transform.translation
+            } else {
+                grid.snap(transform.translation)
+            },
+            rotation: if transform.rotation == start.rotation {
+                transform.rotation
+            } else {
+                self.snap_rotation(start.rotation, transform.rotation)
+            },
+            scale: if transform.scale == start.scale {
+                transform.scale
+            } else {
+                self.snap_scale(transform.scale)
+            },
+        }
+    }
+}
+
+fn snap(value: Vec3, increment: f32) -> Vec3 {
+    if increment <= 0. {
+        return value;
+    }
+    (value / increment).round() * increment
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    const GRID: GridSnapConfig = GridSnapConfig { increment: 0.5 };
+
+    fn settings() -> GizmoSnapSettings {
+        GizmoSnapSettings {
+            enabled: true,
+            rotation: 15.,
+            scale: 0.25,
+        }
+    }
+
+    #[test]
+    fn translation_snaps_to_grid_increment() {
+        let start = Transform::from_xyz(0.1, 0., 0.);
+        let dragged = start.with_translation(Vec3::new(1.3, -0.74, 2.));
+
+        let committed = settings().snap_transform(&GRID, &start, &dragged);
+        assert_eq!(committed.translation, Vec3::new(1.5, -0.5, 2.));
+        assert_eq!(committed.rotation, start.rotation);
+        assert_eq!(committed.scale, start.scale);
+
+        // Coarser increments than the visible grid lines
+        let coarse = GridSnapConfig { increment: 10. };
+        let committed = settings().snap_transform(&coarse, &start, &dragged);
+        assert_eq!(committed.translation, Vec3::new(0., 0., 0.));
+        let dragged = start.with_translation(Vec3::new(14.9, 5.1, -6.));
+        let committed = settings().snap_transform(&coarse, &start, &dragged);
+        assert_eq!(committed.translation, Vec3::new(10., 10., -10.));
+    }
+
+    #[test]
+    fn rotation_snaps_to_angle_increment() {
+        let start = Transform::from_rotation(Quat::from_rotation_y(FRAC_PI_2));
+        let dragged = start.with_rotation(Quat::from_rotation_y(FRAC_PI_2 + 37f32.to_radians()));
+
+        let committed = settings().snap_transform(&GRID, &start, &dragged);
+        let expected = Quat::from_rotation_y(FRAC_PI_2 + 30f32.to_radians());
+        assert!(committed.rotation.angle_between(expected) < 1e-4);
+        assert_eq!(committed.translation, start.translation);
+    }
+
+    #[test]
+    fn scale_snaps_to_step_increment() {
+        let start = Transform::default();
+        let dragged = start.with_scale(Vec3::new(1.6, 0.05, -0.9));
+
+        let committed = settings().snap_transform(&GRID, &start, &dragged);
+        assert_eq!(committed.scale, Vec3::new(1.5, 0.25, -1.));
+        assert_eq!(committed.translation, start.translation);
+    }
+
+    #[test]
+    fn modifier_inverts_snapping() {
+        let mut keyboard = ButtonInput::<KeyCode>::default();
+        let mut settings = settings();
+        assert!(settings.is_active(&keyboard));
+
+        keyboard.press(KeyCode::ShiftLeft);
+        assert!(!settings.is_active(&keyboard));
+
+        settings.enabled = false;
+        assert!(settings.is_active(&keyboard));
+    }
+}
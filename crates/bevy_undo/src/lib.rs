@@ -132,9 +132,9 @@
 
 // Remove after update to newer rust version
 #![allow(clippy::type_complexity)]
-use std::sync::Arc;
+use std::{any::TypeId, sync::Arc};
 
-use bevy::{platform::collections::HashMap, prelude::*};
+use bevy::{ecs::entity::EntityHashMap, platform::collections::HashMap, prelude::*};
 
 const MAX_REFLECT_RECURSION: i32 = 10;
 const AUTO_UNDO_LATENCY: i32 = 2;
@@ -359,27 +359,9 @@ fn undo_redo_logic(world: &mut World) {
                 let mut reader = events.get_cursor();
                 for event in reader.read(&events) {
                     match event {
-                        UndoRedo::Undo => {
-                            if let Some(change) = change_chain.changes.pop() {
-                                let res = change.revert(world, &change_chain.entity_remap).unwrap();
-                                if let ChangeResult::SuccessWithRemap(remap) = res {
-                                    change_chain.entity_remap.extend(remap);
-                                }
-                                change_chain.changes_for_redo.push(change);
-                            }
-                        }
-                        UndoRedo::Redo => {
-                            if let Some(change) = change_chain.changes_for_redo.pop() {
-                                let inverse_change = change.get_inverse();
-                                let res = inverse_change
-                                    .revert(world, &change_chain.entity_remap)
-                                    .unwrap();
-                                if let ChangeResult::SuccessWithRemap(remap) = res {
-                                    change_chain.entity_remap.extend(remap);
-                                }
-                                change_chain.changes.push(change);
-                            }
-                        }
+                        UndoRedo::Undo => change_chain.undo(world),
+                        UndoRedo::Redo => change_chain.redo(world),
+                        UndoRedo::JumpTo(applied) => change_chain.jump_to(world, *applied),
                     }
                 }
             }
@@ -418,25 +400,69 @@ impl Default for ChangeChainSettings {
 }
 
 impl ChangeChain {
-    /// Undo last registered change
+    /// Undo last registered change.
+    /// A change that can no longer be reverted, like one of a despawned entity, is logged and dropped.
     pub fn undo(&mut self, world: &mut World) {
-        if let Some(change) = self.changes.pop() {
-            let res = change.revert(world, &self.entity_remap).unwrap();
-            self.changes_for_redo.push(change);
-            self.update_remap(res);
+        if let Err(err) = self.try_undo(world) {
+            if let Some(change) = self.changes.pop() {
+                warn!("Dropped {} from undo: {err}", change.debug_text());
+            }
         }
     }
 
-    /// Redo last undone change
+    /// Redo last undone change.
+    /// A change that can no longer be applied is logged and dropped.
     pub fn redo(&mut self, world: &mut World) {
-        if let Some(change) = self.changes_for_redo.pop() {
-            let inverse_change = change.get_inverse();
-            let res = inverse_change.revert(world, &self.entity_remap).unwrap();
-            self.changes.push(change);
-            self.update_remap(res);
+        if let Err(err) = self.try_redo(world) {
+            if let Some(change) = self.changes_for_redo.pop() {
+                warn!("Dropped {} from redo: {err}", change.debug_text());
+            }
         }
     }
 
+    /// Undo or redo changes until `applied` changes are applied, all at once.
+    /// Jumping to `0` undoes every change, jumping past the last change redoes every change.
+    /// A change that fails stops the jump at the last change that could be undone or redone,
+    /// leaving the failed change in the chain.
+    pub fn jump_to(&mut self, world: &mut World, applied: usize) {
+        while self.changes.len() > applied {
+            if let Err(err) = self.try_undo(world) {
+                warn!("Stopped jumping in the undo history: {err}");
+                return;
+            }
+        }
+        while self.changes.len() < applied && !self.changes_for_redo.is_empty() {
+            if let Err(err) = self.try_redo(world) {
+                warn!("Stopped jumping in the undo history: {err}");
+                return;
+            }
+        }
+    }
+
+    /// Reverts the last applied change, leaving the chain unchanged when it fails.
+    fn try_undo(&mut self, world: &mut World) -> Result<(), String> {
+        let Some(change) = self.changes.last().cloned() else {
+            return Ok(());
+        };
+        let res = change.revert(world, &self.entity_remap)?;
+        self.changes.pop();
+        self.changes_for_redo.push(change);
+        self.update_remap(res);
+        Ok(())
+    }
+
+    /// Applies the last undone change again, leaving the chain unchanged when it fails.
+    fn try_redo(&mut self, world: &mut World) -> Result<(), String> {
+        let Some(change) = self.changes_for_redo.last().cloned() else {
+            return Ok(());
+        };
+        let res = change.get_inverse().revert(world, &self.entity_remap)?;
+        self.changes_for_redo.pop();
+        self.changes.push(change);
+        self.update_remap(res);
+        Ok(())
+    }
+
     /// Update destroyed-entity->new-entity mapping for handling entities links after undo / redo
     fn update_remap(&mut self, result: ChangeResult) {
         match result {
@@ -467,6 +493,12 @@ pub trait EditorChange {
     /// Returns a human-readable text describing the change
     fn debug_text(&self) -> String;
 
+    /// Returns a short description of the change for users, like "Add Component Transform",
+    /// shown in undo history lists. Defaults to the [`debug_text`](Self::debug_text).
+    fn description(&self) -> String {
+        self.debug_text()
+    }
+
     /// Returns the inverse of this change.
     /// For example:
     /// for `spawn()` -> `despawn()`
@@ -492,6 +524,10 @@ pub enum UndoRedo {
 
     /// Requests to redo the last undone change in the change chain.
     Redo,
+
+    /// Requests to undo or redo changes until the given number of changes is applied,
+    /// see [`ChangeChain::jump_to`].
+    JumpTo(usize),
 }
 
 /// Represents a new change to be added to the change chain.
@@ -539,6 +575,10 @@ impl EditorChange for AddedEntity {
         format!("Added Entity: {}", self.entity.index())
     }
 
+    fn description(&self) -> String {
+        "Add Entity".to_string()
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(RemovedEntity {
             entity: self.entity,
@@ -587,6 +627,10 @@ impl EditorChange for RemovedEntity {
         format!("Removed Entity: {}", self.entity.index())
     }
 
+    fn description(&self) -> String {
+        "Remove Entity".to_string()
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(AddedEntity {
             entity: self.entity,
@@ -594,6 +638,276 @@ impl EditorChange for RemovedEntity {
     }
 }
 
+/// Represents the removal of entities along with all of their descendants.
+///
+/// The removed entities are stored as a [`DynamicScene`], so reverting the change respawns them
+/// with their reflected components, and references between them are remapped to the respawned
+/// entities. Components that aren't registered with [`ReflectComponent`] are not restored.
+pub struct RemovedEntityTree {
+    /// The removed root entities, their descendants were removed along with them.
+    pub roots: Vec<Entity>,
+    /// The removed entities and their components.
+    pub scene: Arc<DynamicScene>,
+}
+
+impl RemovedEntityTree {
+    /// Despawns `roots` and their descendants from `world`, returning the change to record.
+    /// Roots that are descendants of other roots are removed along with their ancestor.
+    pub fn despawn(world: &mut World, roots: &[Entity]) -> Self {
+        let roots: Vec<Entity> = roots
+            .iter()
+            .copied()
+            .filter(|&root| world.get_entity(root).is_ok())
+            .filter(|&root| {
+                let mut parent = world.get::<ChildOf>(root);
+                while let Some(child_of) = parent {
+                    if roots.contains(&child_of.parent()) {
+                        return false;
+                    }
+                    parent = world.get::<ChildOf>(child_of.parent());
+                }
+                true
+            })
+            .collect();
+
+        let mut entities = roots.clone();
+        let mut index = 0;
+        while let Some(&entity) = entities.get(index) {
+            if let Some(children) = world.get::<Children>(entity) {
+                entities.extend(children.iter());
+            }
+            index += 1;
+        }
+        let scene = DynamicSceneBuilder::from_world(world)
+            // Children is rebuilt from ChildOf when the entities are respawned
+            .deny_component::<Children>()
+            .extract_entities(entities.into_iter())
+            .build();
+
+        for &root in &roots {
+            world.entity_mut(root).despawn();
+        }
+
+        Self {
+            roots,
+            scene: Arc::new(scene),
+        }
+    }
+}
+
+impl EditorChange for RemovedEntityTree {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        // References to entities outside of the tree point to the entities they are now
+        let mut entity_map: EntityHashMap<Entity> = world
+            .query::<Entity>()
+            .iter(world)
+            .map(|entity| (entity, entity))
+            .collect();
+        entity_map.extend(entity_remap.iter().map(|(from, to)| (*from, *to)));
+        for entity in &self.scene.entities {
+            entity_map.remove(&entity.entity);
+        }
+
+        self.scene
+            .write_to_world(world, &mut entity_map)
+            .map_err(|error| format!("Unable to respawn removed entities: {error}"))?;
+
+        let remap: Vec<(Entity, Entity)> = self
+            .scene
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity_map[&entity.entity]))
+            .collect();
+        for (_, respawned) in &remap {
+            world
+                .entity_mut(*respawned)
+                .insert(OneFrameUndoIgnore::default());
+        }
+
+        info!("Reverted RemovedEntityTree of {} entities", remap.len());
+
+        Ok(ChangeResult::SuccessWithRemap(remap))
+    }
+
+    fn debug_text(&self) -> String {
+        format!("Removed Entity Tree: {:?}", self.roots)
+    }
+
+    fn description(&self) -> String {
+        match self.roots.len() {
+            1 => "Remove Entity".to_string(),
+            count => format!("Remove {count} Entities"),
+        }
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(AddedEntityTree {
+            roots: self.roots.clone(),
+            scene: self.scene.clone(),
+        })
+    }
+}
+
+/// The inverse of [`RemovedEntityTree`], despawning the respawned entities again.
+pub struct AddedEntityTree {
+    /// The respawned root entities, despawning them also despawns their descendants.
+    pub roots: Vec<Entity>,
+    /// The respawned entities and their components.
+    pub scene: Arc<DynamicScene>,
+}
+
+impl EditorChange for AddedEntityTree {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        for &root in &self.roots {
+            let e = get_entity_with_remap(root, entity_remap);
+            if let Ok(entity) = world.get_entity_mut(e) {
+                entity.despawn();
+            }
+        }
+
+        info!("Reverted AddedEntityTree: {:?}", self.roots);
+
+        Ok(ChangeResult::Success)
+    }
+
+    fn debug_text(&self) -> String {
+        format!("Added Entity Tree: {:?}", self.roots)
+    }
+
+    fn description(&self) -> String {
+        match self.roots.len() {
+            1 => "Add Entity".to_string(),
+            count => format!("Add {count} Entities"),
+        }
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(RemovedEntityTree {
+            roots: self.roots.clone(),
+            scene: self.scene.clone(),
+        })
+    }
+}
+
+/// Represents a change of the parent of an entity.
+///
+/// This struct is used to move an entity back to its previous place in the hierarchy,
+/// keeping its world transform when the change is reverted.
+pub struct ReparentedEntity {
+    /// The ID of the entity that was moved in the hierarchy.
+    pub entity: Entity,
+    /// The parent before the change, `None` if the entity was a root entity.
+    pub old_parent: Option<Entity>,
+    /// The parent after the change, `None` if the entity became a root entity.
+    pub new_parent: Option<Entity>,
+}
+
+impl EditorChange for ReparentedEntity {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        let e = get_entity_with_remap(self.entity, entity_remap);
+        let old_parent = self
+            .old_parent
+            .map(|parent| get_entity_with_remap(parent, entity_remap));
+
+        if let Some(parent) = old_parent {
+            if world.get_entity(parent).is_err() {
+                return Err(format!("Parent {parent} of entity {e} no longer exists"));
+            }
+        }
+        let Ok(mut entity) = world.get_entity_mut(e) else {
+            return Err(format!("Entity {e} no longer exists"));
+        };
+
+        match old_parent {
+            Some(parent) => entity.set_parent_in_place(parent),
+            None => entity.remove_parent_in_place(),
+        };
+        entity.insert(OneFrameUndoIgnore::default());
+
+        info!("Reverted ReparentedEntity for entity: {}", e.index());
+        Ok(ChangeResult::Success)
+    }
+
+    fn debug_text(&self) -> String {
+        format!("Reparented Entity: {}", self.entity.index())
+    }
+
+    fn description(&self) -> String {
+        "Reparent Entity".to_string()
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(ReparentedEntity {
+            entity: self.entity,
+            old_parent: self.new_parent,
+            new_parent: self.old_parent,
+        })
+    }
+}
+
+/// Represents a change of the [`Name`] of an entity.
+///
+/// This struct is used to restore the previous name, or to remove the name again if the
+/// entity didn't have one before the change.
+pub struct RenamedEntity {
+    /// The ID of the entity that was renamed.
+    pub entity: Entity,
+    /// The name before the change, `None` if the entity had no name.
+    pub old_name: Option<Name>,
+    /// The name after the change.
+    pub new_name: Option<Name>,
+}
+
+impl EditorChange for RenamedEntity {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        let e = get_entity_with_remap(self.entity, entity_remap);
+        let Ok(mut entity) = world.get_entity_mut(e) else {
+            return Err(format!("Entity {e} no longer exists"));
+        };
+
+        match &self.old_name {
+            Some(name) => entity.insert(name.clone()),
+            None => entity.remove::<Name>(),
+        };
+        entity.insert(OneFrameUndoIgnore::default());
+
+        info!("Reverted RenamedEntity for entity: {}", e.index());
+        Ok(ChangeResult::Success)
+    }
+
+    fn debug_text(&self) -> String {
+        format!("Renamed Entity: {}", self.entity.index())
+    }
+
+    fn description(&self) -> String {
+        "Rename Entity".to_string()
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(RenamedEntity {
+            entity: self.entity,
+            old_name: self.new_name.clone(),
+            new_name: self.old_name.clone(),
+        })
+    }
+}
+
 /// Represents an changing a component in an entity.
 ///
 /// This struct stores both the old and new values of a component, as well as
@@ -611,6 +925,17 @@ pub struct ComponentChange<T: Component> {
     entity: Entity,
 }
 
+impl<T: Component> ComponentChange<T> {
+    /// Creates a change of the component of `entity` from `old_value` to `new_value`.
+    pub fn new(entity: Entity, old_value: T, new_value: T) -> Self {
+        Self {
+            old_value,
+            new_value,
+            entity,
+        }
+    }
+}
+
 impl<T: Component + Clone> EditorChange for ComponentChange<T> {
     fn revert(
         &self,
@@ -618,9 +943,11 @@ impl<T: Component + Clone> EditorChange for ComponentChange<T> {
         entity_remap: &HashMap<Entity, Entity>,
     ) -> Result<ChangeResult, String> {
         let e = get_entity_with_remap(self.entity, entity_remap);
+        let Ok(mut entity) = world.get_entity_mut(e) else {
+            return Err(format!("Entity {e} no longer exists"));
+        };
 
-        world
-            .entity_mut(e)
+        entity
             .insert(self.old_value.clone())
             .insert(OneFrameUndoIgnore::default());
         info!("Reverted ComponentChange for entity: {}", e.index());
@@ -631,6 +958,10 @@ impl<T: Component + Clone> EditorChange for ComponentChange<T> {
         format!("ComponentChange for entity {:?}", self.entity)
     }
 
+    fn description(&self) -> String {
+        format!("Change {}", pretty_type_name::pretty_type_name::<T>())
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(ComponentChange {
             old_value: self.new_value.clone(),
@@ -702,6 +1033,10 @@ impl<T: Component + Reflect + FromReflect> EditorChange for ReflectedComponentCh
         )
     }
 
+    fn description(&self) -> String {
+        format!("Change {}", pretty_type_name::pretty_type_name::<T>())
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(ReflectedComponentChange {
             old_value: <T as FromReflect>::from_reflect(&self.new_value).unwrap(),
@@ -754,6 +1089,13 @@ impl<T: Component + Clone> EditorChange for AddedComponent<T> {
         format!("AddedComponent for entity {:?}", self.entity)
     }
 
+    fn description(&self) -> String {
+        format!(
+            "Add Component {}",
+            pretty_type_name::pretty_type_name::<T>()
+        )
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(RemovedComponent {
             entity: self.entity,
@@ -811,6 +1153,13 @@ impl<T: Component + Reflect + FromReflect> EditorChange for ReflectedAddedCompon
         format!("ReflectedAddedComponent for entity {:?}", self.entity)
     }
 
+    fn description(&self) -> String {
+        format!(
+            "Add Component {}",
+            pretty_type_name::pretty_type_name::<T>()
+        )
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(ReflectedRemovedComponent {
             old_value: <T as FromReflect>::from_reflect(&self.new_value).unwrap(),
@@ -868,6 +1217,13 @@ impl<T: Component + Clone> EditorChange for RemovedComponent<T> {
         format!("RemovedComponent for entity {:?}", self.entity)
     }
 
+    fn description(&self) -> String {
+        format!(
+            "Remove Component {}",
+            pretty_type_name::pretty_type_name::<T>()
+        )
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(AddedComponent {
             new_value: self.old_value.clone(),
@@ -932,6 +1288,13 @@ impl<T: Component + Reflect + FromReflect> EditorChange for ReflectedRemovedComp
         format!("ReflectedRemovedComponent for entity {:?}", self.entity)
     }
 
+    fn description(&self) -> String {
+        format!(
+            "Remove Component {}",
+            pretty_type_name::pretty_type_name::<T>()
+        )
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         Arc::new(ReflectedAddedComponent {
             new_value: <T as FromReflect>::from_reflect(&self.old_value).unwrap(),
@@ -940,6 +1303,212 @@ impl<T: Component + Reflect + FromReflect> EditorChange for ReflectedRemovedComp
     }
 }
 
+/// Represents a change for adding a component only known through reflection to an entity.
+///
+/// This is the type-erased counterpart of [`ReflectedAddedComponent`], for tools that pick the
+/// component from the type registry at runtime. The type must be registered with
+/// [`ReflectComponent`].
+pub struct DynamicAddedComponent {
+    /// The ID of the entity to which the component was added.
+    pub entity: Entity,
+    /// The type of the component that was added.
+    pub type_id: TypeId,
+    /// The value of the component that was added.
+    pub new_value: Box<dyn PartialReflect>,
+}
+
+impl EditorChange for DynamicAddedComponent {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        let dst = get_entity_with_remap(self.entity, entity_remap);
+        let reflect_component = registered_reflect_component(world, self.type_id)?;
+        if let Ok(mut e) = world.get_entity_mut(dst) {
+            reflect_component.remove(&mut e);
+            e.insert(OneFrameUndoIgnore::default());
+        }
+        world
+            .resource_mut::<UndoIgnoreStorage>()
+            .storage
+            .insert(dst, OneFrameUndoIgnore::default());
+
+        info!("Reverted DynamicAddedComponent for entity: {}", dst.index());
+
+        Ok(ChangeResult::Success)
+    }
+
+    fn debug_text(&self) -> String {
+        format!(
+            "DynamicAddedComponent {} for entity {:?}",
+            self.new_value.reflect_short_type_path(),
+            self.entity
+        )
+    }
+
+    fn description(&self) -> String {
+        format!("Add Component {}", self.new_value.reflect_short_type_path())
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(DynamicRemovedComponent {
+            entity: self.entity,
+            type_id: self.type_id,
+            old_value: self.new_value.to_dynamic(),
+        })
+    }
+}
+
+/// Represents a change for removing a component only known through reflection from an entity.
+///
+/// This is the type-erased counterpart of [`ReflectedRemovedComponent`]. The type must be
+/// registered with [`ReflectComponent`].
+pub struct DynamicRemovedComponent {
+    /// The ID of the entity from which the component was removed.
+    pub entity: Entity,
+    /// The type of the component that was removed.
+    pub type_id: TypeId,
+    /// The value of the component that was removed.
+    pub old_value: Box<dyn PartialReflect>,
+}
+
+impl EditorChange for DynamicRemovedComponent {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        let mut remap = vec![];
+        let dst = entity_remap.get(&self.entity).map_or_else(
+            || {
+                if world.get_entity(self.entity).is_ok() {
+                    self.entity
+                } else {
+                    let id = world.spawn_empty().id();
+                    remap.push((self.entity, id));
+                    id
+                }
+            },
+            |remapped| *remapped,
+        );
+
+        let reflect_component = registered_reflect_component(world, self.type_id)?;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let mut e = world.entity_mut(dst);
+        reflect_component.insert(&mut e, self.old_value.as_ref(), &type_registry.read());
+        e.insert(OneFrameUndoIgnore::default());
+
+        info!(
+            "Reverted DynamicRemovedComponent for entity: {}",
+            dst.index()
+        );
+
+        Ok(ChangeResult::SuccessWithRemap(remap))
+    }
+
+    fn debug_text(&self) -> String {
+        format!(
+            "DynamicRemovedComponent {} for entity {:?}",
+            self.old_value.reflect_short_type_path(),
+            self.entity
+        )
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Remove Component {}",
+            self.old_value.reflect_short_type_path()
+        )
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(DynamicAddedComponent {
+            entity: self.entity,
+            type_id: self.type_id,
+            new_value: self.old_value.to_dynamic(),
+        })
+    }
+}
+
+/// Represents a change of the value of a component only known through reflection.
+///
+/// This is the type-erased counterpart of [`ReflectedComponentChange`], for tools editing
+/// components picked from the type registry at runtime. The type must be registered with
+/// [`ReflectComponent`].
+pub struct DynamicComponentChange {
+    /// The ID of the entity whose component was changed.
+    pub entity: Entity,
+    /// The type of the component that was changed.
+    pub type_id: TypeId,
+    /// The value of the component before the change.
+    pub old_value: Box<dyn PartialReflect>,
+    /// The value of the component after the change.
+    pub new_value: Box<dyn PartialReflect>,
+}
+
+impl EditorChange for DynamicComponentChange {
+    fn revert(
+        &self,
+        world: &mut World,
+        entity_remap: &HashMap<Entity, Entity>,
+    ) -> Result<ChangeResult, String> {
+        let dst = get_entity_with_remap(self.entity, entity_remap);
+        let reflect_component = registered_reflect_component(world, self.type_id)?;
+        let Ok(mut e) = world.get_entity_mut(dst) else {
+            return Err(format!("Entity {dst} no longer exists"));
+        };
+        if !e.contains_type_id(self.type_id) {
+            return Err(format!(
+                "Entity {dst} no longer has the component {}",
+                self.old_value.reflect_short_type_path()
+            ));
+        }
+        reflect_component.apply(&mut e, self.old_value.as_ref());
+        e.insert(OneFrameUndoIgnore::default());
+
+        info!(
+            "Reverted DynamicComponentChange for entity: {}",
+            dst.index()
+        );
+
+        Ok(ChangeResult::Success)
+    }
+
+    fn debug_text(&self) -> String {
+        format!(
+            "DynamicComponentChange {} for entity {:?}",
+            self.new_value.reflect_short_type_path(),
+            self.entity
+        )
+    }
+
+    fn description(&self) -> String {
+        format!("Change {}", self.new_value.reflect_short_type_path())
+    }
+
+    fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
+        Arc::new(DynamicComponentChange {
+            entity: self.entity,
+            type_id: self.type_id,
+            old_value: self.new_value.to_dynamic(),
+            new_value: self.old_value.to_dynamic(),
+        })
+    }
+}
+
+fn registered_reflect_component(
+    world: &World,
+    type_id: TypeId,
+) -> Result<ReflectComponent, String> {
+    world
+        .resource::<AppTypeRegistry>()
+        .read()
+        .get_type_data::<ReflectComponent>(type_id)
+        .cloned()
+        .ok_or_else(|| format!("Component {type_id:?} is not registered with ReflectComponent"))
+}
+
 /// Represents a collection of multiple changes that occurred simultaneously and should be applied or reverted together.
 ///
 /// `ManyChanges` is automatically generated by the undo system to group multiple `EditorChange`
@@ -950,7 +1519,8 @@ impl<T: Component + Reflect + FromReflect> EditorChange for ReflectedRemovedComp
 ///
 /// `ManyChanges` implements the `EditorChange` trait, allowing it to be treated as a single change
 /// in the undo/redo system. When reverted, it applies all contained changes in reverse order
-/// to ensure proper undo behavior.
+/// to ensure proper undo behavior. If one of them fails, the ones already reverted are applied
+/// again, so the group is reverted either completely or not at all.
 pub struct ManyChanges {
     changes: Vec<Arc<dyn EditorChange + Send + Sync>>,
 }
@@ -962,13 +1532,27 @@ impl EditorChange for ManyChanges {
         entity_remap: &HashMap<Entity, Entity>,
     ) -> Result<ChangeResult, String> {
         let mut remap = entity_remap.clone();
-        for change in self.changes.iter() {
-            let res = change.revert(world, &remap)?;
-            match res {
-                ChangeResult::Success => {}
-                ChangeResult::SuccessWithRemap(new_remap) => {
+        for (index, change) in self.changes.iter().enumerate() {
+            match change.revert(world, &remap) {
+                Ok(ChangeResult::Success) => {}
+                Ok(ChangeResult::SuccessWithRemap(new_remap)) => {
                     remap.extend(new_remap);
                 }
+                Err(err) => {
+                    for reverted in self.changes[..index].iter().rev() {
+                        match reverted.get_inverse().revert(world, &remap) {
+                            Ok(ChangeResult::Success) => {}
+                            Ok(ChangeResult::SuccessWithRemap(new_remap)) => {
+                                remap.extend(new_remap);
+                            }
+                            Err(reapply_err) => warn!(
+                                "Unable to apply {} again: {reapply_err}",
+                                reverted.debug_text()
+                            ),
+                        }
+                    }
+                    return Err(err);
+                }
             }
         }
 
@@ -983,6 +1567,14 @@ impl EditorChange for ManyChanges {
         "ManyChanges".to_string()
     }
 
+    fn description(&self) -> String {
+        match self.changes.as_slice() {
+            [] => "No Changes".to_string(),
+            [change] => change.description(),
+            [change, rest @ ..] => format!("{} (+{} more)", change.description(), rest.len()),
+        }
+    }
+
     fn get_inverse(&self) -> Arc<dyn EditorChange + Send + Sync> {
         let mut old_changes = self.changes.clone();
         old_changes.reverse();
@@ -1579,6 +2171,93 @@ mod tests {
         assert!(app.world_mut().get_entity(test_id).is_err());
     }
 
+    #[test]
+    fn changes_of_despawned_entities_are_dropped() {
+        let mut app = configure_app();
+        app.update();
+
+        let parent = app.world_mut().spawn_empty().id();
+        let child = app.world_mut().spawn(ChildOf(parent)).id();
+        let renamed = app.world_mut().spawn(Name::new("New")).id();
+        let moved = app.world_mut().spawn(Transform::from_xyz(1., 0., 0.)).id();
+        app.world_mut().send_event(NewChange::new(ReparentedEntity {
+            entity: child,
+            old_parent: None,
+            new_parent: Some(parent),
+        }));
+        app.update();
+        app.update();
+        app.world_mut().send_event(NewChange::new(RenamedEntity {
+            entity: renamed,
+            old_name: None,
+            new_name: Some(Name::new("New")),
+        }));
+        app.world_mut()
+            .send_event(NewChange::new(ComponentChange::new(
+                moved,
+                Transform::default(),
+                Transform::from_xyz(1., 0., 0.),
+            )));
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<ChangeChain>().changes.len(), 2);
+
+        app.world_mut().despawn(child);
+        app.world_mut().despawn(renamed);
+        app.world_mut().despawn(moved);
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.world_mut().send_event(UndoRedo::Undo);
+        app.update();
+
+        let change_chain = app.world().resource::<ChangeChain>();
+        assert!(change_chain.changes.is_empty());
+        assert!(change_chain.changes_for_redo.is_empty());
+    }
+
+    #[test]
+    fn jump_stops_before_a_change_that_fails() {
+        let mut app = configure_app();
+        app.update();
+
+        let first = app.world_mut().spawn(Transform::from_xyz(1., 0., 0.)).id();
+        let grouped = app.world_mut().spawn(Transform::from_xyz(2., 0., 0.)).id();
+        let despawned = app.world_mut().spawn(Transform::from_xyz(3., 0., 0.)).id();
+        let last = app.world_mut().spawn(Transform::from_xyz(4., 0., 0.)).id();
+        let moved = |entity, x| {
+            NewChange::new(ComponentChange::new(
+                entity,
+                Transform::default(),
+                Transform::from_xyz(x, 0., 0.),
+            ))
+        };
+        app.world_mut().send_event(moved(first, 1.));
+        app.update();
+        app.update();
+        // Sent in the same frame, so they are undone as one step
+        app.world_mut().send_event(moved(grouped, 2.));
+        app.world_mut().send_event(moved(despawned, 3.));
+        app.update();
+        app.update();
+        app.world_mut().send_event(moved(last, 4.));
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<ChangeChain>().changes.len(), 3);
+
+        app.world_mut().despawn(despawned);
+        app.world_mut().send_event(UndoRedo::JumpTo(0));
+        app.update();
+
+        let world = app.world();
+        let x = |entity| world.get::<Transform>(entity).unwrap().translation.x;
+        assert_eq!(x(last), 0.);
+        // The failed step is left whole, along with the steps before it
+        assert_eq!(x(grouped), 2.);
+        assert_eq!(x(first), 1.);
+        let change_chain = world.resource::<ChangeChain>();
+        assert_eq!(change_chain.changes.len(), 2);
+        assert_eq!(change_chain.changes_for_redo.len(), 1);
+    }
+
     #[test]
     fn test_undo_with_remap() {
         let mut app = configure_app();